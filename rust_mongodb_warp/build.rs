@@ -0,0 +1,11 @@
+/// Generates gRPC server code from protobuf definitions.
+/// Bundled protoc is used unless PROTOC environment variable points to another one.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    if std::env::var_os("PROTOC").is_none() {
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    }
+    tonic_build::configure()
+        .build_client(false)
+        .compile(&["proto/movies.proto"], &["proto"])?;
+    Ok(())
+}
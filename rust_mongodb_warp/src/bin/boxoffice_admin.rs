@@ -0,0 +1,232 @@
+#![allow(clippy::needless_return)]
+
+/// Administrative command line tool.
+/// Seeds, imports and exports movies, recomputes verdicts, checks documents,
+/// creates indexes and applies migrations using the server configuration.
+use anyhow::{bail, Context};
+use clap::{Parser, Subcommand};
+use mongodb::bson::{Bson, Document};
+use rust_mongodb_warp::{config::Config, constants::*, db_layer::DB, migrations, release};
+use std::path::{Path, PathBuf};
+
+#[derive(Parser)]
+#[command(name = "boxoffice_admin", about = "Box office database maintenance")]
+struct Cli {
+    /// Configuration file. Defaults to BOXOFFICE_CONFIGURATION or 'boxoffice_configuration.yaml'.
+    #[arg(long, global = true)]
+    config: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Loads sample movies into an empty movies collection.
+    Seed {
+        #[arg(long, default_value = SEED_FILE)]
+        file: PathBuf,
+        /// Deletes existing movies first.
+        #[arg(long)]
+        drop: bool,
+    },
+    /// Imports movies from MONGODB extended JSON array, as exported by Compass or 'export'.
+    Import {
+        file: PathBuf,
+        /// Replaces movies having the same Movie Id instead of inserting.
+        #[arg(long)]
+        upsert: bool,
+    },
+    /// Exports movies as MONGODB extended JSON array to a file or standard output.
+    Export { file: Option<PathBuf> },
+    /// Recalculates verdict of every movie from its budget and collection.
+    RecomputeVerdicts {
+        /// Lists changed verdicts without saving them.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Checks every movie document can be read by the application.
+    Check,
+    /// Creates indexes used by the application.
+    EnsureIndexes,
+    /// Applies pending database migrations.
+    Migrate {
+        /// Lists pending migrations without applying them.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let config = match &cli.config {
+        Some(path) => Config::load_from(path),
+        None => Config::load(),
+    }
+    .map_err(anyhow::Error::msg)?;
+    if !DB::check_mongodb_is_running(&config.database).await? {
+        bail!("Unable to connect to database.");
+    }
+    let db = DB::init(&config.database).await?;
+
+    match cli.command {
+        Command::Seed { file, drop } => seed(&db, &file, drop).await,
+        Command::Import { file, upsert } => import(&db, &file, upsert).await,
+        Command::Export { file } => export(&db, file.as_deref()).await,
+        Command::RecomputeVerdicts { dry_run } => recompute_verdicts(&db, dry_run).await,
+        Command::Check => check(&db).await,
+        Command::EnsureIndexes => ensure_indexes(&db).await,
+        Command::Migrate { dry_run } => migrate(&db, dry_run).await,
+    }
+}
+
+/// Imports seed file, refusing to mix sample movies with existing ones unless they are dropped.
+async fn seed(db: &DB, file: &Path, drop: bool) -> anyhow::Result<()> {
+    if drop {
+        let number_of_movies_deleted = db.delete_all_movies().await?;
+        println!("{} movies deleted.", number_of_movies_deleted);
+    } else if db.count_movies().await? > 0 {
+        bail!("Movies collection is not empty, use --drop to replace existing movies.");
+    }
+    return import(db, file, false).await;
+}
+
+/// Imports every document of the file, reporting documents which can not be imported.
+async fn import(db: &DB, file: &Path, upsert: bool) -> anyhow::Result<()> {
+    let docs = read_documents(file)?;
+    let mut number_of_failures = 0;
+    for (index, doc) in docs.iter().enumerate() {
+        if let Err(e) = db.import_movie_document(doc.clone(), upsert).await {
+            number_of_failures += 1;
+            eprintln!("Document {}: {}", index, e);
+        }
+    }
+    println!(
+        "{} movies imported, {} failed.",
+        docs.len() - number_of_failures,
+        number_of_failures
+    );
+    if number_of_failures > 0 {
+        bail!("{} documents could not be imported.", number_of_failures);
+    }
+    return Ok(());
+}
+
+/// Reads MONGODB extended JSON array of documents.
+fn read_documents(file: &Path) -> anyhow::Result<Vec<Document>> {
+    let contents = std::fs::read_to_string(file)
+        .with_context(|| format!("Unable to read {}", file.display()))?;
+    let values: Vec<serde_json::Value> = serde_json::from_str(&contents)
+        .with_context(|| format!("{} is not a JSON array", file.display()))?;
+    let mut docs: Vec<Document> = Vec::new();
+    for (index, value) in values.into_iter().enumerate() {
+        match Bson::try_from(value) {
+            Ok(Bson::Document(doc)) => docs.push(doc),
+            Ok(_) => bail!("Element {} of {} is not a document.", index, file.display()),
+            Err(e) => bail!("Element {} of {}: {}", index, file.display(), e),
+        }
+    }
+    return Ok(docs);
+}
+
+async fn export(db: &DB, file: Option<&Path>) -> anyhow::Result<()> {
+    let docs = db.read_movie_documents().await?;
+    let number_of_movies = docs.len();
+    let values: Vec<serde_json::Value> = docs
+        .into_iter()
+        .map(|doc| Bson::Document(doc).into_relaxed_extjson())
+        .collect();
+    let json = serde_json::to_string_pretty(&values)?;
+    match file {
+        Some(file) => {
+            std::fs::write(file, json)
+                .with_context(|| format!("Unable to write {}", file.display()))?;
+            println!(
+                "{} movies exported to {}.",
+                number_of_movies,
+                file.display()
+            );
+        }
+        None => println!("{}", json),
+    }
+    return Ok(());
+}
+
+async fn recompute_verdicts(db: &DB, dry_run: bool) -> anyhow::Result<()> {
+    let movies = db.read_movies().await?;
+    let mut number_of_verdicts_changed = 0;
+    for movie in &movies {
+        let verdict = release::verdict(movie.status, movie.budget_crores, movie.collection_crores)?;
+        if verdict != movie.verdict {
+            number_of_verdicts_changed += 1;
+            println!(
+                "Movie ID {}: '{}' -> '{}'",
+                movie.movie_id, movie.verdict, verdict
+            );
+            if !dry_run {
+                db.update_verdict(movie, &verdict).await?;
+            }
+        }
+    }
+    println!(
+        "{} of {} verdicts {}.",
+        number_of_verdicts_changed,
+        movies.len(),
+        if dry_run { "would change" } else { "changed" }
+    );
+    return Ok(());
+}
+
+async fn check(db: &DB) -> anyhow::Result<()> {
+    let docs = db.read_movie_documents().await?;
+    let mut number_of_invalid_documents = 0;
+    for doc in &docs {
+        if let Err(e) = db.doc_to_movie(doc) {
+            number_of_invalid_documents += 1;
+            let id = doc
+                .get(ID)
+                .map(|id| id.to_string())
+                .unwrap_or_else(|| "without _id".to_string());
+            println!("Document {}: {}", id, e);
+        }
+    }
+    println!(
+        "{} of {} documents are valid.",
+        docs.len() - number_of_invalid_documents,
+        docs.len()
+    );
+    if number_of_invalid_documents > 0 {
+        bail!("{} invalid documents.", number_of_invalid_documents);
+    }
+    return Ok(());
+}
+
+async fn ensure_indexes(db: &DB) -> anyhow::Result<()> {
+    for index_name in db.ensure_indexes().await? {
+        println!("Index {} ensured.", index_name);
+    }
+    return Ok(());
+}
+
+async fn migrate(db: &DB, dry_run: bool) -> anyhow::Result<()> {
+    if dry_run {
+        let pending_migrations = migrations::pending(db).await?;
+        for migration in &pending_migrations {
+            println!(
+                "Pending migration {} '{}': {}",
+                migration.version, migration.name, migration.description
+            );
+        }
+        println!("{} pending migrations.", pending_migrations.len());
+        return Ok(());
+    }
+    let applied_migrations = migrations::run(db).await?;
+    for (migration, number_of_documents_modified) in &applied_migrations {
+        println!(
+            "Migration {} '{}' applied, {} documents modified.",
+            migration.version, migration.name, number_of_documents_modified
+        );
+    }
+    println!("{} migrations applied.", applied_migrations.len());
+    return Ok(());
+}
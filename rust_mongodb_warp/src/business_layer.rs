@@ -4,10 +4,11 @@ use rust_decimal::{prelude::*, Decimal};
 /// Function to decide verdict depending upon calculated profit or loss.
 use std::cmp::Ordering;
 
+use crate::model::{Market, Movie, MovieFinancials, TerritoryCollection};
+use crate::request_response_structs::{MovieSortKey, SortOrder, TerritoryRequest};
 use crate::{constants::*, Result};
 
 ///   Decides Verdict depending upon calculated profit or loss.
-
 /**        Following are the 10 Verdict Categories. <br/>
          1.DISASTER              (Loss > 40%) <br/>
          2.FLOP                  (Loss > 20% and Loss <= 40%) <br/>
@@ -22,67 +23,309 @@ use crate::{constants::*, Result};
 
 */
 pub fn calculate_verdict(budget_crores: Decimal, collection_crores: Decimal) -> Result<String> {
-    let mut verdict: &str = "";
-    let loss_percentage: u16;
-    let profit_percentage: u16;
+    let verdict: &str;
 
     if budget_crores.cmp(&collection_crores) == Ordering::Equal {
         verdict = BUDGET_EQUALS_COLLECTION;
-    } else if budget_crores.cmp(&collection_crores) == Ordering::Greater {
-        let loss_percentage_result = calculate_percentage(budget_crores, collection_crores);
-        if loss_percentage_result.is_ok() {
-            loss_percentage = loss_percentage_result.unwrap() as u16;
-            if loss_percentage > 40 {
+    } else {
+        let percentage = calculate_percentage(
+            budget_crores,
+            collection_crores,
+            PercentageRounding::VERDICT,
+        )?;
+        if budget_crores.cmp(&collection_crores) == Ordering::Greater {
+            // Loss percentage
+            if percentage > Decimal::from(40) {
                 verdict = LOSS_PERCENTAGE_GREATER_THAN_40;
-            } else if (loss_percentage > 20) && (loss_percentage <= 40) {
+            } else if percentage > Decimal::from(20) {
                 verdict = LOSS_PERCENTAGE_GREATER_THAN_20_BUT_LESS_THAN_OR_EQUAL_TO_40;
-            } else if (loss_percentage > 10) && (loss_percentage <= 20) {
+            } else if percentage > Decimal::from(10) {
                 verdict = LOSS_PERCENTAGE_GREATER_THAN_10_BUT_LESS_THAN_OR_EQUAL_TO_20;
-            } else if (loss_percentage > 0) && (loss_percentage <= 10) {
+            } else {
+                // Loss rounded to 0% is also average.
                 verdict = LOSS_PERCENTAGE_GREATER_THAN_0_BUT_LESS_THAN_OR_EQUAL_TO_10;
             }
-        }
-    } else if budget_crores.cmp(&collection_crores) == Ordering::Less {
-        let profit_percentage_result = calculate_percentage(budget_crores, collection_crores);
-        if profit_percentage_result.is_ok() {
-            profit_percentage = profit_percentage_result.unwrap() as u16;
-            if (profit_percentage > 0) && (profit_percentage <= 10) {
-                verdict = PROFIT_PERCENTAGE_GREATER_THAN_0_BUT_LESS_THAN_OR_EQUAL_TO_10;
-            } else if (profit_percentage > 10) && (profit_percentage <= 20) {
-                verdict = PROFIT_PERCENTAGE_GREATER_THAN_10_BUT_LESS_THAN_OR_EQUAL_TO_20;
-            } else if (profit_percentage > 20) && (profit_percentage <= 40) {
-                verdict = PROFIT_PERCENTAGE_GREATER_THAN_20_BUT_LESS_THAN_OR_EQUAL_TO_40;
-            } else if (profit_percentage > 40) && (profit_percentage <= 80) {
-                verdict = PROFIT_PERCENTAGE_GREATER_THAN_40_BUT_LESS_THAN_OR_EQUAL_TO_80;
-            } else if (profit_percentage > 80) && (profit_percentage <= 150) {
-                verdict = PROFIT_PERCENTAGE_GREATER_THAN_80_BUT_LESS_THAN_OR_EQUAL_TO_150;
-            } else if (profit_percentage > 150) && (profit_percentage <= 300) {
-                verdict = PROFIT_PERCENTAGE_GREATER_THAN_150_BUT_LESS_THAN_OR_EQUAL_TO_300;
-            } else if profit_percentage > 300 {
+        } else {
+            // Profit percentage
+            if percentage > Decimal::from(300) {
                 verdict = PROFIT_PERCENTAGE_GREATER_THAN_300;
+            } else if percentage > Decimal::from(150) {
+                verdict = PROFIT_PERCENTAGE_GREATER_THAN_150_BUT_LESS_THAN_OR_EQUAL_TO_300;
+            } else if percentage > Decimal::from(80) {
+                verdict = PROFIT_PERCENTAGE_GREATER_THAN_80_BUT_LESS_THAN_OR_EQUAL_TO_150;
+            } else if percentage > Decimal::from(40) {
+                verdict = PROFIT_PERCENTAGE_GREATER_THAN_40_BUT_LESS_THAN_OR_EQUAL_TO_80;
+            } else if percentage > Decimal::from(20) {
+                verdict = PROFIT_PERCENTAGE_GREATER_THAN_20_BUT_LESS_THAN_OR_EQUAL_TO_40;
+            } else if percentage > Decimal::from(10) {
+                verdict = PROFIT_PERCENTAGE_GREATER_THAN_10_BUT_LESS_THAN_OR_EQUAL_TO_20;
+            } else {
+                // Profit rounded to 0% is also average.
+                verdict = PROFIT_PERCENTAGE_GREATER_THAN_0_BUT_LESS_THAN_OR_EQUAL_TO_10;
             }
-        } else {
-            return Err(BoxOfficeError::ErrorinVerdictCalculation("".to_string()));
         }
     }
 
     return Ok(verdict.to_string());
 }
 
+/// Rounding applied to calculated percentage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PercentageRounding {
+    pub decimal_places: u32,
+    pub strategy: RoundingStrategy,
+}
+
+impl PercentageRounding {
+    /// Rounding used to decide verdict, whole percent with midpoint to even.
+    pub const VERDICT: Self = Self {
+        decimal_places: VERDICT_PERCENTAGE_DECIMAL_PLACES,
+        strategy: RoundingStrategy::MidpointNearestEven,
+    };
+    /// Rounding of percentage and ROI multiple included in Movie responses.
+    pub const FINANCIALS: Self = Self {
+        decimal_places: FINANCIAL_DECIMAL_PLACES,
+        strategy: RoundingStrategy::MidpointAwayFromZero,
+    };
+    /// No rounding beyond the 28 decimal places Decimal can hold.
+    pub const EXACT: Self = Self {
+        decimal_places: MAXIMUM_DECIMAL_PLACES,
+        strategy: RoundingStrategy::MidpointNearestEven,
+    };
+
+    pub fn round(&self, value: Decimal) -> Decimal {
+        return value.round_dp_with_strategy(self.decimal_places, self.strategy);
+    }
+}
+
 ///Calculates loss or profit percentage based on budget and collection of the Movie.
 /// This percentage will be used to decide Verdict.
+/// Percentage is always positive, loss or profit is decided by comparing budget and collection.
+/// Returns ErrorinVerdictCalculation if budget is not greater than zero
+/// or percentage exceeds range of Decimal (about 7.9 x 10^28).
+pub fn calculate_percentage(
+    budget_crores: Decimal,
+    collection_crores: Decimal,
+    rounding: PercentageRounding,
+) -> Result<Decimal> {
+    if budget_crores <= Decimal::ZERO {
+        return Err(BoxOfficeError::ErrorinVerdictCalculation(format!(
+            "budget '{}' must be greater than zero, collection '{}'",
+            budget_crores, collection_crores
+        )));
+    }
+    let out_of_range = || {
+        BoxOfficeError::ErrorinVerdictCalculation(format!(
+            "percentage of collection '{}' over budget '{}' is out of range",
+            collection_crores, budget_crores
+        ))
+    };
+    let hundred = Decimal::ONE_HUNDRED;
+    // Loss or profit amount.
+    let difference = collection_crores
+        .checked_sub(budget_crores)
+        .ok_or_else(out_of_range)?
+        .abs();
+    // Multiplying first keeps all digits of the quotient,
+    // dividing first is needed only when the difference is too large to be multiplied.
+    let percentage = difference
+        .checked_mul(hundred)
+        .and_then(|hundred_times_difference| hundred_times_difference.checked_div(budget_crores))
+        .or_else(|| {
+            difference
+                .checked_div(budget_crores)
+                .and_then(|ratio| ratio.checked_mul(hundred))
+        })
+        .ok_or_else(out_of_range)?;
+    return Ok(rounding.round(percentage));
+}
 
-pub fn calculate_percentage(budget_crores: Decimal, collection_crores: Decimal) -> Result<u16> {
-    let hundred = Decimal::new(100, 0);
-    if budget_crores.cmp(&collection_crores) == Ordering::Greater
-    //Loss
-    {
-        let loss_percentage = ((budget_crores - collection_crores) / budget_crores) * hundred;
-        return Ok(loss_percentage.round().to_u16().unwrap());
-    } else
-    //Profit
+/// Financial figures which can be included in Movie responses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FinancialFields {
+    pub profit: bool,
+    pub profit_percentage: bool,
+    pub roi_multiple: bool,
+    pub break_even_gap: bool,
+}
+
+impl FinancialFields {
+    /// Selects every financial figure.
+    pub fn all() -> Self {
+        Self {
+            profit: true,
+            profit_percentage: true,
+            roi_multiple: true,
+            break_even_gap: true,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Calculates selected financial figures.
+/// Amounts are exact. Percentage and multiple are rounded as per 'PercentageRounding::FINANCIALS'
+/// and are not set when budget is not greater than zero or they are out of range.
+pub fn calculate_financials(
+    budget_crores: Decimal,
+    collection_crores: Decimal,
+    fields: &FinancialFields,
+) -> MovieFinancials {
+    let profit_crores = collection_crores - budget_crores;
+    let profit_percentage = || {
+        let percentage = calculate_percentage(
+            budget_crores,
+            collection_crores,
+            PercentageRounding::FINANCIALS,
+        )
+        .ok()?;
+        if profit_crores.is_sign_negative() {
+            return Some(-percentage);
+        }
+        return Some(percentage);
+    };
+    return MovieFinancials {
+        profit_crores: fields.profit.then_some(profit_crores),
+        profit_percentage: fields.profit_percentage.then(profit_percentage).flatten(),
+        roi_multiple: fields
+            .roi_multiple
+            .then(|| collection_crores.checked_div(budget_crores))
+            .flatten()
+            .map(|roi_multiple| PercentageRounding::FINANCIALS.round(roi_multiple)),
+        break_even_gap_crores: fields
+            .break_even_gap
+            .then(|| (budget_crores - collection_crores).max(Decimal::ZERO)),
+    };
+}
+
+/// Adds selected financial figures to movie. Movie is returned unchanged when none is selected.
+pub fn include_financials(mut movie: Movie, fields: &FinancialFields) -> Movie {
+    if !fields.is_empty() {
+        movie.financials = Some(calculate_financials(
+            movie.budget_crores,
+            movie.collection_crores,
+            fields,
+        ));
+    }
+    return movie;
+}
+
+/// Sorts movies by the key in the given order.
+/// Movies must be adjusted for inflation to be sorted by adjusted amounts, movies without them sort first.
+pub fn sort_movies(movies: &mut [Movie], key: MovieSortKey, order: SortOrder) {
+    let adjusted_budget = |movie: &Movie| {
+        movie
+            .inflation_adjusted
+            .as_ref()
+            .map(|amounts| amounts.budget_crores)
+    };
+    let adjusted_collection = |movie: &Movie| {
+        movie
+            .inflation_adjusted
+            .as_ref()
+            .map(|amounts| amounts.collection_crores)
+    };
+    movies.sort_by(|a, b| {
+        let ordering = match key {
+            MovieSortKey::MovieId => a.movie_id.cmp(&b.movie_id),
+            MovieSortKey::Title => a.title.cmp(&b.title),
+            MovieSortKey::YearOfRelease => a.year_of_release.cmp(&b.year_of_release),
+            MovieSortKey::BudgetCrores => a.budget_crores.cmp(&b.budget_crores),
+            MovieSortKey::CollectionCrores => a.collection_crores.cmp(&b.collection_crores),
+            MovieSortKey::AdjustedBudgetCrores => adjusted_budget(a).cmp(&adjusted_budget(b)),
+            MovieSortKey::AdjustedCollectionCrores => {
+                adjusted_collection(a).cmp(&adjusted_collection(b))
+            }
+        };
+        match order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    });
+}
+
+/// Returns territory breakdown of the movie with verdict of every territory.
+/// Budgets and collections of the territories must add up to budget and collection of the movie.
+pub fn territory_breakdown(
+    movie: &Movie,
+    territories: &[TerritoryRequest],
+) -> Result<Vec<TerritoryCollection>> {
+    if territories.is_empty() {
+        return Ok(Vec::new());
+    }
+    let total_budget_crores: Decimal = territories.iter().map(|t| t.budget_crores).sum();
+    let total_collection_crores: Decimal = territories.iter().map(|t| t.collection_crores).sum();
+    if total_budget_crores != movie.budget_crores
+        || total_collection_crores != movie.collection_crores
     {
-        let profit_percentage = ((collection_crores - budget_crores) / budget_crores) * hundred;
-        return Ok(profit_percentage.round().to_u16().unwrap());
+        return Err(BoxOfficeError::TerritoryBreakdownMismatchError(format!(
+            "territories add up to budget {} and collection {}, movie has budget {} and collection {}",
+            total_budget_crores,
+            total_collection_crores,
+            movie.budget_crores,
+            movie.collection_crores
+        )));
+    }
+    return territories
+        .iter()
+        .map(|territory| {
+            Ok(TerritoryCollection {
+                territory: territory.territory.clone(),
+                market: territory.market,
+                budget_crores: territory.budget_crores,
+                collection_crores: territory.collection_crores,
+                verdict: calculate_verdict(territory.budget_crores, territory.collection_crores)?,
+            })
+        })
+        .collect();
+}
+
+/// Returns whether territory breakdown no longer adds up to budget and collection of the movie,
+/// as they changed after the breakdown was recorded.
+pub fn is_territory_breakdown_stale(
+    territories: &[TerritoryCollection],
+    budget_crores: Decimal,
+    collection_crores: Decimal,
+) -> bool {
+    let total_budget_crores: Decimal = territories.iter().map(|t| t.budget_crores).sum();
+    let total_collection_crores: Decimal = territories.iter().map(|t| t.collection_crores).sum();
+    return total_budget_crores != budget_crores || total_collection_crores != collection_crores;
+}
+
+/// Returns whether movie has collection in the territory and market, either may be left out.
+/// Movie without territory breakdown matches only when both are left out.
+pub fn has_territory(movie: &Movie, territory: Option<&str>, market: Option<Market>) -> bool {
+    if territory.is_none() && market.is_none() {
+        return true;
+    }
+    return movie
+        .territories
+        .iter()
+        .flatten()
+        .any(|territory_collection| {
+            territory
+                .map(|name| name == territory_collection.territory)
+                .unwrap_or(true)
+                && market
+                    .map(|market| market == territory_collection.market)
+                    .unwrap_or(true)
+        });
+}
+
+/// Returns whether verdict counts as a hit, i.e. HIT or better.
+pub fn is_hit(verdict: &str) -> bool {
+    return HIT_VERDICTS.contains(&verdict);
+}
+
+/// Returns hits as percentage of released movies, none when no movie is released yet.
+pub fn hit_ratio(hit_count: usize, released_movie_count: usize) -> Option<Decimal> {
+    if released_movie_count == 0 {
+        return None;
     }
+    return Some(PercentageRounding::FINANCIALS.round(
+        Decimal::from(hit_count as u64) * Decimal::ONE_HUNDRED
+            / Decimal::from(released_movie_count as u64),
+    ));
 }
@@ -0,0 +1,622 @@
+/// Typed async client of the Box Office REST API.
+/// Builds requests, parses response envelopes into shared model types and maps error responses
+/// to 'ClientError', retrying failed requests as configured by 'RetryPolicy'.
+use crate::constants::*;
+use crate::error::ErrorResponse;
+use crate::model::{
+    ConsumerPriceIndex, ExchangeRate, Movie, OutboxEntry, SearchResults, Suggestion, Webhook,
+    WebhookDelivery,
+};
+use crate::request_response_structs::*;
+use reqwest::{header::CONTENT_TYPE, Method, StatusCode, Url};
+use serde::{de::DeserializeOwned, Serialize};
+use std::time::Duration;
+use thiserror::Error;
+
+pub type ClientResult<T> = std::result::Result<T, ClientError>;
+
+/// Errors returned by the client.
+/// Application errors mirror 'BoxOfficeError' and carry the error message sent by the server.
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("{0}")]
+    ValidationError(String),
+    #[error("{0}")]
+    BadCreateMovieRequestError(String),
+    #[error("{0}")]
+    BadDeleteMovieRequestError(String),
+    #[error("{0}")]
+    InvalidDeleteMovieRequestError(String),
+    #[error("{0}")]
+    InvalidUpdateMovieRequestError(String),
+    #[error("{0}")]
+    InvalidFieldInCreateMovieRequestError(String),
+    #[error("{0}")]
+    BadUpdateMovieRequestError(String),
+    #[error("{0}")]
+    ErrorinVerdictCalculation(String),
+    #[error("{0}")]
+    MongoDBError(String),
+    #[error("{0}")]
+    InvalidMovieIDError(String),
+    #[error("{0}")]
+    InvalidDocumentError(String),
+    #[error("{0}")]
+    InvalidCreateDocumentError(String),
+    #[error("{0}")]
+    BadCreateWebhookRequestError(String),
+    #[error("{0}")]
+    InvalidWebhookIDError(String),
+    #[error("{0}")]
+    InvalidDeadLetterIDError(String),
+    #[error("{0}")]
+    InvalidOutboxIDError(String),
+    #[error("{0}")]
+    WebhookNotFoundError(String),
+    #[error("{0}")]
+    DeadLetterNotFoundError(String),
+    #[error("{0}")]
+    MovieNotFoundError(String),
+    #[error("{0}")]
+    MovieAlreadyExistsError(String),
+    #[error("{0}")]
+    InvalidIncludeError(String),
+    #[error("{0}")]
+    InvalidQuery(String),
+    #[error("{0}")]
+    InvalidCurrencyError(String),
+    #[error("{0}")]
+    BadExchangeRatesRequestError(String),
+    #[error("{0}")]
+    ExchangeRateNotFoundError(String),
+    #[error("{0}")]
+    AmountConversionError(String),
+    #[error("{0}")]
+    ExchangeRatesFileError(String),
+    #[error("{0}")]
+    BadCpiRequestError(String),
+    #[error("{0}")]
+    CpiNotFoundError(String),
+    #[error("{0}")]
+    InflationAdjustmentError(String),
+    #[error("{0}")]
+    CpiFileError(String),
+    #[error("{0}")]
+    BadCollectionEntryRequestError(String),
+    #[error("{0}")]
+    InvalidCollectionEntryIDError(String),
+    #[error("{0}")]
+    CollectionEntryNotFoundError(String),
+    #[error("{0}")]
+    CollectionEntryConflictError(String),
+    #[error("{0}")]
+    BadTerritoriesRequestError(String),
+    #[error("{0}")]
+    TerritoryBreakdownMismatchError(String),
+    #[error("{0}")]
+    InvalidStatusTransitionError(String),
+    #[error("{0}")]
+    ReleaseLifecycleError(String),
+    #[error("{0}")]
+    BadProductionHouseRequestError(String),
+    #[error("{0}")]
+    InvalidProductionHouseIDError(String),
+    #[error("{0}")]
+    ProductionHouseNotFoundError(String),
+    #[error("{0}")]
+    ProductionHouseConflictError(String),
+    #[error("{0}")]
+    BadPersonRequestError(String),
+    #[error("{0}")]
+    InvalidPersonIDError(String),
+    #[error("{0}")]
+    PersonNotFoundError(String),
+    #[error("{0}")]
+    PersonConflictError(String),
+    #[error("{0}")]
+    BadCreditsRequestError(String),
+    #[error("{0}")]
+    UnknownCreditedPersonError(String),
+    #[error("{0}")]
+    InvalidMetadataError(String),
+    #[error("{0}")]
+    BadFranchiseRequestError(String),
+    #[error("{0}")]
+    InvalidFranchiseIDError(String),
+    #[error("{0}")]
+    FranchiseNotFoundError(String),
+    #[error("{0}")]
+    FranchiseConflictError(String),
+    #[error("{0}")]
+    UnknownInstalmentError(String),
+    #[error("{0}")]
+    BadRelationsRequestError(String),
+    #[error("{0}")]
+    InvalidMovieRelationError(String),
+    #[error("{0}")]
+    NotAcceptableError(String),
+    #[error("{0}")]
+    UnsupportedMediaTypeError(String),
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    InvalidRequestBody(String),
+    #[error("{0}")]
+    MethodNotAllowed(String),
+    #[error("Server error ({status}): '{message}'")]
+    ServerError { status: u16, message: String },
+    #[error("Invalid request: '{0}'")]
+    InvalidRequest(String),
+    #[error("Request failed: '{0}'")]
+    RequestError(#[from] reqwest::Error),
+    #[error("Unexpected response ({status}): '{message}'")]
+    InvalidResponse { status: u16, message: String },
+}
+
+/// Retry policy of the client.
+/// Requests which could not be sent are retried, whatever the method. Timeouts and
+/// 429, 502, 503 and 504 responses are retried only for GET, PUT and DELETE requests,
+/// as repeating them has the same effect as sending them once.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Attempts including the first one, 1 disables retries.
+    pub maximum_attempts: u32,
+    pub initial_backoff: Duration,
+    pub maximum_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            maximum_attempts: DEFAULT_CLIENT_MAXIMUM_ATTEMPTS,
+            initial_backoff: Duration::from_millis(DEFAULT_CLIENT_INITIAL_BACKOFF_MILLISECONDS),
+            maximum_backoff: Duration::from_millis(DEFAULT_CLIENT_MAXIMUM_BACKOFF_MILLISECONDS),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy which sends every request once.
+    pub fn none() -> Self {
+        Self {
+            maximum_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before the given retry, doubling from initial backoff up to maximum backoff.
+    fn backoff(&self, retry: u32) -> Duration {
+        let multiplier = 2u32.saturating_pow(retry.saturating_sub(1));
+        return self
+            .initial_backoff
+            .saturating_mul(multiplier)
+            .min(self.maximum_backoff);
+    }
+}
+
+/// Webhook returned on registration along with the secret used to sign its payloads.
+#[derive(Debug, Clone)]
+pub struct CreatedWebhook {
+    pub webhook: Webhook,
+    pub secret: String,
+}
+
+/// Client of the Box Office REST API.
+#[derive(Debug, Clone)]
+pub struct BoxOfficeClient {
+    http_client: reqwest::Client,
+    base_url: Url,
+    retry_policy: RetryPolicy,
+}
+
+impl BoxOfficeClient {
+    /// Creates client for the server at 'base_url', e.g. 'http://127.0.0.1:3030'.
+    pub fn new(base_url: &str) -> ClientResult<Self> {
+        let base_url = Url::parse(base_url)
+            .map_err(|e| ClientError::InvalidRequest(format!("{}: {}", base_url, e)))?;
+        let http_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(DEFAULT_CLIENT_REQUEST_TIMEOUT_SECONDS))
+            .build()?;
+        return Ok(Self {
+            http_client,
+            base_url,
+            retry_policy: RetryPolicy::default(),
+        });
+    }
+
+    /// Replaces retry policy.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        return self;
+    }
+
+    /// Replaces HTTP client, e.g. to configure timeouts, proxies or TLS.
+    pub fn with_http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = http_client;
+        return self;
+    }
+
+    /// Creates movie, verdict is calculated by the server.
+    pub async fn create_movie(&self, request: &CreateMovieRequest) -> ClientResult<Movie> {
+        return self
+            .create_movie_with(request, &MovieQuery::default())
+            .await;
+    }
+
+    /// Creates movie, returning it with the figures and amounts requested by 'query'.
+    pub async fn create_movie_with(
+        &self,
+        request: &CreateMovieRequest,
+        query: &MovieQuery,
+    ) -> ClientResult<Movie> {
+        let url = with_query(self.url(&MOVIES_API_PATH, &["createmovie"]), query)?;
+        let response: CreateMovieResponse = self.send(Method::POST, url, Some(request)).await?;
+        return Ok(response.data);
+    }
+
+    /// Returns all movies.
+    pub async fn read_movies(&self) -> ClientResult<Vec<Movie>> {
+        return self.read_movies_with(&MovieQuery::default()).await;
+    }
+
+    /// Returns movies filtered, sorted and extended with figures and amounts as requested by 'query',
+    /// e.g. 'MovieQuery { genre: Some("drama".to_string()), currency: Some("USD".to_string()), ..Default::default() }'.
+    pub async fn read_movies_with(&self, query: &MovieQuery) -> ClientResult<Vec<Movie>> {
+        let url = with_query(self.url(&MOVIES_API_PATH, &["readmovies"]), query)?;
+        let response: ReadMoviesResponse = self.send(Method::GET, url, None::<&()>).await?;
+        return Ok(response.data);
+    }
+
+    /// Updates movie, verdict is recalculated by the server.
+    pub async fn update_movie(
+        &self,
+        movie_id: u8,
+        request: &UpdateMovieRequest,
+    ) -> ClientResult<Movie> {
+        return self
+            .update_movie_with(movie_id, request, &MovieQuery::default())
+            .await;
+    }
+
+    /// Updates movie, returning it with the figures and amounts requested by 'query'.
+    pub async fn update_movie_with(
+        &self,
+        movie_id: u8,
+        request: &UpdateMovieRequest,
+        query: &MovieQuery,
+    ) -> ClientResult<Movie> {
+        let url = with_query(
+            self.url(&MOVIES_API_PATH, &["updatemovie", &movie_id.to_string()]),
+            query,
+        )?;
+        let response: UpdateMovieResponse = self.send(Method::PUT, url, Some(request)).await?;
+        return Ok(response.data);
+    }
+
+    /// Searches movies by title, production house and cast, most relevant first.
+    pub async fn search_movies(&self, query: &SearchQuery) -> ClientResult<SearchResults> {
+        let url = with_query(self.url(&SEARCH_API_PATH, &[]), query)?;
+        return self.send(Method::GET, url, None::<&()>).await;
+    }
+
+    /// Returns titles, production houses and people completing the partly typed query.
+    pub async fn suggest(&self, query: &SearchQuery) -> ClientResult<Vec<Suggestion>> {
+        let url = with_query(self.url(&SEARCH_API_PATH, &["suggest"]), query)?;
+        return self.send(Method::GET, url, None::<&()>).await;
+    }
+
+    /// Deletes movie.
+    pub async fn delete_movie(&self, movie_id: u8) -> ClientResult<()> {
+        let url = self.url(&MOVIES_API_PATH, &["deletemovie", &movie_id.to_string()]);
+        let _: DeleteMovieResponse = self.send(Method::DELETE, url, None::<&()>).await?;
+        return Ok(());
+    }
+
+    /// Registers webhook. Secret is generated by the server unless given in the request.
+    pub async fn create_webhook(
+        &self,
+        request: &CreateWebhookRequest,
+    ) -> ClientResult<CreatedWebhook> {
+        let url = self.url(&WEBHOOKS_API_PATH, &["createwebhook"]);
+        let response: CreateWebhookResponse = self.send(Method::POST, url, Some(request)).await?;
+        return Ok(CreatedWebhook {
+            webhook: response.data,
+            secret: response.secret,
+        });
+    }
+
+    /// Returns all webhooks.
+    pub async fn read_webhooks(&self) -> ClientResult<Vec<Webhook>> {
+        let url = self.url(&WEBHOOKS_API_PATH, &["readwebhooks"]);
+        let response: ReadWebhooksResponse = self.send(Method::GET, url, None::<&()>).await?;
+        return Ok(response.data);
+    }
+
+    /// Deletes webhook along with its pending deliveries.
+    pub async fn delete_webhook(&self, id: &str) -> ClientResult<()> {
+        let url = self.url(&WEBHOOKS_API_PATH, &["deletewebhook", id]);
+        let _: DeleteWebhookResponse = self.send(Method::DELETE, url, None::<&()>).await?;
+        return Ok(());
+    }
+
+    /// Returns every delivery attempt made to the webhook, newest first.
+    pub async fn read_webhook_deliveries(&self, id: &str) -> ClientResult<Vec<WebhookDelivery>> {
+        let url = self.url(&WEBHOOKS_API_PATH, &["readdeliveries", id]);
+        let response: ReadWebhookDeliveriesResponse =
+            self.send(Method::GET, url, None::<&()>).await?;
+        return Ok(response.data);
+    }
+
+    /// Returns deliveries which ran out of attempts.
+    pub async fn read_dead_letters(&self) -> ClientResult<Vec<OutboxEntry>> {
+        let url = self.url(&WEBHOOKS_API_PATH, &["readdeadletters"]);
+        let response: ReadDeadLettersResponse = self.send(Method::GET, url, None::<&()>).await?;
+        return Ok(response.data);
+    }
+
+    /// Schedules dead letter for delivery with a fresh set of attempts.
+    pub async fn retry_dead_letter(&self, id: &str) -> ClientResult<()> {
+        let url = self.url(&WEBHOOKS_API_PATH, &["retrydeadletter", id]);
+        let _: RetryDeadLetterResponse = self.send(Method::POST, url, None::<&()>).await?;
+        return Ok(());
+    }
+
+    /// Returns consumer price index of every year, oldest first.
+    pub async fn read_cpi(&self) -> ClientResult<Vec<ConsumerPriceIndex>> {
+        let url = self.url(&CPI_API_PATH, &["readcpi"]);
+        let response: ReadCpiResponse = self.send(Method::GET, url, None::<&()>).await?;
+        return Ok(response.data);
+    }
+
+    /// Adds consumer price index of years, replacing index of the same year.
+    pub async fn upsert_cpi(&self, cpi: Vec<ConsumerPriceIndex>) -> ClientResult<()> {
+        let url = self.url(&CPI_API_PATH, &["upsertcpi"]);
+        let request = UpsertCpiRequest { cpi };
+        let _: UpsertCpiResponse = self.send(Method::PUT, url, Some(&request)).await?;
+        return Ok(());
+    }
+
+    /// Returns all exchange rates ordered by currency and date.
+    pub async fn read_exchange_rates(&self) -> ClientResult<Vec<ExchangeRate>> {
+        let url = self.url(&EXCHANGE_RATES_API_PATH, &["readexchangerates"]);
+        let response: ReadExchangeRatesResponse = self.send(Method::GET, url, None::<&()>).await?;
+        return Ok(response.data);
+    }
+
+    /// Adds exchange rates, replacing rates of the same currency and date.
+    pub async fn upsert_exchange_rates(&self, rates: Vec<ExchangeRate>) -> ClientResult<()> {
+        let url = self.url(&EXCHANGE_RATES_API_PATH, &["upsertexchangerates"]);
+        let request = UpsertExchangeRatesRequest { rates };
+        let _: UpsertExchangeRatesResponse = self.send(Method::PUT, url, Some(&request)).await?;
+        return Ok(());
+    }
+
+    /// Checks whether server is alive.
+    pub async fn liveness(&self) -> ClientResult<()> {
+        let url = self.url(&HEALTH_API_PATH, &["live"]);
+        let _: HealthResponse = self.send(Method::GET, url, None::<&()>).await?;
+        return Ok(());
+    }
+
+    /// Returns whether server accepts requests. Not retried, as a server
+    /// which is shutting down stays not ready.
+    pub async fn readiness(&self) -> ClientResult<bool> {
+        let url = self.url(&HEALTH_API_PATH, &["ready"]);
+        let (status, body) = self
+            .send_with_retries(Method::GET, url, None, &RetryPolicy::none())
+            .await?;
+        if status == StatusCode::SERVICE_UNAVAILABLE {
+            return Ok(false);
+        }
+        let _: HealthResponse = parse_response(status, &body)?;
+        return Ok(true);
+    }
+
+    /// Joins API path and segments to base url, escaping segments.
+    fn url(&self, api_path: &[&str], segments: &[&str]) -> Url {
+        let mut url = self.base_url.clone();
+        if let Ok(mut path_segments) = url.path_segments_mut() {
+            path_segments
+                .pop_if_empty()
+                .extend(api_path)
+                .extend(segments);
+        }
+        return url;
+    }
+
+    /// Sends request, retrying as per retry policy, and parses response envelope.
+    async fn send<B: Serialize, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        url: Url,
+        body: Option<&B>,
+    ) -> ClientResult<T> {
+        let body = match body {
+            Some(body) => Some(
+                serde_json::to_vec(body).map_err(|e| ClientError::InvalidRequest(e.to_string()))?,
+            ),
+            None => None,
+        };
+        let (status, response_body) = self
+            .send_with_retries(method, url, body, &self.retry_policy)
+            .await?;
+        return parse_response(status, &response_body);
+    }
+
+    async fn send_with_retries(
+        &self,
+        method: Method,
+        url: Url,
+        body: Option<Vec<u8>>,
+        retry_policy: &RetryPolicy,
+    ) -> ClientResult<(StatusCode, bytes::Bytes)> {
+        let is_idempotent = matches!(method, Method::GET | Method::PUT | Method::DELETE);
+        let mut attempt = 1;
+        loop {
+            let mut request = self.http_client.request(method.clone(), url.clone());
+            if let Some(body) = &body {
+                request = request
+                    .header(CONTENT_TYPE, JSON_CONTENT_TYPE)
+                    .body(body.clone());
+            }
+            let result = match request.send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    response.bytes().await.map(|bytes| (status, bytes))
+                }
+                Err(e) => Err(e),
+            };
+            let is_retryable = match &result {
+                Ok((status, _)) => is_idempotent && is_retryable_status(*status),
+                Err(e) => e.is_connect() || (is_idempotent && e.is_timeout()),
+            };
+            if !is_retryable || attempt >= retry_policy.maximum_attempts {
+                return Ok(result?);
+            }
+            let backoff = retry_policy.backoff(attempt);
+            log::warn!(
+                "{} {} failed on attempt {}, retrying in {:?}.",
+                method,
+                url,
+                attempt,
+                backoff
+            );
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Sets query string of url to the url-encoded 'query', leaving out unset parameters.
+fn with_query<Q: Serialize>(mut url: Url, query: &Q) -> ClientResult<Url> {
+    let query = serde_urlencoded::to_string(query)
+        .map_err(|e| ClientError::InvalidRequest(e.to_string()))?;
+    if !query.is_empty() {
+        url.set_query(Some(&query));
+    }
+    return Ok(url);
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    return matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    );
+}
+
+/// Parses success envelope, or maps error response to 'ClientError'.
+fn parse_response<T: DeserializeOwned>(status: StatusCode, body: &[u8]) -> ClientResult<T> {
+    if status.is_success() {
+        return serde_json::from_slice(body).map_err(|e| ClientError::InvalidResponse {
+            status: status.as_u16(),
+            message: e.to_string(),
+        });
+    }
+    // Database operation failures are reported with 'message' instead of 'error_message'.
+    if let Ok(error_response) = serde_json::from_slice::<ErrorResponse>(body) {
+        return Err(to_client_error(status, error_response));
+    }
+    if let Ok(failure_response) =
+        serde_json::from_slice::<DatabaseOperationFailedErrorResponse>(body)
+    {
+        return Err(ClientError::ServerError {
+            status: status.as_u16(),
+            message: failure_response.message,
+        });
+    }
+    return Err(ClientError::InvalidResponse {
+        status: status.as_u16(),
+        message: String::from_utf8_lossy(body).to_string(),
+    });
+}
+
+fn to_client_error(status: StatusCode, error_response: ErrorResponse) -> ClientError {
+    let message = error_response.error_message;
+    match error_response.error_code.as_str() {
+        VALIDATION_ERROR_CODE => ClientError::ValidationError(message),
+        BAD_CREATE_MOVIE_REQUEST_ERROR_CODE => ClientError::BadCreateMovieRequestError(message),
+        BAD_DELETE_MOVIE_REQUEST_ERROR_CODE => ClientError::BadDeleteMovieRequestError(message),
+        INVALID_DELETE_MOVIE_REQUEST_ERROR_CODE => {
+            ClientError::InvalidDeleteMovieRequestError(message)
+        }
+        INVALID_UPDATE_MOVIE_REQUEST_ERROR_CODE => {
+            ClientError::InvalidUpdateMovieRequestError(message)
+        }
+        INVALID_FIELD_IN_CREATE_MOVIE_REQUEST_ERROR_CODE => {
+            ClientError::InvalidFieldInCreateMovieRequestError(message)
+        }
+        BAD_UPDATE_MOVIE_REQUEST_ERROR_CODE => ClientError::BadUpdateMovieRequestError(message),
+        ERROR_IN_VERDICT_CALCULATION_CODE => ClientError::ErrorinVerdictCalculation(message),
+        MONGODB_ERROR_CODE => ClientError::MongoDBError(message),
+        INVALID_MOVIE_ID_ERROR_CODE => ClientError::InvalidMovieIDError(message),
+        INVALID_DOCUMENT_ERROR_CODE => ClientError::InvalidDocumentError(message),
+        INVALID_CREATE_DOCUMENT_ERROR_CODE => ClientError::InvalidCreateDocumentError(message),
+        BAD_CREATE_WEBHOOK_REQUEST_ERROR_CODE => ClientError::BadCreateWebhookRequestError(message),
+        INVALID_WEBHOOK_ID_ERROR_CODE => ClientError::InvalidWebhookIDError(message),
+        INVALID_DEAD_LETTER_ID_ERROR_CODE => ClientError::InvalidDeadLetterIDError(message),
+        INVALID_OUTBOX_ID_ERROR_CODE => ClientError::InvalidOutboxIDError(message),
+        WEBHOOK_NOT_FOUND_ERROR_CODE => ClientError::WebhookNotFoundError(message),
+        DEAD_LETTER_NOT_FOUND_ERROR_CODE => ClientError::DeadLetterNotFoundError(message),
+        MOVIE_NOT_FOUND_ERROR_CODE => ClientError::MovieNotFoundError(message),
+        MOVIE_ALREADY_EXISTS_ERROR_CODE => ClientError::MovieAlreadyExistsError(message),
+        INVALID_INCLUDE_ERROR_CODE => ClientError::InvalidIncludeError(message),
+        INVALID_QUERY_ERROR_CODE => ClientError::InvalidQuery(message),
+        INVALID_CURRENCY_ERROR_CODE => ClientError::InvalidCurrencyError(message),
+        BAD_EXCHANGE_RATES_REQUEST_ERROR_CODE => ClientError::BadExchangeRatesRequestError(message),
+        EXCHANGE_RATE_NOT_FOUND_ERROR_CODE => ClientError::ExchangeRateNotFoundError(message),
+        AMOUNT_CONVERSION_ERROR_CODE => ClientError::AmountConversionError(message),
+        EXCHANGE_RATES_FILE_ERROR_CODE => ClientError::ExchangeRatesFileError(message),
+        BAD_CPI_REQUEST_ERROR_CODE => ClientError::BadCpiRequestError(message),
+        CPI_NOT_FOUND_ERROR_CODE => ClientError::CpiNotFoundError(message),
+        INFLATION_ADJUSTMENT_ERROR_CODE => ClientError::InflationAdjustmentError(message),
+        CPI_FILE_ERROR_CODE => ClientError::CpiFileError(message),
+        BAD_COLLECTION_ENTRY_REQUEST_ERROR_CODE => {
+            ClientError::BadCollectionEntryRequestError(message)
+        }
+        INVALID_COLLECTION_ENTRY_ID_ERROR_CODE => {
+            ClientError::InvalidCollectionEntryIDError(message)
+        }
+        COLLECTION_ENTRY_NOT_FOUND_ERROR_CODE => ClientError::CollectionEntryNotFoundError(message),
+        COLLECTION_ENTRY_CONFLICT_ERROR_CODE => ClientError::CollectionEntryConflictError(message),
+        BAD_TERRITORIES_REQUEST_ERROR_CODE => ClientError::BadTerritoriesRequestError(message),
+        TERRITORY_BREAKDOWN_MISMATCH_ERROR_CODE => {
+            ClientError::TerritoryBreakdownMismatchError(message)
+        }
+        INVALID_STATUS_TRANSITION_ERROR_CODE => ClientError::InvalidStatusTransitionError(message),
+        RELEASE_LIFECYCLE_ERROR_CODE => ClientError::ReleaseLifecycleError(message),
+        BAD_PRODUCTION_HOUSE_REQUEST_ERROR_CODE => {
+            ClientError::BadProductionHouseRequestError(message)
+        }
+        INVALID_PRODUCTION_HOUSE_ID_ERROR_CODE => {
+            ClientError::InvalidProductionHouseIDError(message)
+        }
+        PRODUCTION_HOUSE_NOT_FOUND_ERROR_CODE => ClientError::ProductionHouseNotFoundError(message),
+        PRODUCTION_HOUSE_CONFLICT_ERROR_CODE => ClientError::ProductionHouseConflictError(message),
+        BAD_PERSON_REQUEST_ERROR_CODE => ClientError::BadPersonRequestError(message),
+        INVALID_PERSON_ID_ERROR_CODE => ClientError::InvalidPersonIDError(message),
+        PERSON_NOT_FOUND_ERROR_CODE => ClientError::PersonNotFoundError(message),
+        PERSON_CONFLICT_ERROR_CODE => ClientError::PersonConflictError(message),
+        BAD_CREDITS_REQUEST_ERROR_CODE => ClientError::BadCreditsRequestError(message),
+        UNKNOWN_CREDITED_PERSON_ERROR_CODE => ClientError::UnknownCreditedPersonError(message),
+        INVALID_METADATA_ERROR_CODE => ClientError::InvalidMetadataError(message),
+        BAD_FRANCHISE_REQUEST_ERROR_CODE => ClientError::BadFranchiseRequestError(message),
+        INVALID_FRANCHISE_ID_ERROR_CODE => ClientError::InvalidFranchiseIDError(message),
+        FRANCHISE_NOT_FOUND_ERROR_CODE => ClientError::FranchiseNotFoundError(message),
+        FRANCHISE_CONFLICT_ERROR_CODE => ClientError::FranchiseConflictError(message),
+        UNKNOWN_INSTALMENT_ERROR_CODE => ClientError::UnknownInstalmentError(message),
+        BAD_RELATIONS_REQUEST_ERROR_CODE => ClientError::BadRelationsRequestError(message),
+        INVALID_MOVIE_RELATION_ERROR_CODE => ClientError::InvalidMovieRelationError(message),
+        NOT_ACCEPTABLE_ERROR_CODE => ClientError::NotAcceptableError(message),
+        UNSUPPORTED_MEDIA_TYPE_ERROR_CODE => ClientError::UnsupportedMediaTypeError(message),
+        NOT_FOUND_ERROR_CODE => ClientError::NotFound(message),
+        INVALID_REQUEST_BODY_ERROR_CODE => ClientError::InvalidRequestBody(message),
+        METHOD_NOT_ALLOWED_ERROR_CODE => ClientError::MethodNotAllowed(message),
+        _ => ClientError::ServerError {
+            status: status.as_u16(),
+            message,
+        },
+    }
+}
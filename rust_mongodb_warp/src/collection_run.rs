@@ -0,0 +1,246 @@
+/// Theatrical run of a movie built from dated collection entries.
+/// Entries of a day, weekend or week are recorded per territory. An entry is covered by a longer
+/// period entry of the same territory which overlaps it, e.g. days of a recorded week,
+/// remaining entries are rolled up into collection of the movie.
+use crate::business_layer::{calculate_percentage, PercentageRounding};
+use crate::error::BoxOfficeError;
+use crate::model::{
+    CollectionEntry, CollectionPeriod, CollectionPoint, CollectionRun, WeekCollection,
+};
+use crate::{constants::*, Result};
+use mongodb::bson::DateTime;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+impl CollectionPeriod {
+    /// Returns number of days covered by the period.
+    pub fn days(&self) -> i64 {
+        match self {
+            CollectionPeriod::Day => 1,
+            CollectionPeriod::Weekend => DAYS_IN_WEEKEND,
+            CollectionPeriod::Week => DAYS_IN_WEEK,
+        }
+    }
+}
+
+impl fmt::Display for CollectionPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let period = match self {
+            CollectionPeriod::Day => "day",
+            CollectionPeriod::Weekend => "weekend",
+            CollectionPeriod::Week => "week",
+        };
+        return write!(f, "{}", period);
+    }
+}
+
+impl FromStr for CollectionPeriod {
+    type Err = BoxOfficeError;
+
+    fn from_str(period: &str) -> Result<Self> {
+        match period {
+            "day" => Ok(CollectionPeriod::Day),
+            "weekend" => Ok(CollectionPeriod::Weekend),
+            "week" => Ok(CollectionPeriod::Week),
+            _ => Err(BoxOfficeError::InvalidDocumentError(format!(
+                "unknown collection period '{}'",
+                period
+            ))),
+        }
+    }
+}
+
+/// Returns number of days from 1970-01-01 to the 'YYYY-MM-DD' date.
+/// Returns BadCollectionEntryRequestError if there is no such date, e.g. 2023-02-30.
+pub fn day_number(date: &str) -> Result<i64> {
+    let date_time = DateTime::parse_rfc3339_str(format!("{}T00:00:00Z", date)).map_err(|_| {
+        BoxOfficeError::BadCollectionEntryRequestError(format!("no such date '{}'", date))
+    })?;
+    return Ok(date_time.timestamp_millis().div_euclid(MILLISECONDS_IN_DAY));
+}
+
+/// Returns 'YYYY-MM-DD' date of the day number.
+pub fn date_of_day(day: i64) -> String {
+    let date_time = DateTime::from_millis(day * MILLISECONDS_IN_DAY);
+    let rfc3339 = date_time.try_to_rfc3339_string().unwrap_or_default();
+    return rfc3339.chars().take(10).collect();
+}
+
+/// First and last day covered by an entry.
+fn span(entry: &CollectionEntry) -> Result<(i64, i64)> {
+    let first_day = day_number(&entry.date)?;
+    return Ok((first_day, first_day + entry.period.days() - 1));
+}
+
+/// Returns day numbers covered by a period starting on the 'YYYY-MM-DD' date.
+/// Stored with the entry so that a unique index rejects entries of the same period and territory
+/// which share a day.
+pub fn covered_days(date: &str, period: CollectionPeriod) -> Result<Vec<i64>> {
+    let first_day = day_number(date)?;
+    return Ok((first_day..first_day + period.days()).collect());
+}
+
+fn overlaps(first: (i64, i64), second: (i64, i64)) -> bool {
+    first.0 <= second.1 && second.0 <= first.1
+}
+
+/// Returns entries which are not covered by a longer period entry of the same territory.
+/// Weeks are taken first, then weekends and days which do not overlap them.
+pub fn rolled_up_entries(entries: &[CollectionEntry]) -> Result<Vec<&CollectionEntry>> {
+    let mut ordered_entries: Vec<&CollectionEntry> = entries.iter().collect();
+    ordered_entries.sort_by(|first, second| {
+        second
+            .period
+            .cmp(&first.period)
+            .then_with(|| first.date.cmp(&second.date))
+    });
+    let mut taken_spans: BTreeMap<&str, Vec<(i64, i64)>> = BTreeMap::new();
+    let mut rolled_up: Vec<&CollectionEntry> = Vec::new();
+    for entry in ordered_entries {
+        let entry_span = span(entry)?;
+        let territory_spans = taken_spans.entry(entry.territory.as_str()).or_default();
+        if territory_spans
+            .iter()
+            .any(|taken_span| overlaps(*taken_span, entry_span))
+        {
+            continue;
+        }
+        territory_spans.push(entry_span);
+        rolled_up.push(entry);
+    }
+    rolled_up.sort_by(|first, second| {
+        first
+            .date
+            .cmp(&second.date)
+            .then_with(|| first.period.cmp(&second.period))
+    });
+    return Ok(rolled_up);
+}
+
+/// Returns collection of the movie rolled up from its entries.
+pub fn total_collection(entries: &[CollectionEntry]) -> Result<Decimal> {
+    return Ok(rolled_up_entries(entries)?
+        .iter()
+        .map(|entry| entry.collection_crores)
+        .sum());
+}
+
+/// Builds run of the movie from its entries.
+/// Run starts on the first day of the earliest entry.
+pub fn collection_run(movie_id: u8, entries: &[CollectionEntry]) -> Result<CollectionRun> {
+    let rolled_up = rolled_up_entries(entries)?;
+    let first_day = match rolled_up.first() {
+        Some(entry) => day_number(&entry.date)?,
+        None => {
+            return Ok(CollectionRun {
+                movie_id,
+                total_collection_crores: Decimal::ZERO,
+                opening_weekend_crores: None,
+                series: Vec::new(),
+                weeks: Vec::new(),
+            })
+        }
+    };
+
+    // Time series, rolled up entries summed over territories.
+    let mut series: BTreeMap<(&str, CollectionPeriod), Decimal> = BTreeMap::new();
+    for entry in &rolled_up {
+        *series.entry((&entry.date, entry.period)).or_default() += entry.collection_crores;
+    }
+
+    // Weeks counted from the first day, entries belong to the week in which they start.
+    let mut week_collections: BTreeMap<i64, Decimal> = BTreeMap::new();
+    for entry in &rolled_up {
+        let week_index = (day_number(&entry.date)? - first_day) / DAYS_IN_WEEK;
+        *week_collections.entry(week_index).or_default() += entry.collection_crores;
+    }
+    let last_week_index = week_collections
+        .keys()
+        .next_back()
+        .copied()
+        .unwrap_or_default();
+    let mut weeks: Vec<WeekCollection> = Vec::new();
+    let mut previous_collection: Option<Decimal> = None;
+    for week_index in 0..=last_week_index {
+        let collection_crores = week_collections
+            .get(&week_index)
+            .copied()
+            .unwrap_or_default();
+        let drop_percentage = match previous_collection {
+            Some(previous) if previous > Decimal::ZERO => {
+                Some(drop_percentage(previous, collection_crores)?)
+            }
+            _ => None,
+        };
+        weeks.push(WeekCollection {
+            week: week_index as u32 + 1,
+            start_date: date_of_day(first_day + week_index * DAYS_IN_WEEK),
+            collection_crores,
+            drop_percentage,
+        });
+        previous_collection = Some(collection_crores);
+    }
+
+    return Ok(CollectionRun {
+        movie_id,
+        total_collection_crores: rolled_up.iter().map(|entry| entry.collection_crores).sum(),
+        opening_weekend_crores: opening_weekend(entries, first_day)?,
+        series: series
+            .into_iter()
+            .map(|((date, period), collection_crores)| CollectionPoint {
+                date: date.to_string(),
+                period,
+                collection_crores,
+            })
+            .collect(),
+        weeks,
+    });
+}
+
+/// Returns collection of the first three days of the run summed over territories.
+/// Territory counts with its weekend entry starting on the first day, otherwise with its day entries
+/// when all three days are recorded. Not known when no territory counts.
+fn opening_weekend(entries: &[CollectionEntry], first_day: i64) -> Result<Option<Decimal>> {
+    let mut territories: BTreeMap<&str, Vec<&CollectionEntry>> = BTreeMap::new();
+    for entry in entries {
+        territories
+            .entry(entry.territory.as_str())
+            .or_default()
+            .push(entry);
+    }
+    let mut opening_weekend_crores: Option<Decimal> = None;
+    for territory_entries in territories.values() {
+        let mut weekend_crores: Option<Decimal> = None;
+        let mut opening_days: BTreeMap<i64, Decimal> = BTreeMap::new();
+        for entry in territory_entries {
+            let entry_day = day_number(&entry.date)?;
+            match entry.period {
+                CollectionPeriod::Weekend if entry_day == first_day => {
+                    weekend_crores = Some(entry.collection_crores)
+                }
+                CollectionPeriod::Day if entry_day - first_day < DAYS_IN_WEEKEND => {
+                    opening_days.insert(entry_day, entry.collection_crores);
+                }
+                _ => {}
+            }
+        }
+        if weekend_crores.is_none() && opening_days.len() as i64 == DAYS_IN_WEEKEND {
+            weekend_crores = Some(opening_days.values().sum());
+        }
+        if let Some(weekend_crores) = weekend_crores {
+            *opening_weekend_crores.get_or_insert(Decimal::ZERO) += weekend_crores;
+        }
+    }
+    return Ok(opening_weekend_crores);
+}
+
+/// Returns drop from previous to current collection as percentage of previous, negative when it grew.
+fn drop_percentage(previous: Decimal, current: Decimal) -> Result<Decimal> {
+    let percentage = calculate_percentage(previous, current, PercentageRounding::FINANCIALS)?;
+    if current > previous {
+        return Ok(-percentage);
+    }
+    return Ok(percentage);
+}
@@ -0,0 +1,343 @@
+/// Application configuration.
+/// Configuration is read from a YAML file, missing fields fall back to defaults.
+use crate::constants::*;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Root configuration structure.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub tracing: TracingConfig,
+    pub webhooks: WebhookConfig,
+    pub grpc: GrpcConfig,
+    pub exchange_rates: ExchangeRatesConfig,
+    pub inflation: InflationConfig,
+    pub metadata: MetadataConfig,
+}
+
+/// Configuration of the HTTP server.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ServerConfig {
+    pub host: IpAddr,
+    pub port: u16,
+    /// Maximum time to wait for in-flight requests after shutdown is requested.
+    pub drain_timeout_seconds: u64,
+    /// Time between readiness flipping to not-ready and the listener closing.
+    /// Gives load balancers a chance to stop routing new requests to this instance.
+    pub readiness_grace_period_seconds: u64,
+    /// TLS termination. Server listens on plain HTTP when absent.
+    pub tls: Option<TlsConfig>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: IpAddr::V4(Ipv4Addr::from(DEFAULT_HOST)),
+            port: DEFAULT_PORT,
+            drain_timeout_seconds: DEFAULT_DRAIN_TIMEOUT_SECONDS,
+            readiness_grace_period_seconds: DEFAULT_READINESS_GRACE_PERIOD_SECONDS,
+            tls: None,
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Returns address on which server listens.
+    pub fn socket_address(&self) -> SocketAddr {
+        SocketAddr::new(self.host, self.port)
+    }
+
+    pub fn drain_timeout(&self) -> Duration {
+        Duration::from_secs(self.drain_timeout_seconds)
+    }
+
+    pub fn readiness_grace_period(&self) -> Duration {
+        Duration::from_secs(self.readiness_grace_period_seconds)
+    }
+}
+
+/// TLS configuration of the HTTP server.
+/// Certificate and key are PEM files, they are reloaded when changed on disk.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TlsConfig {
+    pub certificate_path: PathBuf,
+    pub private_key_path: PathBuf,
+    /// PEM file with CA certificates used to verify client certificates.
+    #[serde(default)]
+    pub client_ca_path: Option<PathBuf>,
+    #[serde(default)]
+    pub client_auth: ClientAuth,
+    #[serde(default = "default_certificate_reload_interval_seconds")]
+    pub certificate_reload_interval_seconds: u64,
+    /// Time a client has to complete the TLS handshake after connecting.
+    #[serde(default = "default_handshake_timeout_seconds")]
+    pub handshake_timeout_seconds: u64,
+}
+
+impl TlsConfig {
+    pub fn certificate_reload_interval(&self) -> Duration {
+        Duration::from_secs(self.certificate_reload_interval_seconds)
+    }
+
+    pub fn handshake_timeout(&self) -> Duration {
+        Duration::from_secs(self.handshake_timeout_seconds)
+    }
+}
+
+fn default_certificate_reload_interval_seconds() -> u64 {
+    DEFAULT_CERTIFICATE_RELOAD_INTERVAL_SECONDS
+}
+
+fn default_handshake_timeout_seconds() -> u64 {
+    DEFAULT_TLS_HANDSHAKE_TIMEOUT_SECONDS
+}
+
+/// Client certificate verification mode.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientAuth {
+    /// Client certificates are not requested.
+    #[default]
+    None,
+    /// Client certificate is verified if presented.
+    Optional,
+    /// Client must present a certificate signed by 'client_ca_path'.
+    Required,
+}
+
+/// Configuration of the MONGODB connection.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    pub connection_string: String,
+    pub database_name: String,
+    pub tls: Option<DatabaseTlsConfig>,
+    /// Publish movie events from MONGODB change streams instead of write operations.
+    /// Needs MONGODB replica set.
+    pub change_streams: bool,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            connection_string: CONNECTION_STRING.to_string(),
+            database_name: DB_NAME.to_string(),
+            tls: None,
+            change_streams: false,
+        }
+    }
+}
+
+/// TLS configuration of the MONGODB connection.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct DatabaseTlsConfig {
+    /// PEM file with CA certificates used to verify the MONGODB server.
+    pub ca_file_path: Option<PathBuf>,
+    /// PEM file containing client certificate and private key.
+    pub certificate_key_file_path: Option<PathBuf>,
+    pub allow_invalid_certificates: bool,
+}
+
+/// Configuration of OpenTelemetry tracing.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct TracingConfig {
+    pub exporter: TracingExporter,
+    pub service_name: String,
+    /// Base URL of OTLP/HTTP collector, spans are sent to "/v1/traces" under it.
+    /// Used by 'otlp' exporter.
+    pub otlp_endpoint: String,
+    /// File to which spans are appended as JSON lines, used by 'json_file' exporter.
+    pub json_file_path: PathBuf,
+}
+
+impl Default for TracingConfig {
+    fn default() -> Self {
+        Self {
+            exporter: TracingExporter::None,
+            service_name: DEFAULT_SERVICE_NAME.to_string(),
+            otlp_endpoint: DEFAULT_OTLP_ENDPOINT.to_string(),
+            json_file_path: PathBuf::from(DEFAULT_TRACES_FILE),
+        }
+    }
+}
+
+/// Destination of exported spans.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TracingExporter {
+    /// Tracing is disabled.
+    #[default]
+    None,
+    Otlp,
+    JsonFile,
+}
+
+/// Configuration of the gRPC server.
+/// gRPC is served on its own port next to the HTTP server.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct GrpcConfig {
+    pub enabled: bool,
+    pub host: IpAddr,
+    pub port: u16,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: IpAddr::V4(Ipv4Addr::from(DEFAULT_HOST)),
+            port: DEFAULT_GRPC_PORT,
+        }
+    }
+}
+
+impl GrpcConfig {
+    /// Returns address on which gRPC server listens.
+    pub fn socket_address(&self) -> SocketAddr {
+        SocketAddr::new(self.host, self.port)
+    }
+}
+
+/// Configuration of the exchange rate table used to convert amounts to INR crores.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ExchangeRatesConfig {
+    /// JSON file from which exchange rates are loaded and to which changed rates are saved.
+    /// Rates are kept only in memory when absent.
+    pub file_path: Option<PathBuf>,
+}
+
+impl Default for ExchangeRatesConfig {
+    fn default() -> Self {
+        Self {
+            file_path: Some(PathBuf::from(DEFAULT_EXCHANGE_RATES_FILE)),
+        }
+    }
+}
+
+/// Configuration of the consumer price index table used to adjust amounts for inflation.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct InflationConfig {
+    /// JSON file from which CPI is loaded and to which changed CPI is saved.
+    /// CPI is kept only in memory when absent.
+    pub cpi_file_path: Option<PathBuf>,
+}
+
+impl Default for InflationConfig {
+    fn default() -> Self {
+        Self {
+            cpi_file_path: Some(PathBuf::from(DEFAULT_CPI_FILE)),
+        }
+    }
+}
+
+/// Vocabularies of movie metadata.
+/// Genres, languages, certification and tags of movies must be in them, compared ignoring case.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct MetadataConfig {
+    pub genres: Vec<String>,
+    pub languages: Vec<String>,
+    /// CBFC certifications.
+    pub certifications: Vec<String>,
+    /// Any tag is accepted when empty.
+    pub tags: Vec<String>,
+}
+
+impl Default for MetadataConfig {
+    fn default() -> Self {
+        Self {
+            genres: DEFAULT_GENRES
+                .iter()
+                .map(|genre| genre.to_string())
+                .collect(),
+            languages: DEFAULT_LANGUAGES
+                .iter()
+                .map(|language| language.to_string())
+                .collect(),
+            certifications: DEFAULT_CERTIFICATIONS
+                .iter()
+                .map(|certification| certification.to_string())
+                .collect(),
+            tags: Vec::new(),
+        }
+    }
+}
+
+/// Configuration of outbound webhook delivery.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct WebhookConfig {
+    /// Interval at which outbox is checked for due deliveries.
+    pub poll_interval_seconds: u64,
+    /// Delivery is moved to dead letters after this many failed attempts.
+    pub maximum_attempts: u32,
+    /// Delay before the first retry, doubled after every failed attempt.
+    pub initial_backoff_seconds: u64,
+    pub maximum_backoff_seconds: u64,
+    pub request_timeout_seconds: u64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_seconds: DEFAULT_WEBHOOK_POLL_INTERVAL_SECONDS,
+            maximum_attempts: DEFAULT_WEBHOOK_MAXIMUM_ATTEMPTS,
+            initial_backoff_seconds: DEFAULT_WEBHOOK_INITIAL_BACKOFF_SECONDS,
+            maximum_backoff_seconds: DEFAULT_WEBHOOK_MAXIMUM_BACKOFF_SECONDS,
+            request_timeout_seconds: DEFAULT_WEBHOOK_REQUEST_TIMEOUT_SECONDS,
+        }
+    }
+}
+
+impl WebhookConfig {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_secs(self.poll_interval_seconds)
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_seconds)
+    }
+
+    /// Returns delay before the next attempt after 'attempts' failed attempts.
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let exponent = attempts.saturating_sub(1).min(31);
+        let backoff_seconds = self
+            .initial_backoff_seconds
+            .saturating_mul(1u64 << exponent)
+            .min(self.maximum_backoff_seconds);
+        Duration::from_secs(backoff_seconds)
+    }
+}
+
+impl Config {
+    /// Loads configuration.
+    /// File path is taken from 'BOXOFFICE_CONFIGURATION' environment variable,
+    /// otherwise 'boxoffice_configuration.yaml' in the working directory is used.
+    /// If the file does not exist, default configuration is returned.
+    pub fn load() -> Result<Self, String> {
+        let path = std::env::var(CONFIGURATION_FILE_ENVIRONMENT_VARIABLE)
+            .unwrap_or_else(|_| CONFIGURATION_FILE.to_string());
+        return Self::load_from(&path);
+    }
+
+    /// Loads configuration from the given YAML file.
+    /// If the file does not exist, default configuration is returned.
+    pub fn load_from(path: &str) -> Result<Self, String> {
+        if !Path::new(path).exists() {
+            return Ok(Config::default());
+        }
+        let contents = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        return serde_yaml::from_str(&contents).map_err(|e| format!("{}: {}", path, e));
+    }
+}
@@ -5,6 +5,12 @@ pub const DB_NAME: &str = "BoxOffice";
 #[doc(hidden)]
 pub const COLLECTION: &str = "Movies";
 pub const PINGCOMMAND: &str = "ping";
+pub const HELLO_COMMAND: &str = "hello";
+pub const ADMIN_DATABASE: &str = "admin";
+// Fields of 'hello' reply telling replica set members and mongos apart from standalone servers.
+pub const SET_NAME: &str = "setName";
+pub const MSG: &str = "msg";
+pub const ISDBGRID: &str = "isdbgrid";
 
 pub const ID: &str = "_id";
 pub const MOVIEID: &str = "MovieID";
@@ -14,6 +20,33 @@ pub const YEAR_OF_RELEASE: &str = "YearOfRelease";
 pub const BUDGET_CRORES: &str = "BudgetCrores";
 pub const COLLECTION_CRORES: &str = "CollectionCrores";
 pub const VERDICT: &str = "Verdict";
+pub const CURRENCY: &str = "Currency";
+pub const UNIT: &str = "Unit";
+pub const REPORTED_BUDGET: &str = "ReportedBudget";
+pub const REPORTED_COLLECTION: &str = "ReportedCollection";
+pub const EXCHANGE_RATE: &str = "ExchangeRate";
+pub const EXCHANGE_RATE_DATE: &str = "ExchangeRateDate";
+pub const TERRITORIES: &str = "Territories";
+pub const MARKET: &str = "Market";
+pub const RELEASE_DATE: &str = "ReleaseDate";
+pub const PRODUCTION_HOUSE_ID: &str = "ProductionHouseID";
+pub const GENRES: &str = "Genres";
+pub const LANGUAGES: &str = "Languages";
+pub const CERTIFICATION: &str = "Certification";
+pub const RUNTIME_MINUTES: &str = "RuntimeMinutes";
+pub const TAGS: &str = "Tags";
+pub const CREDITS: &str = "Credits";
+pub const PERSON_ID: &str = "PersonID";
+pub const ROLE: &str = "Role";
+pub const CHARACTER: &str = "Character";
+/// Person Id of credits, for queries on movies.
+pub const CREDITS_PERSON_ID: &str = "Credits.PersonID";
+/// Names of credited people, for text search on movies.
+pub const CREDITS_NAME: &str = "Credits.Name";
+pub const RELATIONS: &str = "Relations";
+pub const KIND: &str = "Kind";
+/// Movie Id of relations, for queries on movies related to a movie.
+pub const RELATIONS_MOVIE_ID: &str = "Relations.MovieID";
 
 pub const BUDGET_EQUALS_COLLECTION: &str = "AVERAGE";
 pub const LOSS_PERCENTAGE_GREATER_THAN_40: &str = "DISASTER";
@@ -28,9 +61,18 @@ pub const PROFIT_PERCENTAGE_GREATER_THAN_40_BUT_LESS_THAN_OR_EQUAL_TO_80: &str =
 pub const PROFIT_PERCENTAGE_GREATER_THAN_80_BUT_LESS_THAN_OR_EQUAL_TO_150: &str = "SUPER HIT";
 pub const PROFIT_PERCENTAGE_GREATER_THAN_150_BUT_LESS_THAN_OR_EQUAL_TO_300: &str = "BLOCKBUSTER";
 pub const PROFIT_PERCENTAGE_GREATER_THAN_300: &str = "ALL TIME BLOCKBUSTER";
+pub const NOT_RELEASED_VERDICT: &str = "NOT RELEASED";
+/// Verdicts counted as hits in track records of production houses and filmographies.
+pub const HIT_VERDICTS: [&str; 4] = [
+    PROFIT_PERCENTAGE_GREATER_THAN_40_BUT_LESS_THAN_OR_EQUAL_TO_80,
+    PROFIT_PERCENTAGE_GREATER_THAN_80_BUT_LESS_THAN_OR_EQUAL_TO_150,
+    PROFIT_PERCENTAGE_GREATER_THAN_150_BUT_LESS_THAN_OR_EQUAL_TO_300,
+    PROFIT_PERCENTAGE_GREATER_THAN_300,
+];
 
 pub const MINIMUM_YEAR_OF_RELEASE: u16 = 1989;
-pub const MAXIMUM_YEAR_OF_RELEASE: u16 = 2023;
+/// Upcoming movies can be registered up to this many years after the current year.
+pub const MAXIMUM_YEARS_UNTIL_RELEASE: u16 = 5;
 pub const ZERO: u8 = 0;
 
 pub const DB_OPERATION_FAILED: &str = "Database operation failed";
@@ -38,4 +80,380 @@ pub const BUDGET_GREATER_THAN_ZERO_ERROR: &str = "Budget should be greater than
 pub const COLLECTION_GREATER_THAN_ZERO_ERROR: &str = "Collection should be greater than zero";
 pub const MOVIEID_GREATER_THAN_ZERO_ERROR: &str = "Movie id should be greater than zero";
 pub const YEAR_OF_RELEASE_BETWEEN_MIN_AND_MAX: &str =
-    "Year Of Release should be between 1989 AND 2023";
+    "Year Of Release should be between 1989 AND 5 years after the current year";
+pub const COLLECTION_BEFORE_RELEASE_ERROR: &str =
+    "Collection should be zero until the movie is released";
+pub const INVALID_CURRENCY_ERROR: &str = "Currency should be ISO 4217 code, e.g. INR or USD";
+pub const INVALID_DATE_ERROR: &str = "Date should be YYYY-MM-DD";
+pub const EXCHANGE_RATE_GREATER_THAN_ZERO_ERROR: &str = "Exchange rate should be greater than zero";
+pub const CPI_GREATER_THAN_ZERO_ERROR: &str = "Consumer price index should be greater than zero";
+pub const CPI_YEAR_BETWEEN_MIN_AND_MAX: &str = "CPI year should be between 1900 AND 2100";
+pub const COLLECTION_NOT_NEGATIVE_ERROR: &str = "Collection should not be negative";
+pub const INVALID_TERRITORY_ERROR: &str =
+    "Territory should be lowercase letters, digits, '_' or '-', e.g. domestic or overseas";
+pub const TERRITORY_BUDGET_GREATER_THAN_ZERO_ERROR: &str =
+    "Budget of every territory should be greater than zero";
+pub const DUPLICATE_TERRITORY_ERROR: &str = "Every territory should be given only once";
+pub const PRODUCTION_HOUSE_NAME_ERROR: &str =
+    "Production house name and aliases should contain letters or digits";
+pub const SOURCE_PRODUCTION_HOUSES_REQUIRED_ERROR: &str =
+    "At least one production house should be merged";
+pub const RUNTIME_BETWEEN_MIN_AND_MAX: &str = "Runtime should be between 1 AND 600 minutes";
+pub const METADATA_VALUE_ERROR: &str =
+    "Genres, languages, certification and tags should not be blank";
+pub const PERSON_NAME_ERROR: &str = "Person name should contain letters or digits";
+pub const DUPLICATE_CREDIT_ERROR: &str = "Every person should be credited only once in a role";
+pub const FRANCHISE_NAME_ERROR: &str = "Franchise name should contain letters or digits";
+pub const DUPLICATE_INSTALMENT_ERROR: &str = "Every movie should be an instalment only once";
+pub const DUPLICATE_RELATION_ERROR: &str = "Every movie should be related only once in a kind";
+pub const SEARCH_QUERY_ERROR: &str = "Search query should contain letters or digits";
+pub const SEARCH_QUERY_LENGTH_ERROR: &str = "Search query should be at most 100 characters";
+pub const SEARCH_LIMIT_ERROR: &str = "Limit should be between 1 AND 50";
+pub const PATCH_AMOUNTS_REQUIRED_ERROR: &str =
+    "budgetCrores and collectionCrores are required when currency or unit is changed";
+
+pub const CONFIGURATION_FILE: &str = "boxoffice_configuration.yaml";
+pub const CONFIGURATION_FILE_ENVIRONMENT_VARIABLE: &str = "BOXOFFICE_CONFIGURATION";
+pub const LOG_CONFIGURATION_FILE: &str = "boxoffice_json_log_configuration.yaml";
+pub const DEFAULT_HOST: [u8; 4] = [0, 0, 0, 0];
+pub const DEFAULT_PORT: u16 = 8080;
+pub const DEFAULT_DRAIN_TIMEOUT_SECONDS: u64 = 30;
+pub const DEFAULT_READINESS_GRACE_PERIOD_SECONDS: u64 = 0;
+pub const DEFAULT_CERTIFICATE_RELOAD_INTERVAL_SECONDS: u64 = 30;
+pub const TLS_ACCEPT_BACKLOG: usize = 128;
+pub const DEFAULT_TLS_HANDSHAKE_TIMEOUT_SECONDS: u64 = 10;
+pub const ALPN_HTTP2: &[u8] = b"h2";
+pub const ALPN_HTTP1_1: &[u8] = b"http/1.1";
+
+pub const SERVER_READY: &str = "Server is ready to accept requests.";
+pub const SERVER_NOT_READY: &str = "Server is shutting down.";
+pub const SERVER_ALIVE: &str = "Server is alive.";
+pub const NO_CERTIFICATE_FOUND: &str = "No certificate found in PEM file";
+pub const NO_PRIVATE_KEY_FOUND: &str = "No private key found in PEM file";
+pub const CLIENT_CA_PATH_REQUIRED: &str =
+    "'client_ca_path' is required to verify client certificates";
+
+pub const X_REQUEST_ID: &str = "x-request-id";
+pub const MAXIMUM_REQUEST_ID_LENGTH: usize = 128;
+pub const REQUEST_ID_LOG_FIELD: &str = "request_id";
+pub const REQUEST_JSON_ENCODER: &str = "request_json";
+pub const SENSITIVE_HEADERS: [&str; 5] = [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+];
+pub const REDACTED: &str = "[REDACTED]";
+pub const NON_PRINTABLE_HEADER: &str = "[NON PRINTABLE]";
+
+pub const DEFAULT_SERVICE_NAME: &str = "boxoffice";
+pub const DEFAULT_OTLP_ENDPOINT: &str = "http://localhost:4318";
+pub const DEFAULT_TRACES_FILE: &str = "log/boxoffice_traces.json";
+pub const TRACER_NAME: &str = "rust_mongodb_warp";
+pub const SERVICE_NAME_RESOURCE: &str = "service.name";
+pub const OTLP_EXPORT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+pub const EVENT_CHANNEL_CAPACITY: usize = 1024;
+pub const CHANGE_STREAM_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+pub const MOVIE_CREATED_EVENT: &str = "created";
+pub const MOVIE_UPDATED_EVENT: &str = "updated";
+pub const MOVIE_DELETED_EVENT: &str = "deleted";
+
+pub const WEBHOOKS_COLLECTION: &str = "Webhooks";
+pub const WEBHOOK_OUTBOX_COLLECTION: &str = "WebhookOutbox";
+pub const WEBHOOK_DELIVERIES_COLLECTION: &str = "WebhookDeliveries";
+pub const COLLECTION_ENTRIES_COLLECTION: &str = "MovieCollections";
+pub const PRODUCTION_HOUSES_COLLECTION: &str = "ProductionHouses";
+pub const PEOPLE_COLLECTION: &str = "People";
+pub const FRANCHISES_COLLECTION: &str = "Franchises";
+
+pub const URL: &str = "Url";
+pub const EVENTS: &str = "Events";
+pub const SECRET: &str = "Secret";
+pub const ACTIVE: &str = "Active";
+pub const CREATED_AT: &str = "CreatedAt";
+pub const WEBHOOK_ID: &str = "WebhookID";
+pub const OUTBOX_ID: &str = "OutboxID";
+pub const EVENT_TYPE: &str = "EventType";
+pub const PAYLOAD: &str = "Payload";
+pub const STATUS: &str = "Status";
+pub const ATTEMPTS: &str = "Attempts";
+pub const ATTEMPT: &str = "Attempt";
+pub const NEXT_ATTEMPT_AT: &str = "NextAttemptAt";
+pub const LAST_ERROR: &str = "LastError";
+pub const SUCCESS: &str = "Success";
+pub const STATUS_CODE: &str = "StatusCode";
+pub const ERROR: &str = "Error";
+pub const DURATION_MS: &str = "DurationMs";
+pub const DELIVERED_AT: &str = "DeliveredAt";
+pub const DATE: &str = "Date";
+pub const PERIOD: &str = "Period";
+pub const TERRITORY: &str = "Territory";
+/// Day numbers covered by a collection entry, unique per movie, territory and period.
+pub const DAYS: &str = "Days";
+pub const ALIASES: &str = "Aliases";
+/// Normalized name and aliases of a production house, unique across production houses.
+pub const NAME_KEYS: &str = "NameKeys";
+/// Movie Ids of a franchise in instalment order, a movie belongs to one franchise at most.
+pub const MOVIE_IDS: &str = "MovieIDs";
+
+pub const OUTBOX_PENDING: &str = "PENDING";
+pub const OUTBOX_DELIVERED: &str = "DELIVERED";
+pub const OUTBOX_DEAD: &str = "DEAD";
+
+pub const WEBHOOK_EVENT_CREATED: &str = "created";
+pub const WEBHOOK_EVENT_UPDATED: &str = "updated";
+pub const WEBHOOK_EVENT_DELETED: &str = "deleted";
+pub const WEBHOOK_EVENT_VERDICT_CHANGED: &str = "verdict_changed";
+pub const WEBHOOK_EVENTS: [&str; 4] = [
+    WEBHOOK_EVENT_CREATED,
+    WEBHOOK_EVENT_UPDATED,
+    WEBHOOK_EVENT_DELETED,
+    WEBHOOK_EVENT_VERDICT_CHANGED,
+];
+
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+pub const MESSAGEPACK_CONTENT_TYPE: &str = "application/msgpack";
+pub const X_MESSAGEPACK_CONTENT_TYPE: &str = "application/x-msgpack";
+pub const CBOR_CONTENT_TYPE: &str = "application/cbor";
+pub const RON_CONTENT_TYPE: &str = "application/ron";
+pub const CSV_CONTENT_TYPE: &str = "text/csv";
+pub const ANY_MEDIA_TYPE: &str = "*/*";
+pub const ANY_APPLICATION_MEDIA_TYPE: &str = "application/*";
+pub const ANY_TEXT_MEDIA_TYPE: &str = "text/*";
+pub const ACCEPT_HEADER: &str = "accept";
+pub const CONTENT_TYPE_HEADER: &str = "content-type";
+pub const DEPRECATION_HEADER: &str = "deprecation";
+pub const SUNSET_HEADER: &str = "sunset";
+pub const DEPRECATION: &str = "true";
+/// v1 movie routes are removed after this date.
+pub const MOVIES_V1_SUNSET: &str = "Fri, 31 Dec 2027 23:59:59 GMT";
+pub const MOVIES_V1_SUCCESSOR_LINK: &str = "</boxoffice/v2/movies>; rel=\"successor-version\"";
+pub const MOVIES_V2_PATH: &str = "/boxoffice/v2/movies";
+pub const PRODUCTION_HOUSES_V2_PATH: &str = "/boxoffice/v2/productionhouses";
+pub const PEOPLE_V2_PATH: &str = "/boxoffice/v2/people";
+pub const FRANCHISES_V2_PATH: &str = "/boxoffice/v2/franchises";
+/// Separates list items in a CSV cell.
+pub const CSV_LIST_SEPARATOR: &str = ";";
+pub const WEBHOOK_EVENT_HEADER: &str = "X-BoxOffice-Event";
+pub const WEBHOOK_DELIVERY_HEADER: &str = "X-BoxOffice-Delivery";
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-BoxOffice-Signature";
+pub const WEBHOOK_SIGNATURE_PREFIX: &str = "sha256=";
+pub const WEBHOOK_DELETED_ERROR: &str = "Webhook no longer exists";
+pub const MINIMUM_WEBHOOK_SECRET_LENGTH: u64 = 16;
+pub const INVALID_WEBHOOK_EVENT_ERROR: &str =
+    "Events should be one or more of created, updated, deleted, verdict_changed";
+
+pub const DEFAULT_WEBHOOK_POLL_INTERVAL_SECONDS: u64 = 5;
+pub const DEFAULT_WEBHOOK_MAXIMUM_ATTEMPTS: u32 = 8;
+pub const DEFAULT_WEBHOOK_INITIAL_BACKOFF_SECONDS: u64 = 10;
+pub const DEFAULT_WEBHOOK_MAXIMUM_BACKOFF_SECONDS: u64 = 3600;
+pub const DEFAULT_WEBHOOK_REQUEST_TIMEOUT_SECONDS: u64 = 10;
+/// Claimed outbox entry is not picked again by another dispatcher until this lease expires.
+pub const WEBHOOK_CLAIM_LEASE_SECONDS: u64 = 60;
+
+pub const GRAPHQL_ENDPOINT: &str = "/graphql";
+/// Maximum nesting of GraphQL queries.
+pub const GRAPHQL_DEPTH_LIMIT: usize = 10;
+
+pub const DEFAULT_GRPC_PORT: u16 = 50051;
+
+pub const MIGRATIONS_COLLECTION: &str = "Migrations";
+pub const VERSION: &str = "Version";
+pub const NAME: &str = "Name";
+pub const APPLIED_AT: &str = "AppliedAt";
+pub const SEED_FILE: &str = "HELP/DatabaseHelp/Movies.json";
+
+/// Error codes returned in 'error_code' of error responses, one for every application error
+/// and one for every error raised by warp itself.
+pub const VALIDATION_ERROR_CODE: &str = "ValidationError";
+pub const BAD_CREATE_MOVIE_REQUEST_ERROR_CODE: &str = "BadCreateMovieRequestError";
+pub const BAD_DELETE_MOVIE_REQUEST_ERROR_CODE: &str = "BadDeleteMovieRequestError";
+pub const INVALID_DELETE_MOVIE_REQUEST_ERROR_CODE: &str = "InvalidDeleteMovieRequestError";
+pub const INVALID_UPDATE_MOVIE_REQUEST_ERROR_CODE: &str = "InvalidUpdateMovieRequestError";
+pub const INVALID_FIELD_IN_CREATE_MOVIE_REQUEST_ERROR_CODE: &str =
+    "InvalidFieldInCreateMovieRequestError";
+pub const BAD_UPDATE_MOVIE_REQUEST_ERROR_CODE: &str = "BadUpdateMovieRequestError";
+pub const ERROR_IN_VERDICT_CALCULATION_CODE: &str = "ErrorinVerdictCalculation";
+pub const MONGODB_ERROR_CODE: &str = "MongoDBError";
+pub const INVALID_MOVIE_ID_ERROR_CODE: &str = "InvalidMovieIDError";
+pub const INVALID_DOCUMENT_ERROR_CODE: &str = "InvalidDocumentError";
+pub const INVALID_CREATE_DOCUMENT_ERROR_CODE: &str = "InvalidCreateDocumentError";
+pub const BAD_CREATE_WEBHOOK_REQUEST_ERROR_CODE: &str = "BadCreateWebhookRequestError";
+pub const INVALID_WEBHOOK_ID_ERROR_CODE: &str = "InvalidWebhookIDError";
+pub const INVALID_DEAD_LETTER_ID_ERROR_CODE: &str = "InvalidDeadLetterIDError";
+pub const INVALID_OUTBOX_ID_ERROR_CODE: &str = "InvalidOutboxIDError";
+pub const WEBHOOK_NOT_FOUND_ERROR_CODE: &str = "WebhookNotFoundError";
+pub const DEAD_LETTER_NOT_FOUND_ERROR_CODE: &str = "DeadLetterNotFoundError";
+pub const INTERNAL_SERVER_ERROR_CODE: &str = "InternalServerError";
+pub const NOT_FOUND_ERROR_CODE: &str = "NotFound";
+pub const INVALID_REQUEST_BODY_ERROR_CODE: &str = "InvalidRequestBody";
+pub const METHOD_NOT_ALLOWED_ERROR_CODE: &str = "MethodNotAllowed";
+pub const MOVIE_NOT_FOUND_ERROR_CODE: &str = "MovieNotFoundError";
+pub const MOVIE_ALREADY_EXISTS_ERROR_CODE: &str = "MovieAlreadyExistsError";
+pub const INVALID_INCLUDE_ERROR_CODE: &str = "InvalidIncludeError";
+pub const INVALID_QUERY_ERROR_CODE: &str = "InvalidQuery";
+pub const INVALID_CURRENCY_ERROR_CODE: &str = "InvalidCurrencyError";
+pub const BAD_EXCHANGE_RATES_REQUEST_ERROR_CODE: &str = "BadExchangeRatesRequestError";
+pub const EXCHANGE_RATE_NOT_FOUND_ERROR_CODE: &str = "ExchangeRateNotFoundError";
+pub const AMOUNT_CONVERSION_ERROR_CODE: &str = "AmountConversionError";
+pub const EXCHANGE_RATES_FILE_ERROR_CODE: &str = "ExchangeRatesFileError";
+pub const BAD_CPI_REQUEST_ERROR_CODE: &str = "BadCpiRequestError";
+pub const CPI_NOT_FOUND_ERROR_CODE: &str = "CpiNotFoundError";
+pub const INFLATION_ADJUSTMENT_ERROR_CODE: &str = "InflationAdjustmentError";
+pub const CPI_FILE_ERROR_CODE: &str = "CpiFileError";
+pub const BAD_COLLECTION_ENTRY_REQUEST_ERROR_CODE: &str = "BadCollectionEntryRequestError";
+pub const INVALID_COLLECTION_ENTRY_ID_ERROR_CODE: &str = "InvalidCollectionEntryIDError";
+pub const COLLECTION_ENTRY_NOT_FOUND_ERROR_CODE: &str = "CollectionEntryNotFoundError";
+pub const COLLECTION_ENTRY_CONFLICT_ERROR_CODE: &str = "CollectionEntryConflictError";
+pub const BAD_TERRITORIES_REQUEST_ERROR_CODE: &str = "BadTerritoriesRequestError";
+pub const TERRITORY_BREAKDOWN_MISMATCH_ERROR_CODE: &str = "TerritoryBreakdownMismatchError";
+pub const INVALID_STATUS_TRANSITION_ERROR_CODE: &str = "InvalidStatusTransitionError";
+pub const RELEASE_LIFECYCLE_ERROR_CODE: &str = "ReleaseLifecycleError";
+pub const BAD_PRODUCTION_HOUSE_REQUEST_ERROR_CODE: &str = "BadProductionHouseRequestError";
+pub const INVALID_PRODUCTION_HOUSE_ID_ERROR_CODE: &str = "InvalidProductionHouseIDError";
+pub const PRODUCTION_HOUSE_NOT_FOUND_ERROR_CODE: &str = "ProductionHouseNotFoundError";
+pub const PRODUCTION_HOUSE_CONFLICT_ERROR_CODE: &str = "ProductionHouseConflictError";
+pub const BAD_PERSON_REQUEST_ERROR_CODE: &str = "BadPersonRequestError";
+pub const INVALID_PERSON_ID_ERROR_CODE: &str = "InvalidPersonIDError";
+pub const PERSON_NOT_FOUND_ERROR_CODE: &str = "PersonNotFoundError";
+pub const PERSON_CONFLICT_ERROR_CODE: &str = "PersonConflictError";
+pub const BAD_CREDITS_REQUEST_ERROR_CODE: &str = "BadCreditsRequestError";
+pub const UNKNOWN_CREDITED_PERSON_ERROR_CODE: &str = "UnknownCreditedPersonError";
+pub const INVALID_METADATA_ERROR_CODE: &str = "InvalidMetadataError";
+pub const BAD_FRANCHISE_REQUEST_ERROR_CODE: &str = "BadFranchiseRequestError";
+pub const INVALID_FRANCHISE_ID_ERROR_CODE: &str = "InvalidFranchiseIDError";
+pub const FRANCHISE_NOT_FOUND_ERROR_CODE: &str = "FranchiseNotFoundError";
+pub const FRANCHISE_CONFLICT_ERROR_CODE: &str = "FranchiseConflictError";
+pub const UNKNOWN_INSTALMENT_ERROR_CODE: &str = "UnknownInstalmentError";
+pub const BAD_RELATIONS_REQUEST_ERROR_CODE: &str = "BadRelationsRequestError";
+pub const INVALID_MOVIE_RELATION_ERROR_CODE: &str = "InvalidMovieRelationError";
+pub const NOT_ACCEPTABLE_ERROR_CODE: &str = "NotAcceptableError";
+pub const UNSUPPORTED_MEDIA_TYPE_ERROR_CODE: &str = "UnsupportedMediaTypeError";
+
+pub const MOVIES_API_PATH: [&str; 3] = ["boxoffice", "movies", "v1"];
+pub const WEBHOOKS_API_PATH: [&str; 3] = ["boxoffice", "webhooks", "v1"];
+pub const HEALTH_API_PATH: [&str; 2] = ["boxoffice", "health"];
+pub const EXCHANGE_RATES_API_PATH: [&str; 3] = ["boxoffice", "exchangerates", "v1"];
+pub const CPI_API_PATH: [&str; 3] = ["boxoffice", "cpi", "v1"];
+pub const SEARCH_API_PATH: [&str; 3] = ["boxoffice", "v2", "search"];
+pub const DEFAULT_CLIENT_MAXIMUM_ATTEMPTS: u32 = 3;
+pub const DEFAULT_CLIENT_INITIAL_BACKOFF_MILLISECONDS: u64 = 100;
+pub const DEFAULT_CLIENT_MAXIMUM_BACKOFF_MILLISECONDS: u64 = 2000;
+pub const DEFAULT_CLIENT_REQUEST_TIMEOUT_SECONDS: u64 = 30;
+
+/// Values of 'include' query parameter selecting computed financial figures of movies.
+pub const INCLUDE_PROFIT: &str = "profit";
+pub const INCLUDE_PROFIT_PERCENTAGE: &str = "profit_percentage";
+pub const INCLUDE_ROI_MULTIPLE: &str = "roi_multiple";
+pub const INCLUDE_BREAK_EVEN_GAP: &str = "break_even_gap";
+/// Selects every financial figure.
+pub const INCLUDE_FINANCIALS: &str = "financials";
+/// Adds budget and collection adjusted for inflation, to the latest CPI year unless 'baseYear' is given.
+pub const INCLUDE_INFLATION_ADJUSTED: &str = "inflation_adjusted";
+pub const INCLUDE_SEPARATOR: char = ',';
+/// Decimal places of computed percentage and ROI multiple.
+pub const FINANCIAL_DECIMAL_PLACES: u32 = 2;
+/// Decimal places of percentage deciding verdict.
+pub const VERDICT_PERCENTAGE_DECIMAL_PLACES: u32 = 0;
+/// Decimal places Decimal can hold.
+pub const MAXIMUM_DECIMAL_PLACES: u32 = 28;
+
+/// Currency in which verdict is calculated.
+pub const INR: &str = "INR";
+/// Values of amount units.
+pub const CRORE: &str = "crore";
+pub const LAKH: &str = "lakh";
+pub const MILLION: &str = "million";
+/// Decimal places of amounts converted to requested currency and unit.
+pub const CONVERTED_AMOUNT_DECIMAL_PLACES: u32 = 4;
+pub const DEFAULT_EXCHANGE_RATES_FILE: &str = "exchange_rates.json";
+
+/// Range of years accepted in the consumer price index table.
+pub const MINIMUM_CPI_YEAR: u16 = 1900;
+pub const MAXIMUM_CPI_YEAR: u16 = 2100;
+/// Decimal places of inflation adjusted amounts.
+pub const INFLATION_ADJUSTED_AMOUNT_DECIMAL_PLACES: u32 = 4;
+pub const DEFAULT_CPI_FILE: &str = "cpi.json";
+
+/// Default vocabularies of movie metadata.
+pub const DEFAULT_GENRES: [&str; 18] = [
+    "action",
+    "adventure",
+    "animation",
+    "biography",
+    "comedy",
+    "crime",
+    "drama",
+    "family",
+    "fantasy",
+    "historical",
+    "horror",
+    "musical",
+    "mystery",
+    "romance",
+    "sci-fi",
+    "sports",
+    "thriller",
+    "war",
+];
+pub const DEFAULT_LANGUAGES: [&str; 12] = [
+    "hindi",
+    "tamil",
+    "telugu",
+    "malayalam",
+    "kannada",
+    "bengali",
+    "marathi",
+    "punjabi",
+    "gujarati",
+    "odia",
+    "assamese",
+    "english",
+];
+/// CBFC certifications.
+pub const DEFAULT_CERTIFICATIONS: [&str; 7] = ["U", "UA", "UA 7+", "UA 13+", "UA 16+", "A", "S"];
+pub const MINIMUM_RUNTIME_MINUTES: u16 = 1;
+pub const MAXIMUM_RUNTIME_MINUTES: u16 = 600;
+
+/// Text index over title, production house and cast of movies. A collection has one text index at most.
+pub const MOVIES_TEXT_INDEX: &str = "MoviesText";
+/// Transliterated titles are not stemmed nor stripped of stop words.
+pub const TEXT_INDEX_LANGUAGE: &str = "none";
+pub const TITLE_TEXT_WEIGHT: i32 = 10;
+pub const PRODUCTION_HOUSE_TEXT_WEIGHT: i32 = 5;
+pub const CAST_TEXT_WEIGHT: i32 = 3;
+/// Weights of fields in relevance of in-process search, in line with the text index weights.
+pub const TITLE_SEARCH_WEIGHT: f64 = 3.0;
+pub const PRODUCTION_HOUSE_SEARCH_WEIGHT: f64 = 1.5;
+pub const CAST_SEARCH_WEIGHT: f64 = 1.0;
+/// Added to relevance of movies whose title is the whole query.
+pub const EXACT_TITLE_SEARCH_BONUS: f64 = 2.0;
+/// Text index score is projected into this field of movies found by full-text search.
+pub const TEXT_SCORE: &str = "TextScore";
+/// Scales text index scores added to relevance, so that a title word weighs about as much in both.
+pub const TEXT_SCORE_SEARCH_WEIGHT: f64 = 0.3;
+/// Relevance of a word matched by its beginning, or with one or two typos, instead of exactly.
+pub const PREFIX_MATCH_SCORE: f64 = 0.7;
+pub const ONE_TYPO_MATCH_SCORE: f64 = 0.6;
+pub const TWO_TYPOS_MATCH_SCORE: f64 = 0.4;
+/// Shortest query word matched by its beginning or with a typo, shorter words match exactly only.
+pub const MINIMUM_PREFIX_LENGTH: usize = 3;
+pub const MINIMUM_ONE_TYPO_LENGTH: usize = 4;
+pub const MINIMUM_TWO_TYPOS_LENGTH: usize = 8;
+pub const DEFAULT_SEARCH_LIMIT: usize = 10;
+pub const MAXIMUM_SEARCH_LIMIT: usize = 50;
+pub const MAXIMUM_SEARCH_QUERY_LENGTH: usize = 100;
+
+/// Territory of collection entries which do not name one.
+pub const DEFAULT_TERRITORY: &str = "domestic";
+pub const MAXIMUM_TERRITORY_LENGTH: usize = 64;
+/// Days covered by collection entry periods.
+pub const DAYS_IN_WEEK: i64 = 7;
+pub const DAYS_IN_WEEKEND: i64 = 3;
+pub const MILLISECONDS_IN_DAY: i64 = 86_400_000;
+/// Code of MONGODB write errors rejected by a unique index.
+pub const DUPLICATE_KEY_CODE: i32 = 11000;
+/// Code of MONGODB query errors for a missing index, e.g. '$text' without a text index.
+pub const INDEX_NOT_FOUND_CODE: i32 = 27;
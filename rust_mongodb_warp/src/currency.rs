@@ -0,0 +1,229 @@
+/// Currencies and units of box office amounts.
+/// Amounts are stored as reported, along with their INR crore equivalent used for verdict calculation.
+/// INR crore equivalent is calculated with the exchange rate table, which is loaded from a JSON file
+/// and can be changed through the exchange rates endpoint.
+use crate::config::ExchangeRatesConfig;
+use crate::error::BoxOfficeError;
+use crate::file_table::{FileTable, TableEntry};
+use crate::model::{AmountUnit, ExchangeRate, MovieAmounts};
+use crate::{constants::*, Result};
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::fmt;
+use std::str::FromStr;
+
+impl AmountUnit {
+    /// Returns value of one unit in crores.
+    pub fn in_crores(&self) -> Decimal {
+        match self {
+            AmountUnit::Crore => Decimal::ONE,
+            AmountUnit::Lakh => Decimal::new(1, 2),
+            AmountUnit::Million => Decimal::new(1, 1),
+        }
+    }
+}
+
+impl fmt::Display for AmountUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = match self {
+            AmountUnit::Crore => CRORE,
+            AmountUnit::Lakh => LAKH,
+            AmountUnit::Million => MILLION,
+        };
+        return write!(f, "{}", unit);
+    }
+}
+
+impl FromStr for AmountUnit {
+    type Err = BoxOfficeError;
+
+    fn from_str(unit: &str) -> Result<Self> {
+        match unit {
+            CRORE => Ok(AmountUnit::Crore),
+            LAKH => Ok(AmountUnit::Lakh),
+            MILLION => Ok(AmountUnit::Million),
+            _ => Err(BoxOfficeError::AmountConversionError(format!(
+                "unknown amount unit '{}'",
+                unit
+            ))),
+        }
+    }
+}
+
+/// Budget and collection in INR crores along with the amounts as reported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedAmounts {
+    pub budget_crores: Decimal,
+    pub collection_crores: Decimal,
+    pub reported_amounts: MovieAmounts,
+}
+
+impl TableEntry for ExchangeRate {
+    /// Currency and date, dates are 'YYYY-MM-DD' so that they sort chronologically.
+    type Key = (String, String);
+
+    fn key(&self) -> Self::Key {
+        (self.currency.clone(), self.date.clone())
+    }
+}
+
+/// Exchange rate table, INR for one unit of each currency per date.
+/// Clones share the same table. Changes are saved to the file the table was loaded from.
+#[derive(Debug, Clone)]
+pub struct ExchangeRates {
+    rates: FileTable<ExchangeRate>,
+}
+
+impl Default for ExchangeRates {
+    fn default() -> Self {
+        Self {
+            rates: FileTable::new(None, BoxOfficeError::ExchangeRatesFileError),
+        }
+    }
+}
+
+impl ExchangeRates {
+    /// Loads exchange rates from the configured file.
+    /// Table is empty, i.e. only INR amounts are accepted, if the file does not exist yet.
+    pub fn load(exchange_rates_config: &ExchangeRatesConfig) -> Result<Self> {
+        let rates = FileTable::load(
+            exchange_rates_config.file_path.clone(),
+            BoxOfficeError::ExchangeRatesFileError,
+        )?;
+        if let Some(file_path) = exchange_rates_config
+            .file_path
+            .as_ref()
+            .filter(|file_path| file_path.exists())
+        {
+            log::info!(
+                "{} exchange rates loaded from {}.",
+                rates.read().len(),
+                file_path.display()
+            );
+        }
+        return Ok(Self { rates });
+    }
+
+    /// Returns all exchange rates ordered by currency and date.
+    pub fn read(&self) -> Vec<ExchangeRate> {
+        return self.rates.read();
+    }
+
+    /// Adds exchange rates, replacing rates of the same currency and date,
+    /// once the changed table is saved to its file.
+    pub fn upsert(&self, rates: &[ExchangeRate]) -> Result<()> {
+        return self.rates.upsert(rates);
+    }
+
+    /// Returns INR for one unit of currency along with date of the rate.
+    /// Rate of the given date or the latest rate before it is used, the latest rate when date is not given.
+    /// INR is always 1 and has no date.
+    pub fn rate(&self, currency: &str, date: Option<&str>) -> Result<(Decimal, Option<String>)> {
+        if currency == INR {
+            return Ok((Decimal::ONE, None));
+        }
+        let first_key = (currency.to_string(), String::new());
+        let rate = self.rates.with_entries(|rates| {
+            match date {
+                Some(date) => rates
+                    .range(first_key..=(currency.to_string(), date.to_string()))
+                    .next_back(),
+                None => rates
+                    .range(first_key..)
+                    .take_while(|((rate_currency, _), _)| rate_currency == currency)
+                    .last(),
+            }
+            .map(|(_, rate)| rate.clone())
+        });
+        return match rate {
+            Some(rate) => Ok((rate.rate_to_inr, Some(rate.date))),
+            None => Err(BoxOfficeError::ExchangeRateNotFoundError(match date {
+                Some(date) => format!("{} on or before {}", currency, date),
+                None => currency.to_string(),
+            })),
+        };
+    }
+
+    /// Converts budget and collection in the given currency and unit to INR crores.
+    /// INR crores are assumed when currency and unit are not given.
+    pub fn normalize(
+        &self,
+        budget: Decimal,
+        collection: Decimal,
+        currency: Option<&str>,
+        unit: Option<AmountUnit>,
+        exchange_rate_date: Option<&str>,
+    ) -> Result<NormalizedAmounts> {
+        let currency = currency.unwrap_or(INR);
+        let unit = unit.unwrap_or_default();
+        let (exchange_rate, rate_date) = self.rate(currency, exchange_rate_date)?;
+        let to_crores = |amount: Decimal| {
+            amount
+                .checked_mul(unit.in_crores())
+                .and_then(|amount| amount.checked_mul(exchange_rate))
+                .ok_or_else(|| {
+                    BoxOfficeError::AmountConversionError(format!(
+                        "{} {} {} is out of range in INR crores",
+                        amount, currency, unit
+                    ))
+                })
+        };
+        return Ok(NormalizedAmounts {
+            budget_crores: to_crores(budget)?,
+            collection_crores: to_crores(collection)?,
+            reported_amounts: MovieAmounts {
+                currency: currency.to_string(),
+                unit,
+                budget,
+                collection,
+                exchange_rate,
+                exchange_rate_date: rate_date,
+            },
+        });
+    }
+
+    /// Converts budget and collection in INR crores to the given currency and unit with the latest rate.
+    /// Converted amounts are rounded to 'CONVERTED_AMOUNT_DECIMAL_PLACES' decimal places.
+    pub fn convert(
+        &self,
+        budget_crores: Decimal,
+        collection_crores: Decimal,
+        currency: &str,
+        unit: AmountUnit,
+    ) -> Result<MovieAmounts> {
+        let (exchange_rate, rate_date) = self.rate(currency, None)?;
+        return Ok(MovieAmounts {
+            currency: currency.to_string(),
+            unit,
+            budget: from_crores(budget_crores, currency, unit, exchange_rate)?,
+            collection: from_crores(collection_crores, currency, unit, exchange_rate)?,
+            exchange_rate,
+            exchange_rate_date: rate_date,
+        });
+    }
+}
+
+/// Converts amount in INR crores to the currency and unit, 'exchange_rate' being INR for one unit of the currency.
+/// Converted amount is rounded to 'CONVERTED_AMOUNT_DECIMAL_PLACES' decimal places.
+pub fn from_crores(
+    amount_crores: Decimal,
+    currency: &str,
+    unit: AmountUnit,
+    exchange_rate: Decimal,
+) -> Result<Decimal> {
+    return unit
+        .in_crores()
+        .checked_mul(exchange_rate)
+        .and_then(|crores_per_unit| amount_crores.checked_div(crores_per_unit))
+        .map(|amount| {
+            amount.round_dp_with_strategy(
+                CONVERTED_AMOUNT_DECIMAL_PLACES,
+                RoundingStrategy::MidpointAwayFromZero,
+            )
+        })
+        .ok_or_else(|| {
+            BoxOfficeError::AmountConversionError(format!(
+                "{} INR crores is out of range in {} {}",
+                amount_crores, currency, unit
+            ))
+        });
+}
@@ -1,40 +1,132 @@
 /// Performs database operations.
+use crate::business_layer::is_territory_breakdown_stale;
 use crate::error::*;
 use crate::{
-    business_layer::*, constants::*, error::BoxOfficeError::*, model::Movie,
-    request_response_structs::*, Result,
+    collection_run,
+    config::DatabaseConfig,
+    constants::*,
+    currency::{self, ExchangeRates},
+    error::BoxOfficeError::*,
+    events::{EventBus, MovieEvent, MovieEventType},
+    inflation::CpiTable,
+    metadata::Vocabularies,
+    model::{
+        AmountUnit, CollectionEntry, Credit, Franchise, Movie, MovieAmounts, MovieMetadata,
+        MovieRelation, OutboxEntry, Person, ProductionHouse, ReleaseStatus, TerritoryCollection,
+        Webhook, WebhookDelivery,
+    },
+    production_house, release,
+    request_response_structs::*,
+    search::SearchIndexCache,
+    webhooks, Result,
+};
+use futures::{Stream, StreamExt};
+use mongodb::bson::{doc, document::Document, oid::ObjectId, DateTime};
+use mongodb::{
+    error::{ErrorKind, WriteFailure},
+    options::{
+        ClientOptions, FindOneAndUpdateOptions, FindOptions, IndexOptions, ReplaceOptions,
+        ReturnDocument, Tls, TlsOptions, UpdateOptions,
+    },
+    Client, ClientSession, Collection, IndexModel,
 };
-use futures::StreamExt;
-use mongodb::bson::{doc, document::Document};
-use mongodb::{options::ClientOptions, Client, Collection};
 use rust_decimal::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
 
 /// Structure with MONGODB Client.
 /// MONGODB Client is used to connect to MONGODB.
 #[derive(Clone, Debug)]
 pub struct DB {
     pub client: Client,
+    pub database_name: String,
+    /// Movie change notifications published by write operations.
+    pub events: EventBus,
+    /// Write operations do not publish events when they come from MONGODB change streams.
+    pub publish_write_events: bool,
+    /// Exchange rates used to convert amounts to INR crores.
+    pub exchange_rates: ExchangeRates,
+    /// Consumer price index used to adjust amounts for inflation.
+    pub cpi: CpiTable,
+    /// Vocabularies against which movie metadata is checked.
+    pub vocabularies: Vocabularies,
+    /// Search index over all movies, rebuilt after movie events.
+    pub search_index: SearchIndexCache,
+    /// Whether MONGODB supports transactions, detected on the first movie write.
+    supports_transactions: Arc<OnceCell<bool>>,
+}
+
+/// Movie write in progress.
+/// When MONGODB supports transactions (replica set or sharded cluster) the movie change and
+/// the webhook outbox entries of its events are committed in one transaction, so no event is lost.
+/// On a standalone server outbox entries are written right after the change and failures are logged.
+struct MovieWrite {
+    session: ClientSession,
+    is_transaction: bool,
+    events: Vec<MovieEvent>,
 }
 
 impl DB {
     /// Returns MONGODB Client for Connection String and Database.
-    pub async fn init() -> Result<Self> {
-        let mut client_options = ClientOptions::parse(CONNECTION_STRING).await?;
-        client_options.app_name = Some(DB_NAME.to_string());
+    pub async fn init(database_config: &DatabaseConfig) -> Result<Self> {
+        let client = Self::client(database_config).await?;
+        let events = EventBus::default();
+        Ok(Self {
+            client,
+            database_name: database_config.database_name.clone(),
+            search_index: SearchIndexCache::new(&events),
+            events,
+            publish_write_events: !database_config.change_streams,
+            exchange_rates: ExchangeRates::default(),
+            cpi: CpiTable::default(),
+            vocabularies: Vocabularies::default(),
+            supports_transactions: Arc::new(OnceCell::new()),
+        })
+    }
+
+    /// Uses the given exchange rate table. Database starts with an empty table, i.e. INR amounts only.
+    pub fn with_exchange_rates(mut self, exchange_rates: ExchangeRates) -> Self {
+        self.exchange_rates = exchange_rates;
+        return self;
+    }
+
+    /// Uses the given consumer price index table. Database starts with an empty table, i.e. no adjustment.
+    pub fn with_cpi(mut self, cpi: CpiTable) -> Self {
+        self.cpi = cpi;
+        return self;
+    }
+
+    /// Uses the given metadata vocabularies. Database starts with the default vocabularies.
+    pub fn with_vocabularies(mut self, vocabularies: Vocabularies) -> Self {
+        self.vocabularies = vocabularies;
+        return self;
+    }
+
+    /// Creates MONGODB Client from database configuration.
+    /// TLS is enabled when database configuration contains TLS section.
+    async fn client(database_config: &DatabaseConfig) -> Result<Client> {
+        let mut client_options = ClientOptions::parse(&database_config.connection_string).await?;
+        client_options.app_name = Some(database_config.database_name.clone());
+        if let Some(tls_config) = &database_config.tls {
+            let tls_options = TlsOptions::builder()
+                .ca_file_path(tls_config.ca_file_path.clone())
+                .cert_key_file_path(tls_config.certificate_key_file_path.clone())
+                .allow_invalid_certificates(tls_config.allow_invalid_certificates)
+                .build();
+            client_options.tls = Some(Tls::Enabled(tls_options));
+        }
         let client = Client::with_options(client_options)?;
-        Ok(Self { client })
+        return Ok(client);
     }
 
     /// Pings to database and checks whether database is running or not.
     /// Returns true if database is up and running else returns false.
-    pub async fn check_mongodb_is_running() -> Result<bool> {
-        let mut client_options = ClientOptions::parse(CONNECTION_STRING).await?;
-        client_options.app_name = Some(DB_NAME.to_string());
-
-        let client = Client::with_options(client_options)?;
+    pub async fn check_mongodb_is_running(database_config: &DatabaseConfig) -> Result<bool> {
+        let client = Self::client(database_config).await?;
 
         let ping_result = client
-            .database(DB_NAME)
+            .database(&database_config.database_name)
             .run_command(doc! {PINGCOMMAND: 1}, None)
             .await;
         let is_connected: bool;
@@ -54,47 +146,97 @@ impl DB {
 
     /// Returns MONGODB Collection on which we can perform CRUD operations.
     pub fn get_collection(&self) -> Collection<Document> {
-        let database = self.client.database(DB_NAME);
+        let database = self.client.database(&self.database_name);
         let collection = database.collection::<Document>(COLLECTION);
         return collection;
     }
 
+    /// Returns MONGODB Collection with the given name.
+    pub fn get_named_collection(&self, name: &str) -> Collection<Document> {
+        let database = self.client.database(&self.database_name);
+        let collection = database.collection::<Document>(name);
+        return collection;
+    }
+
     /// Creates Movie using Create Movie Request.
+    /// Amounts are stored as reported and in INR crores, verdict is calculated on INR crores.
+    /// Movie is released when no status is given.
+    /// Production house name is replaced by the name of the production house it matches, which is created if there is none.
+    #[tracing::instrument(skip_all)]
     pub async fn create_movie(&self, create_movie_request: &CreateMovieRequest) -> Result<()> {
-        let verdict: &str;
-        let mut calculated_verdict = "".to_string();
-        let calculate_verdict_result = calculate_verdict(
+        let amounts = self.exchange_rates.normalize(
             create_movie_request.budget_crores,
             create_movie_request.collection_crores,
-        );
-        if calculate_verdict_result.is_ok() {
-            calculated_verdict = calculate_verdict_result.unwrap();
-        }
-        verdict = calculated_verdict.as_str();
-        let doc = doc! {
-            MOVIEID: create_movie_request.movie_id.clone() as i32,
+            create_movie_request.currency.as_deref(),
+            create_movie_request.unit,
+            create_movie_request.exchange_rate_date.as_deref(),
+        )?;
+        let status = create_movie_request
+            .status
+            .unwrap_or(ReleaseStatus::Released);
+        release::check_release(
+            status,
+            create_movie_request.release_date.as_deref(),
+            create_movie_request.year_of_release,
+            amounts.collection_crores,
+        )?;
+        let calculated_verdict =
+            release::verdict(status, amounts.budget_crores, amounts.collection_crores)?;
+        let verdict: &str = calculated_verdict.as_str();
+        let metadata = self
+            .vocabularies
+            .normalize(&create_movie_request.metadata.clone().unwrap_or_default())?;
+        let production_house = self
+            .resolve_production_house(&create_movie_request.production_house)
+            .await?;
+        let mut doc = doc! {
+            MOVIEID: create_movie_request.movie_id as i32,
             TITLE: create_movie_request.title.clone(),
-            PRODUCTION_HOUSE: create_movie_request.production_house.clone(),
-            YEAR_OF_RELEASE: create_movie_request.year_of_release.clone() as i32,
-            BUDGET_CRORES: create_movie_request.budget_crores.clone().to_string() as String,
-            COLLECTION_CRORES: create_movie_request.collection_crores.clone().to_string() as String,
+            PRODUCTION_HOUSE: production_house.name,
+            PRODUCTION_HOUSE_ID: production_house.id,
+            YEAR_OF_RELEASE: create_movie_request.year_of_release as i32,
+            BUDGET_CRORES: amounts.budget_crores.to_string(),
+            COLLECTION_CRORES: amounts.collection_crores.to_string(),
             VERDICT: verdict,
+            STATUS: status.to_string(),
         };
+        if let Some(release_date) = &create_movie_request.release_date {
+            doc.insert(RELEASE_DATE, release_date.clone());
+        }
+        doc.extend(reported_amounts_doc(&amounts.reported_amounts));
+        doc.extend(metadata_doc(&metadata));
 
-        let _insert_one_result = self
+        let mut write = self.start_movie_write().await?;
+        let insert_one_result = self
             .get_collection()
-            .insert_one(doc, None)
+            .insert_one_with_session(&doc, None, &mut write.session)
             .await
             .map_err(MongoQueryError)?;
-        return Ok(());
+
+        let mut created_doc = doc;
+        created_doc.insert(ID, insert_one_result.inserted_id);
+        let created_movie = self.doc_to_movie(&created_doc)?;
+        self.queue_movie_event(
+            &mut write,
+            MovieEvent::new(MovieEventType::Created, None, Some(created_movie)),
+        )
+        .await?;
+        return self.commit_movie_write(write).await;
     }
 
     /// Function to Read Movies.
     /// Returns a vector containing Movies.
     pub async fn read_movies(&self) -> Result<Vec<Movie>> {
+        return self.read_movies_with(&MovieQuery::default()).await;
+    }
+
+    /// Function to Read Movies kept by territory, market and metadata filters of the query.
+    /// Filters are matched by the database so that indexes of metadata fields are used.
+    #[tracing::instrument(skip(self))]
+    pub async fn read_movies_with(&self, query: &MovieQuery) -> Result<Vec<Movie>> {
         let mut cursor = self
             .get_collection()
-            .find(None, None)
+            .find(movie_query_filter(query, &self.vocabularies), None)
             .await
             .map_err(MongoQueryError)?;
 
@@ -105,99 +247,220 @@ impl DB {
         return Ok(movies);
     }
 
+    /// Function to Read Movies one by one as they are read from the database.
+    #[tracing::instrument(skip(self))]
+    pub async fn stream_movies(&self) -> Result<impl Stream<Item = Result<Movie>>> {
+        let cursor = self
+            .get_collection()
+            .find(None, None)
+            .await
+            .map_err(MongoQueryError)?;
+
+        let db = self.clone();
+        return Ok(cursor.map(move |doc| db.doc_to_movie(&doc?)));
+    }
+
     /// Updates Movie using Update Movie Request and Movie Id.
-    /// Returns number of documents updated (1).
+    /// Returns number of documents updated (1), MovieNotFoundError if there is no such movie.
+    #[tracing::instrument(skip(self, update_movie_request))]
     pub async fn update_movie(
         &self,
         id: &str,
         update_movie_request: &UpdateMovieRequest,
     ) -> Result<u64> {
-        let verdict: &str;
-        let mut calculated_verdict = "".to_string();
-        let calculate_verdict_result = calculate_verdict(
+        let amounts = self.exchange_rates.normalize(
             update_movie_request.budget_crores,
             update_movie_request.collection_crores,
-        );
-        if calculate_verdict_result.is_ok() {
-            calculated_verdict = calculate_verdict_result.unwrap();
-        }
-        verdict = calculated_verdict.as_str();
+            update_movie_request.currency.as_deref(),
+            update_movie_request.unit,
+            update_movie_request.exchange_rate_date.as_deref(),
+        )?;
 
-        let query = doc! {
-            "MovieID": id.parse::<i32>().unwrap() as i32,
-        };
+        let doc_id = id
+            .parse::<i32>()
+            .map_err(|_| InvalidMovieIDError(id.to_string()))?;
+        // Movie is read within the write, so that checks see the movie as it is changed.
+        let mut write = self.start_movie_write().await?;
+        let result = self
+            .get_collection()
+            .find_one_with_session(doc! { MOVIEID: doc_id }, None, &mut write.session)
+            .await
+            .map_err(MongoQueryError)?
+            .ok_or_else(|| MovieNotFoundError(id.to_string()))?;
+        let old_movie = self.doc_to_movie(&result)?;
+
+        // Status and release date are kept when not given.
+        let status = update_movie_request.status.unwrap_or(old_movie.status);
+        let release_date = update_movie_request
+            .release_date
+            .clone()
+            .or_else(|| old_movie.release_date.clone());
+        release::check_transition(old_movie.status, status)?;
+        release::check_release(
+            status,
+            release_date.as_deref(),
+            update_movie_request.year_of_release,
+            amounts.collection_crores,
+        )?;
+        let calculated_verdict =
+            release::verdict(status, amounts.budget_crores, amounts.collection_crores)?;
+        let verdict: &str = calculated_verdict.as_str();
+        // Metadata is kept when not given.
+        let metadata = update_movie_request
+            .metadata
+            .as_ref()
+            .map(|metadata| self.vocabularies.normalize(metadata))
+            .transpose()?;
+        let production_house = self
+            .resolve_production_house(&update_movie_request.production_house)
+            .await?;
 
-        let doc_id = id.parse::<i32>().unwrap() as i32;
         let doc_title = update_movie_request.title.clone();
-        let doc_production_house = update_movie_request.production_house.clone();
-        let doc_year_of_release = update_movie_request.year_of_release.clone() as i32;
-        let doc_budget_crores = update_movie_request.budget_crores.clone().to_string() as String;
-        let doc_collection_crores =
-            update_movie_request.collection_crores.clone().to_string() as String;
+        let doc_production_house = production_house.name;
+        let doc_year_of_release = update_movie_request.year_of_release as i32;
+        let doc_budget_crores = amounts.budget_crores.to_string();
+        let doc_collection_crores = amounts.collection_crores.to_string();
         let doc_verdict = verdict.to_string();
 
-        let doc = doc! {
-           "$set": {
+        let mut set_doc = doc! {
              MOVIEID: doc_id ,
              TITLE: doc_title ,
              PRODUCTION_HOUSE: doc_production_house ,
+             PRODUCTION_HOUSE_ID: production_house.id ,
              YEAR_OF_RELEASE: doc_year_of_release ,
              BUDGET_CRORES: doc_budget_crores ,
              COLLECTION_CRORES: doc_collection_crores ,
-             VERDICT: doc_verdict
-            },
+             VERDICT: doc_verdict ,
+             STATUS: status.to_string()
+        };
+        if let Some(release_date) = release_date {
+            set_doc.insert(RELEASE_DATE, release_date);
+        }
+        set_doc.extend(reported_amounts_doc(&amounts.reported_amounts));
+        if let Some(metadata) = &metadata {
+            set_doc.extend(metadata_doc(metadata));
+        }
+        let doc = doc! {
+           "$set": set_doc.clone(),
         };
-
-        let find_result = self
-            .get_collection()
-            .find_one(query.clone(), None)
-            .await
-            .map_err(MongoQueryError)?;
-        let result = find_result.unwrap();
 
         let query1 = doc! {
             "_id": &result.get("_id"),
         };
         let bson_movie = self
             .get_collection()
-            .update_many(query1, doc, None)
+            .update_many_with_session(query1, doc, None, &mut write.session)
             .await
             .map_err(MongoQueryError)?;
         let number_of_records_modified = bson_movie.modified_count;
+
+        // Territory breakdown is kept when budget or collection changes, movie flags it as stale.
+        if number_of_records_modified == 1 {
+            let mut updated_doc = result;
+            updated_doc.extend(set_doc);
+            let updated_movie = self.doc_to_movie(&updated_doc)?;
+            self.queue_movie_event(
+                &mut write,
+                MovieEvent::new(
+                    MovieEventType::Updated,
+                    Some(&old_movie),
+                    Some(updated_movie),
+                ),
+            )
+            .await?;
+        }
+        self.commit_movie_write(write).await?;
         return Ok(number_of_records_modified);
     }
 
     /// Deletes Movie using Movie Id
     /// Returns number of documents deleted (1).
+    #[tracing::instrument(skip(self))]
     pub async fn delete_movie(&self, id: &str) -> Result<u64> {
         if id.parse::<i32>().is_ok() {
             let filter = doc! {
-                    "MovieID": id.parse::<i32>().unwrap() as i32,
+                    "MovieID": id.parse::<i32>().unwrap(),
+            };
+            // Movie is read before deleting so that an unreadable document is reported without deleting it.
+            let movie_option = self
+                .get_collection()
+                .find_one(filter.clone(), None)
+                .await
+                .map_err(MongoQueryError)?;
+            let deleted_movie = match movie_option {
+                Some(movie_document) => self.doc_to_movie(&movie_document)?,
+                None => return Ok(0),
             };
+            let mut write = self.start_movie_write().await?;
             let delete_result = self
                 .get_collection()
-                .delete_one(filter, None)
+                .delete_one_with_session(filter, None, &mut write.session)
                 .await
                 .map_err(MongoQueryError)?;
-            let number_of_records_deleted = delete_result.deleted_count;
-            return Ok(number_of_records_deleted);
+            if delete_result.deleted_count == 0 {
+                return Ok(0);
+            }
+            self.queue_movie_event(
+                &mut write,
+                MovieEvent::new(MovieEventType::Deleted, Some(&deleted_movie), None),
+            )
+            .await?;
+            self.commit_movie_write(write).await?;
+            // Movie is already deleted, so failures of the cascade are logged instead of failing the request.
+            if let Err(e) = self
+                .delete_movie_references(deleted_movie.movie_id as i32)
+                .await
+            {
+                log::error!(
+                    "Movie {} deleted, but removing its references failed: {}",
+                    deleted_movie.movie_id,
+                    e
+                );
+            }
+            return Ok(1);
         } else {
             return Err(BoxOfficeError::InvalidMovieIDError("".to_string()));
         }
     }
 
+    /// Deletes collection entries of a deleted movie and removes it from franchises and relations.
+    async fn delete_movie_references(&self, movie_id: i32) -> Result<()> {
+        self.get_named_collection(COLLECTION_ENTRIES_COLLECTION)
+            .delete_many(doc! { MOVIEID: movie_id }, None)
+            .await
+            .map_err(MongoQueryError)?;
+        // Deleted movie is no longer an instalment nor a related movie.
+        self.get_named_collection(FRANCHISES_COLLECTION)
+            .update_many(
+                doc! { MOVIE_IDS: movie_id },
+                doc! { "$pull": { MOVIE_IDS: movie_id } },
+                None,
+            )
+            .await
+            .map_err(MongoQueryError)?;
+        self.get_collection()
+            .update_many(
+                doc! { RELATIONS_MOVIE_ID: movie_id },
+                doc! { "$pull": { RELATIONS: { MOVIEID: movie_id } } },
+                None,
+            )
+            .await
+            .map_err(MongoQueryError)?;
+        return Ok(());
+    }
+
     /// Function to fetch Movie using Movie Id.
+    #[tracing::instrument(skip(self))]
     pub async fn get_movie_by_id(&self, id: &str) -> Result<Movie> {
         let filter = doc! {
-            "MovieID": id.parse::<i32>().unwrap() as i32,
+            "MovieID": id.parse::<i32>().unwrap(),
         };
         let movie_option = self
             .get_collection()
             .find_one(filter, None)
             .await
             .map_err(MongoQueryError)?;
-        if movie_option.is_some() {
-            let movie_document = movie_option.unwrap();
+        if let Some(movie_document) = movie_option {
             if !movie_document.is_empty() {
                 //if movie present.
                 let movie = self.doc_to_movie(&movie_document)?;
@@ -210,20 +473,36 @@ impl DB {
         }
     }
 
+    /// Fetches Movie using Movie Id, None if there is no such Movie.
+    #[tracing::instrument(skip(self))]
+    pub async fn find_movie_by_id(&self, movie_id: u8) -> Result<Option<Movie>> {
+        let filter = doc! {
+            MOVIEID: movie_id as i32,
+        };
+        let movie_option = self
+            .get_collection()
+            .find_one(filter, None)
+            .await
+            .map_err(MongoQueryError)?;
+        return movie_option
+            .map(|movie_document| self.doc_to_movie(&movie_document))
+            .transpose();
+    }
+
     /// Function to check whether Movie with input Movie Id exists in the Collection or Not.
     /// Returns true if Movie is present else returns false.
+    #[tracing::instrument(skip(self))]
     pub async fn check_movie_exist(&self, id: &str) -> Result<&bool> {
         let is_exist: &bool;
         let filter = doc! {
-            "MovieID": id.parse::<i32>().unwrap() as i32,
+            "MovieID": id.parse::<i32>().unwrap(),
         };
         let movie_option = self
             .get_collection()
             .find_one(filter, None)
             .await
             .map_err(MongoQueryError)?;
-        if movie_option.is_some() {
-            let movie_document = movie_option.unwrap();
+        if let Some(movie_document) = movie_option {
             if !movie_document.is_empty() {
                 //if movie present.
                 is_exist = &true;
@@ -238,9 +517,227 @@ impl DB {
         }
     }
 
+    /// Returns Movie documents as stored, including those which can not be converted to Movie.
+    pub async fn read_movie_documents(&self) -> Result<Vec<Document>> {
+        let mut cursor = self
+            .get_collection()
+            .find(None, None)
+            .await
+            .map_err(MongoQueryError)?;
+
+        let mut docs: Vec<Document> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            docs.push(doc?);
+        }
+        return Ok(docs);
+    }
+
+    /// Returns number of Movies.
+    pub async fn count_movies(&self) -> Result<u64> {
+        let count = self
+            .get_collection()
+            .count_documents(None, None)
+            .await
+            .map_err(MongoQueryError)?;
+        return Ok(count);
+    }
+
+    /// Deletes all Movies.
+    /// Returns number of documents deleted.
+    pub async fn delete_all_movies(&self) -> Result<u64> {
+        let delete_result = self
+            .get_collection()
+            .delete_many(doc! {}, None)
+            .await
+            .map_err(MongoQueryError)?;
+        return Ok(delete_result.deleted_count);
+    }
+
+    /// Writes Movie document as is, after checking it can be read by 'doc_to_movie'.
+    /// With 'upsert' the Movie having the same Movie Id is replaced, otherwise document is inserted.
+    /// Events are not published for imported documents.
+    pub async fn import_movie_document(&self, mut doc: Document, upsert: bool) -> Result<()> {
+        if !doc.contains_key(ID) {
+            doc.insert(ID, ObjectId::new());
+        }
+        let movie = self.doc_to_movie(&doc)?;
+        if upsert {
+            // '_id' of an existing document can not be changed.
+            doc.remove(ID);
+            let options = ReplaceOptions::builder().upsert(true).build();
+            self.get_collection()
+                .replace_one(doc! { MOVIEID: movie.movie_id as i32 }, doc, options)
+                .await
+                .map_err(MongoQueryError)?;
+        } else {
+            self.get_collection()
+                .insert_one(doc, None)
+                .await
+                .map_err(MongoQueryError)?;
+        }
+        return Ok(());
+    }
+
+    /// Sets verdict of the Movie and publishes update event.
+    pub async fn update_verdict(&self, movie: &Movie, verdict: &str) -> Result<()> {
+        let mut write = self.start_movie_write().await?;
+        self.get_collection()
+            .update_one_with_session(
+                doc! { MOVIEID: movie.movie_id as i32 },
+                doc! { "$set": { VERDICT: verdict } },
+                None,
+                &mut write.session,
+            )
+            .await
+            .map_err(MongoQueryError)?;
+        let mut updated_movie = movie.clone();
+        updated_movie.verdict = verdict.to_string();
+        self.queue_movie_event(
+            &mut write,
+            MovieEvent::new(MovieEventType::Updated, Some(movie), Some(updated_movie)),
+        )
+        .await?;
+        return self.commit_movie_write(write).await;
+    }
+
+    /// Creates indexes used by queries and uniqueness of Movie Id.
+    /// Existing indexes with the same keys are left unchanged.
+    /// Returns names of the indexes prefixed with collection name.
+    pub async fn ensure_indexes(&self) -> Result<Vec<String>> {
+        let indexes = [
+            (COLLECTION, doc! { MOVIEID: 1 }, true),
+            (COLLECTION, doc! { PRODUCTION_HOUSE: 1 }, false),
+            (COLLECTION, doc! { VERDICT: 1 }, false),
+            (
+                WEBHOOK_OUTBOX_COLLECTION,
+                doc! { STATUS: 1, NEXT_ATTEMPT_AT: 1 },
+                false,
+            ),
+            (
+                WEBHOOK_DELIVERIES_COLLECTION,
+                doc! { WEBHOOK_ID: 1, DELIVERED_AT: -1 },
+                false,
+            ),
+            (MIGRATIONS_COLLECTION, doc! { VERSION: 1 }, true),
+            (
+                COLLECTION_ENTRIES_COLLECTION,
+                doc! { MOVIEID: 1, DATE: 1 },
+                false,
+            ),
+            (COLLECTION, doc! { PRODUCTION_HOUSE_ID: 1 }, false),
+            (PRODUCTION_HOUSES_COLLECTION, doc! { NAME_KEYS: 1 }, true),
+            (COLLECTION, doc! { CREDITS_PERSON_ID: 1 }, false),
+            (COLLECTION, doc! { RELATIONS_MOVIE_ID: 1 }, false),
+            (FRANCHISES_COLLECTION, doc! { MOVIE_IDS: 1 }, false),
+            (COLLECTION, doc! { GENRES: 1 }, false),
+            (COLLECTION, doc! { LANGUAGES: 1 }, false),
+            (COLLECTION, doc! { CERTIFICATION: 1 }, false),
+            (COLLECTION, doc! { TAGS: 1 }, false),
+        ];
+        let mut index_names: Vec<String> = Vec::new();
+        for (collection, keys, unique) in indexes {
+            let index = IndexModel::builder()
+                .keys(keys)
+                .options(IndexOptions::builder().unique(unique).build())
+                .build();
+            let create_index_result = self
+                .get_named_collection(collection)
+                .create_index(index, None)
+                .await
+                .map_err(MongoQueryError)?;
+            index_names.push(format!("{}.{}", collection, create_index_result.index_name));
+        }
+        // Text index has field weights and is not stemmed, titles are transliterated.
+        let text_index = IndexModel::builder()
+            .keys(doc! { TITLE: "text", PRODUCTION_HOUSE: "text", CREDITS_NAME: "text" })
+            .options(
+                IndexOptions::builder()
+                    .name(MOVIES_TEXT_INDEX.to_string())
+                    .weights(doc! {
+                        TITLE: TITLE_TEXT_WEIGHT,
+                        PRODUCTION_HOUSE: PRODUCTION_HOUSE_TEXT_WEIGHT,
+                        CREDITS_NAME: CAST_TEXT_WEIGHT,
+                    })
+                    .default_language(TEXT_INDEX_LANGUAGE.to_string())
+                    .build(),
+            )
+            .build();
+        let create_index_result = self
+            .get_collection()
+            .create_index(text_index, None)
+            .await
+            .map_err(MongoQueryError)?;
+        index_names.push(format!("{}.{}", COLLECTION, create_index_result.index_name));
+        // Entries of the same period and territory must not share a day. Entries stored before
+        // days were recorded are left out until migrated.
+        let days_index = IndexModel::builder()
+            .keys(doc! { MOVIEID: 1, TERRITORY: 1, PERIOD: 1, DAYS: 1 })
+            .options(
+                IndexOptions::builder()
+                    .unique(true)
+                    .partial_filter_expression(doc! { DAYS: { "$exists": true } })
+                    .build(),
+            )
+            .build();
+        let create_index_result = self
+            .get_named_collection(COLLECTION_ENTRIES_COLLECTION)
+            .create_index(days_index, None)
+            .await
+            .map_err(MongoQueryError)?;
+        index_names.push(format!(
+            "{}.{}",
+            COLLECTION_ENTRIES_COLLECTION, create_index_result.index_name
+        ));
+        return Ok(index_names);
+    }
+
+    /// Returns versions of applied migrations.
+    pub async fn read_applied_migrations(&self) -> Result<Vec<i32>> {
+        let mut cursor = self
+            .get_named_collection(MIGRATIONS_COLLECTION)
+            .find(None, None)
+            .await
+            .map_err(MongoQueryError)?;
+
+        let mut versions: Vec<i32> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            versions.push(doc?.get_i32(VERSION)?);
+        }
+        return Ok(versions);
+    }
+
+    /// Records migration as applied.
+    pub async fn record_migration(&self, version: i32, name: &str) -> Result<()> {
+        let doc = doc! {
+            VERSION: version,
+            NAME: name,
+            APPLIED_AT: DateTime::now(),
+        };
+        self.get_named_collection(MIGRATIONS_COLLECTION)
+            .insert_one(doc, None)
+            .await
+            .map_err(MongoQueryError)?;
+        return Ok(());
+    }
+
+    /// Updates Movies matching the filter with an aggregation pipeline.
+    /// Returns number of documents modified.
+    pub async fn update_movies_with_pipeline(
+        &self,
+        filter: Document,
+        pipeline: Vec<Document>,
+    ) -> Result<u64> {
+        let update_result = self
+            .get_collection()
+            .update_many(filter, pipeline, None)
+            .await
+            .map_err(MongoQueryError)?;
+        return Ok(update_result.modified_count);
+    }
+
     /// Converts BSON Document to Movie Structure.
     /// This Movie Structure is used to return response.
-    fn doc_to_movie(&self, doc: &Document) -> Result<Movie> {
+    pub fn doc_to_movie(&self, doc: &Document) -> Result<Movie> {
         let id = doc.get_object_id(ID)?;
         let movie_id = doc.get_i32(MOVIEID)?;
         let title = doc.get_str(TITLE)?;
@@ -249,17 +746,1755 @@ impl DB {
         let budget_crores = doc.get_str(BUDGET_CRORES)?;
         let collection_crores = doc.get_str(COLLECTION_CRORES)?;
         let verdict = doc.get_str(VERDICT)?;
+        // Movies stored before release lifecycle have no status, their runs are over.
+        let status = match doc.get_str(STATUS) {
+            Ok(status) => ReleaseStatus::from_str(status)?,
+            Err(_) => ReleaseStatus::Closed,
+        };
+        // Movies stored before amounts had a currency have no reported amounts.
+        let reported_amounts = match doc.get_str(CURRENCY) {
+            Ok(currency) => Some(MovieAmounts {
+                currency: currency.to_owned(),
+                unit: AmountUnit::from_str(doc.get_str(UNIT)?)?,
+                budget: Decimal::from_str(doc.get_str(REPORTED_BUDGET)?)
+                    .map_err(|e| InvalidDocumentError(e.to_string()))?,
+                collection: Decimal::from_str(doc.get_str(REPORTED_COLLECTION)?)
+                    .map_err(|e| InvalidDocumentError(e.to_string()))?,
+                exchange_rate: Decimal::from_str(doc.get_str(EXCHANGE_RATE)?)
+                    .map_err(|e| InvalidDocumentError(e.to_string()))?,
+                exchange_rate_date: doc.get_str(EXCHANGE_RATE_DATE).ok().map(str::to_owned),
+            }),
+            Err(_) => None,
+        };
+        // Movies without territory breakdown have no territories.
+        let territories = match doc.get_array(TERRITORIES) {
+            Ok(territories) => Some(
+                territories
+                    .iter()
+                    .filter_map(|territory| territory.as_document())
+                    .map(doc_to_territory_collection)
+                    .collect::<Result<Vec<TerritoryCollection>>>()?,
+            ),
+            Err(_) => None,
+        };
+        // Movies without cast and crew have no credits.
+        let credits = match doc.get_array(CREDITS) {
+            Ok(credits) => Some(
+                credits
+                    .iter()
+                    .filter_map(|credit| credit.as_document())
+                    .map(doc_to_credit)
+                    .collect::<Result<Vec<Credit>>>()?,
+            ),
+            Err(_) => None,
+        };
+        // Movies not related to other movies have no relations.
+        let relations = match doc.get_array(RELATIONS) {
+            Ok(relations) => Some(
+                relations
+                    .iter()
+                    .filter_map(|relation| relation.as_document())
+                    .map(doc_to_movie_relation)
+                    .collect::<Result<Vec<MovieRelation>>>()?,
+            ),
+            Err(_) => None,
+        };
+
+        let budget_crores =
+            Decimal::from_str(budget_crores).map_err(|e| InvalidDocumentError(e.to_string()))?;
+        let collection_crores = Decimal::from_str(collection_crores)
+            .map_err(|e| InvalidDocumentError(e.to_string()))?;
+        let territories_stale = territories.as_ref().is_some_and(|territories| {
+            is_territory_breakdown_stale(territories, budget_crores, collection_crores)
+        });
 
         let movie = Movie {
             id: id.to_hex(),
             movie_id: movie_id as u8,
             title: title.to_owned(),
             production_house: production_house.to_owned(),
+            production_house_id: doc.get_str(PRODUCTION_HOUSE_ID).ok().map(str::to_owned),
             year_of_release: year_of_release as u16,
-            budget_crores: Decimal::from_str(budget_crores).unwrap(),
-            collection_crores: Decimal::from_str(collection_crores).unwrap(),
+            release_date: doc.get_str(RELEASE_DATE).ok().map(str::to_owned),
+            status,
+            budget_crores,
+            collection_crores,
             verdict: verdict.to_owned(),
+            verdict_provisional: status.is_verdict_provisional(),
+            financials: None,
+            reported_amounts,
+            requested_amounts: None,
+            inflation_adjusted: None,
+            territories,
+            territories_stale,
+            metadata: doc_to_metadata(doc),
+            credits,
+            relations,
         };
         Ok(movie)
     }
+
+    /// Whether MONGODB is a replica set member or mongos, i.e. supports transactions.
+    async fn supports_transactions(&self) -> bool {
+        return *self
+            .supports_transactions
+            .get_or_init(|| async {
+                let hello = self
+                    .client
+                    .database(ADMIN_DATABASE)
+                    .run_command(doc! { HELLO_COMMAND: 1 }, None)
+                    .await;
+                let supports_transactions = match hello {
+                    Ok(hello) => {
+                        hello.contains_key(SET_NAME) || hello.get_str(MSG).ok() == Some(ISDBGRID)
+                    }
+                    Err(_) => false,
+                };
+                if !supports_transactions {
+                    log::warn!("MONGODB does not support transactions, webhook outbox entries are written after movie changes.");
+                }
+                supports_transactions
+            })
+            .await;
+    }
+
+    /// Starts a movie write, in a transaction when MONGODB supports transactions.
+    async fn start_movie_write(&self) -> Result<MovieWrite> {
+        let mut session = self
+            .client
+            .start_session(None)
+            .await
+            .map_err(MongoQueryError)?;
+        let is_transaction = self.supports_transactions().await;
+        if is_transaction {
+            session
+                .start_transaction(None)
+                .await
+                .map_err(MongoQueryError)?;
+        }
+        return Ok(MovieWrite {
+            session,
+            is_transaction,
+            events: Vec::new(),
+        });
+    }
+
+    /// Queues webhook deliveries of a movie event within the movie write.
+    /// In a transaction failing to queue them fails the write, otherwise the change is already saved
+    /// and the failure is logged.
+    async fn queue_movie_event(&self, write: &mut MovieWrite, event: MovieEvent) -> Result<()> {
+        let queued = self
+            .enqueue_webhook_deliveries(&event, &mut write.session)
+            .await;
+        match queued {
+            Err(e) if write.is_transaction => return Err(e),
+            Err(e) => log::error!("Unable to queue webhook deliveries: {}", e),
+            Ok(()) => {}
+        }
+        write.events.push(event);
+        return Ok(());
+    }
+
+    /// Commits the movie write, then publishes its events to subscribers.
+    async fn commit_movie_write(&self, mut write: MovieWrite) -> Result<()> {
+        if write.is_transaction {
+            write
+                .session
+                .commit_transaction()
+                .await
+                .map_err(MongoQueryError)?;
+        }
+        if self.publish_write_events {
+            for event in write.events {
+                self.events.publish(event);
+            }
+        }
+        return Ok(());
+    }
+
+    /// Writes one outbox entry per active webhook and webhook event matching the movie event.
+    #[tracing::instrument(skip_all)]
+    async fn enqueue_webhook_deliveries(
+        &self,
+        event: &MovieEvent,
+        session: &mut ClientSession,
+    ) -> Result<()> {
+        let event_types = webhooks::event_types(event);
+        let filter = doc! {
+            ACTIVE: true,
+            EVENTS: { "$in": event_types.clone() },
+        };
+        let mut cursor = self
+            .get_named_collection(WEBHOOKS_COLLECTION)
+            .find(filter, None)
+            .await
+            .map_err(MongoQueryError)?;
+
+        let now = DateTime::now();
+        let mut outbox_docs: Vec<Document> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            let webhook = self.doc_to_webhook(&doc?)?;
+            for event_type in event_types
+                .iter()
+                .filter(|event_type| webhook.events.iter().any(|e| e == *event_type))
+            {
+                let outbox_id = ObjectId::new();
+                outbox_docs.push(doc! {
+                    ID: outbox_id,
+                    WEBHOOK_ID: webhook.id.clone(),
+                    EVENT_TYPE: *event_type,
+                    PAYLOAD: webhooks::payload(&outbox_id.to_hex(), event_type, event),
+                    STATUS: OUTBOX_PENDING,
+                    ATTEMPTS: 0,
+                    NEXT_ATTEMPT_AT: now,
+                    LAST_ERROR: "",
+                    CREATED_AT: now,
+                });
+            }
+        }
+        if !outbox_docs.is_empty() {
+            self.get_named_collection(WEBHOOK_OUTBOX_COLLECTION)
+                .insert_many_with_session(outbox_docs, None, session)
+                .await
+                .map_err(MongoQueryError)?;
+        }
+        return Ok(());
+    }
+
+    /// Registers webhook using Create Webhook Request and signing secret.
+    #[tracing::instrument(skip_all)]
+    pub async fn create_webhook(
+        &self,
+        create_webhook_request: &CreateWebhookRequest,
+        secret: &str,
+    ) -> Result<Webhook> {
+        let mut doc = doc! {
+            URL: create_webhook_request.url.clone(),
+            EVENTS: create_webhook_request.events.clone(),
+            SECRET: secret,
+            ACTIVE: true,
+            CREATED_AT: DateTime::now(),
+        };
+        let insert_one_result = self
+            .get_named_collection(WEBHOOKS_COLLECTION)
+            .insert_one(&doc, None)
+            .await
+            .map_err(MongoQueryError)?;
+        doc.insert(ID, insert_one_result.inserted_id);
+        return self.doc_to_webhook(&doc);
+    }
+
+    /// Function to Read Webhooks.
+    #[tracing::instrument(skip(self))]
+    pub async fn read_webhooks(&self) -> Result<Vec<Webhook>> {
+        let mut cursor = self
+            .get_named_collection(WEBHOOKS_COLLECTION)
+            .find(None, None)
+            .await
+            .map_err(MongoQueryError)?;
+
+        let mut registered_webhooks: Vec<Webhook> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            registered_webhooks.push(self.doc_to_webhook(&doc?)?);
+        }
+        return Ok(registered_webhooks);
+    }
+
+    /// Function to fetch Webhook using Webhook Id.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_webhook(&self, id: &str) -> Result<Option<Webhook>> {
+        let filter = doc! {
+            ID: parse_object_id(id, InvalidWebhookIDError)?,
+        };
+        let webhook_option = self
+            .get_named_collection(WEBHOOKS_COLLECTION)
+            .find_one(filter, None)
+            .await
+            .map_err(MongoQueryError)?;
+        return webhook_option
+            .map(|webhook_document| self.doc_to_webhook(&webhook_document))
+            .transpose();
+    }
+
+    /// Deletes Webhook using Webhook Id together with its pending deliveries.
+    /// Returns number of webhooks deleted (1).
+    #[tracing::instrument(skip(self))]
+    pub async fn delete_webhook(&self, id: &str) -> Result<u64> {
+        let filter = doc! {
+            ID: parse_object_id(id, InvalidWebhookIDError)?,
+        };
+        let delete_result = self
+            .get_named_collection(WEBHOOKS_COLLECTION)
+            .delete_one(filter, None)
+            .await
+            .map_err(MongoQueryError)?;
+        if delete_result.deleted_count == 1 {
+            self.get_named_collection(WEBHOOK_OUTBOX_COLLECTION)
+                .delete_many(doc! { WEBHOOK_ID: id, STATUS: OUTBOX_PENDING }, None)
+                .await
+                .map_err(MongoQueryError)?;
+        }
+        return Ok(delete_result.deleted_count);
+    }
+
+    /// Claims the oldest due pending outbox entry.
+    /// Claimed entry is not due again until the lease expires, so that a crashed dispatcher
+    /// does not lose it and concurrent dispatchers do not deliver it twice.
+    pub async fn claim_due_outbox_entry(&self, lease: Duration) -> Result<Option<OutboxEntry>> {
+        let now = DateTime::now();
+        let filter = doc! {
+            STATUS: OUTBOX_PENDING,
+            NEXT_ATTEMPT_AT: { "$lte": now },
+        };
+        let update = doc! {
+            "$set": { NEXT_ATTEMPT_AT: date_time_after(now, lease) },
+        };
+        let options = FindOneAndUpdateOptions::builder()
+            .sort(doc! { NEXT_ATTEMPT_AT: 1 })
+            .return_document(ReturnDocument::After)
+            .build();
+        let outbox_option = self
+            .get_named_collection(WEBHOOK_OUTBOX_COLLECTION)
+            .find_one_and_update(filter, update, options)
+            .await
+            .map_err(MongoQueryError)?;
+        return outbox_option
+            .map(|outbox_document| self.doc_to_outbox_entry(&outbox_document))
+            .transpose();
+    }
+
+    /// Records outcome of a delivery attempt on the outbox entry.
+    /// Pending entries are attempted again after 'retry_after'.
+    pub async fn update_outbox_entry(
+        &self,
+        id: &str,
+        status: &str,
+        attempts: u32,
+        retry_after: Duration,
+        last_error: &str,
+    ) -> Result<()> {
+        let filter = doc! {
+            ID: parse_object_id(id, InvalidOutboxIDError)?,
+        };
+        let update = doc! {
+            "$set": {
+                STATUS: status,
+                ATTEMPTS: attempts as i32,
+                NEXT_ATTEMPT_AT: date_time_after(DateTime::now(), retry_after),
+                LAST_ERROR: last_error,
+            },
+        };
+        self.get_named_collection(WEBHOOK_OUTBOX_COLLECTION)
+            .update_one(filter, update, None)
+            .await
+            .map_err(MongoQueryError)?;
+        return Ok(());
+    }
+
+    /// Appends delivery attempt to the delivery log.
+    pub async fn record_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<()> {
+        let doc = doc! {
+            WEBHOOK_ID: delivery.webhook_id.clone(),
+            OUTBOX_ID: delivery.outbox_id.clone(),
+            EVENT_TYPE: delivery.event_type.clone(),
+            ATTEMPT: delivery.attempt as i32,
+            SUCCESS: delivery.success,
+            STATUS_CODE: delivery.status_code.map(|status_code| status_code as i32),
+            ERROR: delivery.error.clone(),
+            DURATION_MS: delivery.duration_ms as i64,
+            DELIVERED_AT: DateTime::now(),
+        };
+        self.get_named_collection(WEBHOOK_DELIVERIES_COLLECTION)
+            .insert_one(doc, None)
+            .await
+            .map_err(MongoQueryError)?;
+        return Ok(());
+    }
+
+    /// Function to Read delivery log of a Webhook, newest first.
+    #[tracing::instrument(skip(self))]
+    pub async fn read_webhook_deliveries(&self, webhook_id: &str) -> Result<Vec<WebhookDelivery>> {
+        parse_object_id(webhook_id, InvalidWebhookIDError)?;
+        let options = FindOptions::builder()
+            .sort(doc! { DELIVERED_AT: -1 })
+            .build();
+        let mut cursor = self
+            .get_named_collection(WEBHOOK_DELIVERIES_COLLECTION)
+            .find(doc! { WEBHOOK_ID: webhook_id }, options)
+            .await
+            .map_err(MongoQueryError)?;
+
+        let mut deliveries: Vec<WebhookDelivery> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            deliveries.push(self.doc_to_webhook_delivery(&doc?)?);
+        }
+        return Ok(deliveries);
+    }
+
+    /// Function to Read outbox entries which ran out of delivery attempts.
+    #[tracing::instrument(skip(self))]
+    pub async fn read_dead_letters(&self) -> Result<Vec<OutboxEntry>> {
+        let options = FindOptions::builder()
+            .sort(doc! { NEXT_ATTEMPT_AT: -1 })
+            .build();
+        let mut cursor = self
+            .get_named_collection(WEBHOOK_OUTBOX_COLLECTION)
+            .find(doc! { STATUS: OUTBOX_DEAD }, options)
+            .await
+            .map_err(MongoQueryError)?;
+
+        let mut dead_letters: Vec<OutboxEntry> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            dead_letters.push(self.doc_to_outbox_entry(&doc?)?);
+        }
+        return Ok(dead_letters);
+    }
+
+    /// Moves dead letter back to pending with a fresh set of attempts.
+    /// Returns number of dead letters rescheduled (1).
+    #[tracing::instrument(skip(self))]
+    pub async fn retry_dead_letter(&self, id: &str) -> Result<u64> {
+        let filter = doc! {
+            ID: parse_object_id(id, InvalidDeadLetterIDError)?,
+            STATUS: OUTBOX_DEAD,
+        };
+        let update = doc! {
+            "$set": {
+                STATUS: OUTBOX_PENDING,
+                ATTEMPTS: 0,
+                NEXT_ATTEMPT_AT: DateTime::now(),
+                LAST_ERROR: "",
+            },
+        };
+        let update_result = self
+            .get_named_collection(WEBHOOK_OUTBOX_COLLECTION)
+            .update_one(filter, update, None)
+            .await
+            .map_err(MongoQueryError)?;
+        return Ok(update_result.matched_count);
+    }
+
+    /// Creates production house using Production House Request.
+    /// Returns ProductionHouseConflictError if name or an alias is a name of another production house.
+    #[tracing::instrument(skip(self, production_house_request))]
+    pub async fn create_production_house(
+        &self,
+        production_house_request: &ProductionHouseRequest,
+    ) -> Result<ProductionHouse> {
+        let name_keys = production_house::name_keys(
+            &production_house_request.name,
+            &production_house_request.aliases,
+        );
+        self.check_production_house_names(&name_keys, None).await?;
+        let mut doc = doc! {
+            NAME: production_house_request.name.clone(),
+            ALIASES: production_house_request.aliases.clone(),
+            NAME_KEYS: name_keys,
+            CREATED_AT: DateTime::now(),
+        };
+        // Unique index on name keys rejects names taken by a production house created in the meantime.
+        let insert_one_result = self
+            .get_named_collection(PRODUCTION_HOUSES_COLLECTION)
+            .insert_one(&doc, None)
+            .await
+            .map_err(|e| match is_duplicate_key_error(&e) {
+                true => ProductionHouseConflictError(production_house_request.name.clone()),
+                false => MongoQueryError(e),
+            })?;
+        doc.insert(ID, insert_one_result.inserted_id);
+        return self.doc_to_production_house(&doc);
+    }
+
+    /// Function to Read Production Houses, ordered by name.
+    #[tracing::instrument(skip(self))]
+    pub async fn read_production_houses(&self) -> Result<Vec<ProductionHouse>> {
+        let options = FindOptions::builder().sort(doc! { NAME: 1 }).build();
+        let mut cursor = self
+            .get_named_collection(PRODUCTION_HOUSES_COLLECTION)
+            .find(None, options)
+            .await
+            .map_err(MongoQueryError)?;
+
+        let mut production_houses: Vec<ProductionHouse> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            production_houses.push(self.doc_to_production_house(&doc?)?);
+        }
+        return Ok(production_houses);
+    }
+
+    /// Function to fetch Production House using Production House Id.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_production_house(&self, id: &str) -> Result<Option<ProductionHouse>> {
+        let filter = doc! {
+            ID: parse_object_id(id, InvalidProductionHouseIDError)?,
+        };
+        let production_house_option = self
+            .get_named_collection(PRODUCTION_HOUSES_COLLECTION)
+            .find_one(filter, None)
+            .await
+            .map_err(MongoQueryError)?;
+        return production_house_option
+            .map(|doc| self.doc_to_production_house(&doc))
+            .transpose();
+    }
+
+    /// Returns production house whose name or alias matches the name, creating one named so if there is none.
+    pub async fn resolve_production_house(&self, name: &str) -> Result<ProductionHouse> {
+        let filter = doc! { NAME_KEYS: production_house::name_key(name) };
+        let collection = self.get_named_collection(PRODUCTION_HOUSES_COLLECTION);
+        if let Some(doc) = collection
+            .find_one(filter.clone(), None)
+            .await
+            .map_err(MongoQueryError)?
+        {
+            return self.doc_to_production_house(&doc);
+        }
+        let production_house_request = ProductionHouseRequest {
+            name: name.to_string(),
+            aliases: Vec::new(),
+        };
+        return match self
+            .create_production_house(&production_house_request)
+            .await
+        {
+            Ok(production_house) => {
+                log::info!(
+                    "Production house {} created for '{}'.",
+                    production_house.id,
+                    name
+                );
+                Ok(production_house)
+            }
+            // Created by another request in the meantime.
+            Err(ProductionHouseConflictError(_)) => {
+                match collection
+                    .find_one(filter, None)
+                    .await
+                    .map_err(MongoQueryError)?
+                {
+                    Some(doc) => self.doc_to_production_house(&doc),
+                    None => Err(ProductionHouseConflictError(name.to_string())),
+                }
+            }
+            Err(e) => Err(e),
+        };
+    }
+
+    /// Replaces name and aliases of production house.
+    /// Movies of the production house are renamed when its name changes.
+    #[tracing::instrument(skip(self, production_house, production_house_request))]
+    pub async fn update_production_house(
+        &self,
+        production_house: &ProductionHouse,
+        production_house_request: &ProductionHouseRequest,
+    ) -> Result<ProductionHouse> {
+        let name_keys = production_house::name_keys(
+            &production_house_request.name,
+            &production_house_request.aliases,
+        );
+        self.check_production_house_names(&name_keys, Some(&production_house.id))
+            .await?;
+        let updated_production_house = ProductionHouse {
+            name: production_house_request.name.clone(),
+            aliases: production_house_request.aliases.clone(),
+            ..production_house.clone()
+        };
+        let mut write = self.start_movie_write().await?;
+        self.set_production_house_names(&updated_production_house, name_keys, &mut write.session)
+            .await?;
+        if updated_production_house.name != production_house.name {
+            self.relink_movies(
+                &mut write,
+                std::slice::from_ref(&production_house.id),
+                &updated_production_house,
+            )
+            .await?;
+        }
+        self.commit_movie_write(write).await?;
+        return Ok(updated_production_house);
+    }
+
+    /// Merges production houses into 'production_house'.
+    /// Names and aliases of the merged production houses become its aliases, their movies are re-pointed to it
+    /// and they are deleted. Returns the production house as merged.
+    /// Merge is one transaction where supported. Otherwise movies are re-pointed before the merged production houses
+    /// are deleted, so that no movie refers to a deleted production house if the merge fails part way.
+    #[tracing::instrument(skip(self, production_house, merged_production_houses))]
+    pub async fn merge_production_houses(
+        &self,
+        production_house: &ProductionHouse,
+        merged_production_houses: &[ProductionHouse],
+    ) -> Result<ProductionHouse> {
+        let merged_ids: Vec<String> = merged_production_houses
+            .iter()
+            .map(|merged_production_house| merged_production_house.id.clone())
+            .collect();
+        let merged_object_ids = merged_ids
+            .iter()
+            .map(|id| parse_object_id(id, InvalidProductionHouseIDError))
+            .collect::<Result<Vec<ObjectId>>>()?;
+        let merged_production_house = ProductionHouse {
+            aliases: production_house::merged_aliases(production_house, merged_production_houses),
+            ..production_house.clone()
+        };
+        let name_keys = production_house::name_keys(
+            &merged_production_house.name,
+            &merged_production_house.aliases,
+        );
+        let mut write = self.start_movie_write().await?;
+        let number_of_movies_relinked = self
+            .relink_movies(&mut write, &merged_ids, &merged_production_house)
+            .await?;
+        // Merged production houses are deleted before their names become aliases, as names are unique.
+        self.get_named_collection(PRODUCTION_HOUSES_COLLECTION)
+            .delete_many_with_session(
+                doc! { ID: { "$in": merged_object_ids } },
+                None,
+                &mut write.session,
+            )
+            .await
+            .map_err(MongoQueryError)?;
+        self.set_production_house_names(&merged_production_house, name_keys, &mut write.session)
+            .await?;
+        self.commit_movie_write(write).await?;
+        log::info!(
+            "{} production houses merged into {}, {} movies re-pointed.",
+            merged_ids.len(),
+            merged_production_house.id,
+            number_of_movies_relinked
+        );
+        return Ok(merged_production_house);
+    }
+
+    /// Deletes production house using Production House Id.
+    /// Returns ProductionHouseConflictError if movies refer to it, number of production houses deleted (1) otherwise.
+    #[tracing::instrument(skip(self))]
+    pub async fn delete_production_house(&self, id: &str) -> Result<u64> {
+        let filter = doc! {
+            ID: parse_object_id(id, InvalidProductionHouseIDError)?,
+        };
+        let number_of_movies = self
+            .get_collection()
+            .count_documents(doc! { PRODUCTION_HOUSE_ID: id }, None)
+            .await
+            .map_err(MongoQueryError)?;
+        if number_of_movies > 0 {
+            return Err(ProductionHouseConflictError(format!(
+                "{} movies refer to production house {}",
+                number_of_movies, id
+            )));
+        }
+        let delete_result = self
+            .get_named_collection(PRODUCTION_HOUSES_COLLECTION)
+            .delete_one(filter, None)
+            .await
+            .map_err(MongoQueryError)?;
+        return Ok(delete_result.deleted_count);
+    }
+
+    /// Function to Read Movies of production house.
+    #[tracing::instrument(skip(self))]
+    pub async fn read_production_house_movies(&self, id: &str) -> Result<Vec<Movie>> {
+        let mut cursor = self
+            .get_collection()
+            .find(doc! { PRODUCTION_HOUSE_ID: id }, None)
+            .await
+            .map_err(MongoQueryError)?;
+
+        let mut movies: Vec<Movie> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            movies.push(self.doc_to_movie(&doc?)?);
+        }
+        return Ok(movies);
+    }
+
+    /// Links movies without production house id to the production house matching their name,
+    /// creating production houses as needed. Returns number of movies linked.
+    pub async fn link_movies_to_production_houses(&self) -> Result<u64> {
+        let mut cursor = self
+            .get_collection()
+            .find(doc! { PRODUCTION_HOUSE_ID: { "$exists": false } }, None)
+            .await
+            .map_err(MongoQueryError)?;
+        let mut number_of_movies_linked = 0;
+        while let Some(doc) = cursor.next().await {
+            let doc = doc?;
+            let production_house = self
+                .resolve_production_house(doc.get_str(PRODUCTION_HOUSE)?)
+                .await?;
+            self.get_collection()
+                .update_one(
+                    doc! { ID: doc.get_object_id(ID)? },
+                    doc! {
+                        "$set": {
+                            PRODUCTION_HOUSE: production_house.name,
+                            PRODUCTION_HOUSE_ID: production_house.id,
+                        },
+                    },
+                    None,
+                )
+                .await
+                .map_err(MongoQueryError)?;
+            number_of_movies_linked += 1;
+        }
+        return Ok(number_of_movies_linked);
+    }
+
+    /// Returns ProductionHouseConflictError if a name key belongs to another production house than 'id'.
+    async fn check_production_house_names(
+        &self,
+        name_keys: &[String],
+        id: Option<&str>,
+    ) -> Result<()> {
+        let mut filter = doc! { NAME_KEYS: { "$in": name_keys } };
+        if let Some(id) = id {
+            filter.insert(
+                ID,
+                doc! { "$ne": parse_object_id(id, InvalidProductionHouseIDError)? },
+            );
+        }
+        let conflicting_doc = self
+            .get_named_collection(PRODUCTION_HOUSES_COLLECTION)
+            .find_one(filter, None)
+            .await
+            .map_err(MongoQueryError)?;
+        if let Some(conflicting_doc) = conflicting_doc {
+            let conflicting_production_house = self.doc_to_production_house(&conflicting_doc)?;
+            return Err(ProductionHouseConflictError(format!(
+                "name or alias is already used by production house {} '{}'",
+                conflicting_production_house.id, conflicting_production_house.name
+            )));
+        }
+        return Ok(());
+    }
+
+    async fn set_production_house_names(
+        &self,
+        production_house: &ProductionHouse,
+        name_keys: Vec<String>,
+        session: &mut ClientSession,
+    ) -> Result<()> {
+        self.get_named_collection(PRODUCTION_HOUSES_COLLECTION)
+            .update_one_with_session(
+                doc! { ID: parse_object_id(&production_house.id, InvalidProductionHouseIDError)? },
+                doc! {
+                    "$set": {
+                        NAME: production_house.name.clone(),
+                        ALIASES: production_house.aliases.clone(),
+                        NAME_KEYS: name_keys,
+                    },
+                },
+                None,
+                session,
+            )
+            .await
+            .map_err(MongoQueryError)?;
+        return Ok(());
+    }
+
+    /// Points movies of production houses 'ids' to 'production_house' and renames them accordingly
+    /// within the movie write, queuing update event of every movie. Returns number of movies re-pointed.
+    async fn relink_movies(
+        &self,
+        write: &mut MovieWrite,
+        ids: &[String],
+        production_house: &ProductionHouse,
+    ) -> Result<u64> {
+        let mut cursor = self
+            .get_collection()
+            .find(doc! { PRODUCTION_HOUSE_ID: { "$in": ids } }, None)
+            .await
+            .map_err(MongoQueryError)?;
+        let mut movies: Vec<Movie> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            movies.push(self.doc_to_movie(&doc?)?);
+        }
+        self.get_collection()
+            .update_many_with_session(
+                doc! { PRODUCTION_HOUSE_ID: { "$in": ids } },
+                doc! {
+                    "$set": {
+                        PRODUCTION_HOUSE: production_house.name.clone(),
+                        PRODUCTION_HOUSE_ID: production_house.id.clone(),
+                    },
+                },
+                None,
+                &mut write.session,
+            )
+            .await
+            .map_err(MongoQueryError)?;
+        for movie in &movies {
+            let mut updated_movie = movie.clone();
+            updated_movie.production_house = production_house.name.clone();
+            updated_movie.production_house_id = Some(production_house.id.clone());
+            self.queue_movie_event(
+                write,
+                MovieEvent::new(MovieEventType::Updated, Some(movie), Some(updated_movie)),
+            )
+            .await?;
+        }
+        return Ok(movies.len() as u64);
+    }
+
+    /// Replaces territory breakdown of movie, an empty breakdown removes it.
+    /// Returns the movie as updated.
+    #[tracing::instrument(skip(self, movie, territories))]
+    pub async fn update_territories(
+        &self,
+        movie: &Movie,
+        territories: &[TerritoryCollection],
+    ) -> Result<Movie> {
+        let query = doc! {
+            MOVIEID: movie.movie_id as i32,
+        };
+        let update = match territories.is_empty() {
+            true => doc! { "$unset": { TERRITORIES: "" } },
+            false => doc! {
+                "$set": {
+                    TERRITORIES: territories.iter().map(territory_collection_doc).collect::<Vec<Document>>(),
+                },
+            },
+        };
+        let mut write = self.start_movie_write().await?;
+        self.get_collection()
+            .update_one_with_session(query, update, None, &mut write.session)
+            .await
+            .map_err(MongoQueryError)?;
+        let mut updated_movie = movie.clone();
+        updated_movie.territories = match territories.is_empty() {
+            true => None,
+            false => Some(territories.to_vec()),
+        };
+        updated_movie.territories_stale = false;
+        if updated_movie.territories != movie.territories {
+            self.queue_movie_event(
+                &mut write,
+                MovieEvent::new(
+                    MovieEventType::Updated,
+                    Some(movie),
+                    Some(updated_movie.clone()),
+                ),
+            )
+            .await?;
+        }
+        self.commit_movie_write(write).await?;
+        return Ok(updated_movie);
+    }
+
+    /// Replaces cast and crew of movie, empty credits remove them.
+    pub async fn update_credits(&self, movie: &Movie, credits: &[Credit]) -> Result<Movie> {
+        let query = doc! {
+            MOVIEID: movie.movie_id as i32,
+        };
+        let update = match credits.is_empty() {
+            true => doc! { "$unset": { CREDITS: "" } },
+            false => doc! {
+                "$set": {
+                    CREDITS: credits.iter().map(credit_doc).collect::<Vec<Document>>(),
+                },
+            },
+        };
+        let mut write = self.start_movie_write().await?;
+        self.get_collection()
+            .update_one_with_session(query, update, None, &mut write.session)
+            .await
+            .map_err(MongoQueryError)?;
+        let mut updated_movie = movie.clone();
+        updated_movie.credits = match credits.is_empty() {
+            true => None,
+            false => Some(credits.to_vec()),
+        };
+        if updated_movie.credits != movie.credits {
+            self.queue_movie_event(
+                &mut write,
+                MovieEvent::new(
+                    MovieEventType::Updated,
+                    Some(movie),
+                    Some(updated_movie.clone()),
+                ),
+            )
+            .await?;
+        }
+        self.commit_movie_write(write).await?;
+        return Ok(updated_movie);
+    }
+
+    /// Creates person using Person Request.
+    #[tracing::instrument(skip(self, person_request))]
+    pub async fn create_person(&self, person_request: &PersonRequest) -> Result<Person> {
+        let mut doc = doc! {
+            NAME: person_request.name.clone(),
+            CREATED_AT: DateTime::now(),
+        };
+        let insert_one_result = self
+            .get_named_collection(PEOPLE_COLLECTION)
+            .insert_one(&doc, None)
+            .await
+            .map_err(MongoQueryError)?;
+        doc.insert(ID, insert_one_result.inserted_id);
+        return self.doc_to_person(&doc);
+    }
+
+    /// Function to Read People, ordered by name.
+    #[tracing::instrument(skip(self))]
+    pub async fn read_people(&self) -> Result<Vec<Person>> {
+        let options = FindOptions::builder().sort(doc! { NAME: 1 }).build();
+        let mut cursor = self
+            .get_named_collection(PEOPLE_COLLECTION)
+            .find(None, options)
+            .await
+            .map_err(MongoQueryError)?;
+
+        let mut people: Vec<Person> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            people.push(self.doc_to_person(&doc?)?);
+        }
+        return Ok(people);
+    }
+
+    /// Function to fetch Person using Person Id.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_person(&self, id: &str) -> Result<Option<Person>> {
+        let filter = doc! {
+            ID: parse_object_id(id, InvalidPersonIDError)?,
+        };
+        let person_option = self
+            .get_named_collection(PEOPLE_COLLECTION)
+            .find_one(filter, None)
+            .await
+            .map_err(MongoQueryError)?;
+        return person_option
+            .map(|doc| self.doc_to_person(&doc))
+            .transpose();
+    }
+
+    /// Renames person. Credits of the person are renamed with it, publishing update event of every credited movie.
+    #[tracing::instrument(skip(self, person, person_request))]
+    pub async fn update_person(
+        &self,
+        person: &Person,
+        person_request: &PersonRequest,
+    ) -> Result<Person> {
+        let updated_person = Person {
+            name: person_request.name.clone(),
+            ..person.clone()
+        };
+        self.get_named_collection(PEOPLE_COLLECTION)
+            .update_one(
+                doc! { ID: parse_object_id(&person.id, InvalidPersonIDError)? },
+                doc! { "$set": { NAME: updated_person.name.clone() } },
+                None,
+            )
+            .await
+            .map_err(MongoQueryError)?;
+        if updated_person.name == person.name {
+            return Ok(updated_person);
+        }
+        let movies = self.read_person_movies(&person.id).await?;
+        let options = UpdateOptions::builder()
+            .array_filters(vec![doc! { format!("credit.{}", PERSON_ID): &person.id }])
+            .build();
+        let mut write = self.start_movie_write().await?;
+        self.get_collection()
+            .update_many_with_session(
+                doc! { CREDITS_PERSON_ID: &person.id },
+                doc! { "$set": { format!("{}.$[credit].{}", CREDITS, NAME): updated_person.name.clone() } },
+                options,
+                &mut write.session,
+            )
+            .await
+            .map_err(MongoQueryError)?;
+        for movie in &movies {
+            let mut updated_movie = movie.clone();
+            for credit in updated_movie.credits.iter_mut().flatten() {
+                if credit.person_id == person.id {
+                    credit.name = updated_person.name.clone();
+                }
+            }
+            self.queue_movie_event(
+                &mut write,
+                MovieEvent::new(MovieEventType::Updated, Some(movie), Some(updated_movie)),
+            )
+            .await?;
+        }
+        self.commit_movie_write(write).await?;
+        return Ok(updated_person);
+    }
+
+    /// Deletes person using Person Id.
+    /// Returns PersonConflictError if movies credit the person, number of people deleted (1) otherwise.
+    #[tracing::instrument(skip(self))]
+    pub async fn delete_person(&self, id: &str) -> Result<u64> {
+        let filter = doc! {
+            ID: parse_object_id(id, InvalidPersonIDError)?,
+        };
+        let number_of_movies = self
+            .get_collection()
+            .count_documents(doc! { CREDITS_PERSON_ID: id }, None)
+            .await
+            .map_err(MongoQueryError)?;
+        if number_of_movies > 0 {
+            return Err(PersonConflictError(format!(
+                "{} movies credit person {}",
+                number_of_movies, id
+            )));
+        }
+        let delete_result = self
+            .get_named_collection(PEOPLE_COLLECTION)
+            .delete_one(filter, None)
+            .await
+            .map_err(MongoQueryError)?;
+        return Ok(delete_result.deleted_count);
+    }
+
+    /// Function to Read Movies crediting person.
+    #[tracing::instrument(skip(self))]
+    pub async fn read_person_movies(&self, id: &str) -> Result<Vec<Movie>> {
+        let mut cursor = self
+            .get_collection()
+            .find(doc! { CREDITS_PERSON_ID: id }, None)
+            .await
+            .map_err(MongoQueryError)?;
+
+        let mut movies: Vec<Movie> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            movies.push(self.doc_to_movie(&doc?)?);
+        }
+        return Ok(movies);
+    }
+
+    /// Replaces relations of movie to other movies, empty relations remove them.
+    pub async fn update_relations(
+        &self,
+        movie: &Movie,
+        relations: &[MovieRelation],
+    ) -> Result<Movie> {
+        let query = doc! {
+            MOVIEID: movie.movie_id as i32,
+        };
+        let update = match relations.is_empty() {
+            true => doc! { "$unset": { RELATIONS: "" } },
+            false => doc! {
+                "$set": {
+                    RELATIONS: relations.iter().map(movie_relation_doc).collect::<Vec<Document>>(),
+                },
+            },
+        };
+        let mut write = self.start_movie_write().await?;
+        self.get_collection()
+            .update_one_with_session(query, update, None, &mut write.session)
+            .await
+            .map_err(MongoQueryError)?;
+        let mut updated_movie = movie.clone();
+        updated_movie.relations = match relations.is_empty() {
+            true => None,
+            false => Some(relations.to_vec()),
+        };
+        if updated_movie.relations != movie.relations {
+            self.queue_movie_event(
+                &mut write,
+                MovieEvent::new(
+                    MovieEventType::Updated,
+                    Some(movie),
+                    Some(updated_movie.clone()),
+                ),
+            )
+            .await?;
+        }
+        self.commit_movie_write(write).await?;
+        return Ok(updated_movie);
+    }
+
+    /// Function to Read Movies related to movie, e.g. its sequels.
+    #[tracing::instrument(skip(self))]
+    pub async fn read_related_movies(&self, movie_id: u8) -> Result<Vec<Movie>> {
+        let mut cursor = self
+            .get_collection()
+            .find(doc! { RELATIONS_MOVIE_ID: movie_id as i32 }, None)
+            .await
+            .map_err(MongoQueryError)?;
+
+        let mut movies: Vec<Movie> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            movies.push(self.doc_to_movie(&doc?)?);
+        }
+        return Ok(movies);
+    }
+
+    /// Function to Read Movies with the Movie Ids, in no particular order. Unknown Movie Ids are left out.
+    #[tracing::instrument(skip(self))]
+    pub async fn read_movies_by_ids(&self, movie_ids: &[u8]) -> Result<Vec<Movie>> {
+        let mut cursor = self
+            .get_collection()
+            .find(doc! { MOVIEID: { "$in": movie_ids_bson(movie_ids) } }, None)
+            .await
+            .map_err(MongoQueryError)?;
+
+        let mut movies: Vec<Movie> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            movies.push(self.doc_to_movie(&doc?)?);
+        }
+        return Ok(movies);
+    }
+
+    /// Function to Read Movies matching any word of the query on the text index with their text scores,
+    /// highest score first, at most 'limit' of them.
+    /// Returns no movie while the text index does not exist, i.e. before 'boxoffice_admin ensure-indexes' is run.
+    #[tracing::instrument(skip(self))]
+    pub async fn search_movies_text(&self, query: &str, limit: usize) -> Result<Vec<(Movie, f64)>> {
+        let text_score = doc! { TEXT_SCORE: { "$meta": "textScore" } };
+        let options = FindOptions::builder()
+            .projection(text_score.clone())
+            .sort(text_score)
+            .limit(i64::try_from(limit).unwrap_or(i64::MAX))
+            .build();
+        let find_result = self
+            .get_collection()
+            .find(doc! { "$text": { "$search": query } }, options)
+            .await;
+        let mut cursor = match find_result {
+            Err(e) if is_index_not_found_error(&e) => {
+                log::warn!("Text index of movies not found, run 'boxoffice_admin ensure-indexes'.");
+                return Ok(Vec::new());
+            }
+            find_result => find_result.map_err(MongoQueryError)?,
+        };
+
+        let mut movies: Vec<(Movie, f64)> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            let doc = doc?;
+            movies.push((self.doc_to_movie(&doc)?, doc.get_f64(TEXT_SCORE)?));
+        }
+        return Ok(movies);
+    }
+
+    /// Creates franchise using Franchise Request.
+    /// Returns UnknownInstalmentError if a movie does not exist,
+    /// FranchiseConflictError if a movie is an instalment of another franchise.
+    #[tracing::instrument(skip(self, franchise_request))]
+    pub async fn create_franchise(
+        &self,
+        franchise_request: &FranchiseRequest,
+    ) -> Result<Franchise> {
+        self.check_instalments(&franchise_request.movie_ids, None)
+            .await?;
+        let mut doc = doc! {
+            NAME: franchise_request.name.clone(),
+            MOVIE_IDS: movie_ids_bson(&franchise_request.movie_ids),
+            CREATED_AT: DateTime::now(),
+        };
+        let insert_one_result = self
+            .get_named_collection(FRANCHISES_COLLECTION)
+            .insert_one(&doc, None)
+            .await
+            .map_err(MongoQueryError)?;
+        doc.insert(ID, insert_one_result.inserted_id);
+        return self.doc_to_franchise(&doc);
+    }
+
+    /// Function to Read Franchises, ordered by name.
+    #[tracing::instrument(skip(self))]
+    pub async fn read_franchises(&self) -> Result<Vec<Franchise>> {
+        let options = FindOptions::builder().sort(doc! { NAME: 1 }).build();
+        let mut cursor = self
+            .get_named_collection(FRANCHISES_COLLECTION)
+            .find(None, options)
+            .await
+            .map_err(MongoQueryError)?;
+
+        let mut franchises: Vec<Franchise> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            franchises.push(self.doc_to_franchise(&doc?)?);
+        }
+        return Ok(franchises);
+    }
+
+    /// Function to fetch Franchise using Franchise Id.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_franchise(&self, id: &str) -> Result<Option<Franchise>> {
+        let filter = doc! {
+            ID: parse_object_id(id, InvalidFranchiseIDError)?,
+        };
+        let franchise_option = self
+            .get_named_collection(FRANCHISES_COLLECTION)
+            .find_one(filter, None)
+            .await
+            .map_err(MongoQueryError)?;
+        return franchise_option
+            .map(|doc| self.doc_to_franchise(&doc))
+            .transpose();
+    }
+
+    /// Replaces name and instalments of franchise, with the same checks as creating one.
+    #[tracing::instrument(skip(self, franchise, franchise_request))]
+    pub async fn update_franchise(
+        &self,
+        franchise: &Franchise,
+        franchise_request: &FranchiseRequest,
+    ) -> Result<Franchise> {
+        self.check_instalments(&franchise_request.movie_ids, Some(&franchise.id))
+            .await?;
+        self.get_named_collection(FRANCHISES_COLLECTION)
+            .update_one(
+                doc! { ID: parse_object_id(&franchise.id, InvalidFranchiseIDError)? },
+                doc! {
+                    "$set": {
+                        NAME: franchise_request.name.clone(),
+                        MOVIE_IDS: movie_ids_bson(&franchise_request.movie_ids),
+                    },
+                },
+                None,
+            )
+            .await
+            .map_err(MongoQueryError)?;
+        return Ok(Franchise {
+            name: franchise_request.name.clone(),
+            movie_ids: franchise_request.movie_ids.clone(),
+            ..franchise.clone()
+        });
+    }
+
+    /// Deletes franchise using Franchise Id, its movies are kept.
+    /// Returns number of franchises deleted.
+    #[tracing::instrument(skip(self))]
+    pub async fn delete_franchise(&self, id: &str) -> Result<u64> {
+        let filter = doc! {
+            ID: parse_object_id(id, InvalidFranchiseIDError)?,
+        };
+        let delete_result = self
+            .get_named_collection(FRANCHISES_COLLECTION)
+            .delete_one(filter, None)
+            .await
+            .map_err(MongoQueryError)?;
+        return Ok(delete_result.deleted_count);
+    }
+
+    /// Checks that instalments are existing movies which are not instalments of another franchise.
+    async fn check_instalments(&self, movie_ids: &[u8], id: Option<&str>) -> Result<()> {
+        let movies = self.read_movies_by_ids(movie_ids).await?;
+        if let Some(unknown_movie_id) = movie_ids
+            .iter()
+            .find(|movie_id| !movies.iter().any(|movie| movie.movie_id == **movie_id))
+        {
+            return Err(UnknownInstalmentError(unknown_movie_id.to_string()));
+        }
+        let mut filter = doc! { MOVIE_IDS: { "$in": movie_ids_bson(movie_ids) } };
+        if let Some(id) = id {
+            filter.insert(
+                ID,
+                doc! { "$ne": parse_object_id(id, InvalidFranchiseIDError)? },
+            );
+        }
+        let conflicting_doc = self
+            .get_named_collection(FRANCHISES_COLLECTION)
+            .find_one(filter, None)
+            .await
+            .map_err(MongoQueryError)?;
+        if let Some(conflicting_doc) = conflicting_doc {
+            let conflicting_franchise = self.doc_to_franchise(&conflicting_doc)?;
+            return Err(FranchiseConflictError(format!(
+                "movie is already an instalment of franchise {} '{}'",
+                conflicting_franchise.id, conflicting_franchise.name
+            )));
+        }
+        return Ok(());
+    }
+
+    /// Records collection entry of movie using Create Collection Entry Request and rolls entries of the movie up
+    /// into its collection, in one movie write.
+    /// Returns ReleaseLifecycleError if the movie is not collecting or the entry is before its release date,
+    /// CollectionEntryConflictError if the entry overlaps an entry of the same period and territory.
+    #[tracing::instrument(skip(self, create_collection_entry_request))]
+    pub async fn create_collection_entry(
+        &self,
+        movie_id: u8,
+        create_collection_entry_request: &CreateCollectionEntryRequest,
+    ) -> Result<CollectionEntry> {
+        let territory = create_collection_entry_request
+            .territory
+            .clone()
+            .unwrap_or_else(|| DEFAULT_TERRITORY.to_string());
+        let days = collection_run::covered_days(
+            &create_collection_entry_request.date,
+            create_collection_entry_request.period,
+        )?;
+        let mut write = self.start_movie_write().await?;
+        let movie_doc = self.find_movie_doc(&mut write, movie_id).await?;
+        release::check_collection_entry(
+            &self.doc_to_movie(&movie_doc)?,
+            &create_collection_entry_request.date,
+        )?;
+        let mut doc = doc! {
+            MOVIEID: movie_id as i32,
+            DATE: create_collection_entry_request.date.clone(),
+            PERIOD: create_collection_entry_request.period.to_string(),
+            TERRITORY: territory.clone(),
+            DAYS: days,
+            COLLECTION_CRORES: create_collection_entry_request.collection_crores.to_string(),
+            CREATED_AT: DateTime::now(),
+        };
+        // Unique index on days rejects entries overlapping an entry of the same period and territory.
+        let insert_one_result = self
+            .get_named_collection(COLLECTION_ENTRIES_COLLECTION)
+            .insert_one_with_session(&doc, None, &mut write.session)
+            .await
+            .map_err(|e| match is_duplicate_key_error(&e) {
+                true => CollectionEntryConflictError(format!(
+                    "{} {} from {}",
+                    territory,
+                    create_collection_entry_request.period,
+                    create_collection_entry_request.date
+                )),
+                false => MongoQueryError(e),
+            })?;
+        doc.insert(ID, insert_one_result.inserted_id);
+        let collection_entry = self.doc_to_collection_entry(&doc)?;
+        self.roll_up_collection(&mut write, movie_doc).await?;
+        self.commit_movie_write(write).await?;
+        return Ok(collection_entry);
+    }
+
+    /// Stores days covered by collection entries stored before days were recorded.
+    /// Entries overlapping an entry of the same period and territory are logged and left without days.
+    /// Returns number of entries updated.
+    pub async fn store_collection_entry_days(&self) -> Result<u64> {
+        let collection_entries = self.get_named_collection(COLLECTION_ENTRIES_COLLECTION);
+        let mut cursor = collection_entries
+            .find(doc! { DAYS: { "$exists": false } }, None)
+            .await
+            .map_err(MongoQueryError)?;
+        let mut number_of_entries_updated = 0;
+        while let Some(doc) = cursor.next().await {
+            let collection_entry = self.doc_to_collection_entry(&doc?)?;
+            let days =
+                collection_run::covered_days(&collection_entry.date, collection_entry.period)?;
+            let update_result = collection_entries
+                .update_one(
+                    doc! { ID: parse_object_id(&collection_entry.id, InvalidCollectionEntryIDError)? },
+                    doc! { "$set": { DAYS: days } },
+                    None,
+                )
+                .await;
+            match update_result {
+                Ok(_) => number_of_entries_updated += 1,
+                Err(e) if is_duplicate_key_error(&e) => log::warn!(
+                    "Collection entry {} of Movie ID {} overlaps another {} entry of {}, left without days.",
+                    collection_entry.id,
+                    collection_entry.movie_id,
+                    collection_entry.period,
+                    collection_entry.territory
+                ),
+                Err(e) => return Err(MongoQueryError(e)),
+            }
+        }
+        return Ok(number_of_entries_updated);
+    }
+
+    /// Function to Read collection entries of movie, oldest first.
+    #[tracing::instrument(skip(self))]
+    pub async fn read_collection_entries(&self, movie_id: u8) -> Result<Vec<CollectionEntry>> {
+        let options = FindOptions::builder().sort(doc! { DATE: 1 }).build();
+        let mut cursor = self
+            .get_named_collection(COLLECTION_ENTRIES_COLLECTION)
+            .find(doc! { MOVIEID: movie_id as i32 }, options)
+            .await
+            .map_err(MongoQueryError)?;
+
+        let mut collection_entries: Vec<CollectionEntry> = Vec::new();
+        while let Some(doc) = cursor.next().await {
+            collection_entries.push(self.doc_to_collection_entry(&doc?)?);
+        }
+        return Ok(collection_entries);
+    }
+
+    /// Deletes collection entry of movie using Collection Entry Id and rolls remaining entries of the movie up
+    /// into its collection, in one movie write.
+    /// Returns number of entries deleted (1), ReleaseLifecycleError if the movie is not collecting.
+    #[tracing::instrument(skip(self))]
+    pub async fn delete_collection_entry(&self, movie_id: u8, id: &str) -> Result<u64> {
+        let filter = doc! {
+            ID: parse_object_id(id, InvalidCollectionEntryIDError)?,
+            MOVIEID: movie_id as i32,
+        };
+        let mut write = self.start_movie_write().await?;
+        let movie_doc = self.find_movie_doc(&mut write, movie_id).await?;
+        release::check_collecting(&self.doc_to_movie(&movie_doc)?)?;
+        let delete_result = self
+            .get_named_collection(COLLECTION_ENTRIES_COLLECTION)
+            .delete_one_with_session(filter, None, &mut write.session)
+            .await
+            .map_err(MongoQueryError)?;
+        if delete_result.deleted_count == 0 {
+            return Ok(0);
+        }
+        self.roll_up_collection(&mut write, movie_doc).await?;
+        self.commit_movie_write(write).await?;
+        return Ok(delete_result.deleted_count);
+    }
+
+    /// Reads movie document within the movie write.
+    /// Returns MovieNotFoundError if there is no such movie.
+    async fn find_movie_doc(&self, write: &mut MovieWrite, movie_id: u8) -> Result<Document> {
+        return self
+            .get_collection()
+            .find_one_with_session(doc! { MOVIEID: movie_id as i32 }, None, &mut write.session)
+            .await
+            .map_err(MongoQueryError)?
+            .ok_or_else(|| MovieNotFoundError(movie_id.to_string()));
+    }
+
+    /// Replaces collection of the movie with the total of its collection entries within the movie write,
+    /// recalculating verdict. Only collection and verdict are set, so that other fields changed meanwhile are kept.
+    /// Total in INR crores is converted to the currency and unit the movie is reported in, with the same exchange rate.
+    /// Collection is left unchanged once the last entry is deleted.
+    async fn roll_up_collection(&self, write: &mut MovieWrite, movie_doc: Document) -> Result<()> {
+        let movie = self.doc_to_movie(&movie_doc)?;
+        let options = FindOptions::builder().sort(doc! { DATE: 1 }).build();
+        let mut cursor = self
+            .get_named_collection(COLLECTION_ENTRIES_COLLECTION)
+            .find_with_session(
+                doc! { MOVIEID: movie.movie_id as i32 },
+                options,
+                &mut write.session,
+            )
+            .await
+            .map_err(MongoQueryError)?;
+        let mut collection_entries: Vec<CollectionEntry> = Vec::new();
+        while let Some(doc) = cursor.next(&mut write.session).await {
+            collection_entries.push(self.doc_to_collection_entry(&doc?)?);
+        }
+        if collection_entries.is_empty() {
+            return Ok(());
+        }
+
+        let total_collection_crores = collection_run::total_collection(&collection_entries)?;
+        let verdict = release::verdict(movie.status, movie.budget_crores, total_collection_crores)?;
+        let mut set_doc = doc! {
+            COLLECTION_CRORES: total_collection_crores.to_string(),
+            VERDICT: verdict,
+        };
+        if let Some(reported_amounts) = &movie.reported_amounts {
+            let reported_collection = currency::from_crores(
+                total_collection_crores,
+                &reported_amounts.currency,
+                reported_amounts.unit,
+                reported_amounts.exchange_rate,
+            )?;
+            set_doc.insert(REPORTED_COLLECTION, reported_collection.to_string());
+        }
+        let update_result = self
+            .get_collection()
+            .update_one_with_session(
+                doc! { ID: movie_doc.get_object_id(ID)? },
+                doc! { "$set": set_doc.clone() },
+                None,
+                &mut write.session,
+            )
+            .await
+            .map_err(MongoQueryError)?;
+        if update_result.modified_count == 1 {
+            let mut updated_doc = movie_doc;
+            updated_doc.extend(set_doc);
+            let updated_movie = self.doc_to_movie(&updated_doc)?;
+            self.queue_movie_event(
+                write,
+                MovieEvent::new(MovieEventType::Updated, Some(&movie), Some(updated_movie)),
+            )
+            .await?;
+        }
+        return Ok(());
+    }
+
+    /// Converts BSON Document to Collection Entry Structure.
+    pub fn doc_to_collection_entry(&self, doc: &Document) -> Result<CollectionEntry> {
+        let collection_entry = CollectionEntry {
+            id: doc.get_object_id(ID)?.to_hex(),
+            movie_id: doc.get_i32(MOVIEID)? as u8,
+            date: doc.get_str(DATE)?.to_owned(),
+            period: doc.get_str(PERIOD)?.parse()?,
+            territory: doc.get_str(TERRITORY)?.to_owned(),
+            collection_crores: Decimal::from_str(doc.get_str(COLLECTION_CRORES)?)
+                .map_err(|e| InvalidDocumentError(e.to_string()))?,
+            created_at: rfc3339(doc.get_datetime(CREATED_AT)?),
+        };
+        Ok(collection_entry)
+    }
+
+    /// Converts BSON Document to Production House Structure.
+    pub fn doc_to_production_house(&self, doc: &Document) -> Result<ProductionHouse> {
+        let aliases = doc
+            .get_array(ALIASES)?
+            .iter()
+            .filter_map(|alias| alias.as_str().map(str::to_owned))
+            .collect();
+        let production_house = ProductionHouse {
+            id: doc.get_object_id(ID)?.to_hex(),
+            name: doc.get_str(NAME)?.to_owned(),
+            aliases,
+            created_at: rfc3339(doc.get_datetime(CREATED_AT)?),
+        };
+        Ok(production_house)
+    }
+
+    /// Converts BSON Document to Person Structure.
+    pub fn doc_to_person(&self, doc: &Document) -> Result<Person> {
+        let person = Person {
+            id: doc.get_object_id(ID)?.to_hex(),
+            name: doc.get_str(NAME)?.to_owned(),
+            created_at: rfc3339(doc.get_datetime(CREATED_AT)?),
+        };
+        Ok(person)
+    }
+
+    /// Converts BSON Document to Franchise Structure.
+    pub fn doc_to_franchise(&self, doc: &Document) -> Result<Franchise> {
+        let movie_ids = doc
+            .get_array(MOVIE_IDS)?
+            .iter()
+            .filter_map(|movie_id| movie_id.as_i32())
+            .map(|movie_id| movie_id as u8)
+            .collect();
+        let franchise = Franchise {
+            id: doc.get_object_id(ID)?.to_hex(),
+            name: doc.get_str(NAME)?.to_owned(),
+            movie_ids,
+            created_at: rfc3339(doc.get_datetime(CREATED_AT)?),
+        };
+        Ok(franchise)
+    }
+
+    /// Converts BSON Document to Webhook Structure.
+    pub fn doc_to_webhook(&self, doc: &Document) -> Result<Webhook> {
+        let events = doc
+            .get_array(EVENTS)?
+            .iter()
+            .filter_map(|event| event.as_str().map(|event| event.to_owned()))
+            .collect();
+        let webhook = Webhook {
+            id: doc.get_object_id(ID)?.to_hex(),
+            url: doc.get_str(URL)?.to_owned(),
+            events,
+            active: doc.get_bool(ACTIVE)?,
+            created_at: rfc3339(doc.get_datetime(CREATED_AT)?),
+            secret: doc.get_str(SECRET)?.to_owned(),
+        };
+        Ok(webhook)
+    }
+
+    /// Converts BSON Document to Outbox Entry Structure.
+    pub fn doc_to_outbox_entry(&self, doc: &Document) -> Result<OutboxEntry> {
+        let outbox_entry = OutboxEntry {
+            id: doc.get_object_id(ID)?.to_hex(),
+            webhook_id: doc.get_str(WEBHOOK_ID)?.to_owned(),
+            event_type: doc.get_str(EVENT_TYPE)?.to_owned(),
+            payload: doc.get_str(PAYLOAD)?.to_owned(),
+            status: doc.get_str(STATUS)?.to_owned(),
+            attempts: doc.get_i32(ATTEMPTS)? as u32,
+            next_attempt_at: rfc3339(doc.get_datetime(NEXT_ATTEMPT_AT)?),
+            last_error: doc.get_str(LAST_ERROR)?.to_owned(),
+            created_at: rfc3339(doc.get_datetime(CREATED_AT)?),
+        };
+        Ok(outbox_entry)
+    }
+
+    /// Converts BSON Document to Webhook Delivery Structure.
+    pub fn doc_to_webhook_delivery(&self, doc: &Document) -> Result<WebhookDelivery> {
+        let webhook_delivery = WebhookDelivery {
+            id: doc.get_object_id(ID)?.to_hex(),
+            webhook_id: doc.get_str(WEBHOOK_ID)?.to_owned(),
+            outbox_id: doc.get_str(OUTBOX_ID)?.to_owned(),
+            event_type: doc.get_str(EVENT_TYPE)?.to_owned(),
+            attempt: doc.get_i32(ATTEMPT)? as u32,
+            success: doc.get_bool(SUCCESS)?,
+            status_code: doc
+                .get_i32(STATUS_CODE)
+                .ok()
+                .map(|status_code| status_code as u16),
+            error: doc.get_str(ERROR)?.to_owned(),
+            duration_ms: doc.get_i64(DURATION_MS)? as u64,
+            delivered_at: rfc3339(doc.get_datetime(DELIVERED_AT)?),
+        };
+        Ok(webhook_delivery)
+    }
+}
+
+/// Parses hexadecimal document id, 'invalid_id_error' is returned if it is malformed.
+fn parse_object_id(id: &str, invalid_id_error: fn(String) -> BoxOfficeError) -> Result<ObjectId> {
+    return ObjectId::parse_str(id).map_err(|_| invalid_id_error(id.to_string()));
+}
+
+/// Returns whether a write was rejected by a unique index.
+fn is_duplicate_key_error(error: &mongodb::error::Error) -> bool {
+    return match error.kind.as_ref() {
+        ErrorKind::Write(WriteFailure::WriteError(write_error)) => {
+            write_error.code == DUPLICATE_KEY_CODE
+        }
+        ErrorKind::Command(command_error) => command_error.code == DUPLICATE_KEY_CODE,
+        _ => false,
+    };
+}
+
+/// Returns whether a query needed an index which does not exist, e.g. the text index for '$text'.
+fn is_index_not_found_error(error: &mongodb::error::Error) -> bool {
+    return match error.kind.as_ref() {
+        ErrorKind::Command(command_error) => command_error.code == INDEX_NOT_FOUND_CODE,
+        _ => false,
+    };
+}
+
+fn date_time_after(date_time: DateTime, duration: Duration) -> DateTime {
+    return DateTime::from_millis(date_time.timestamp_millis() + duration.as_millis() as i64);
+}
+
+fn rfc3339(date_time: &DateTime) -> String {
+    return date_time.try_to_rfc3339_string().unwrap_or_default();
+}
+
+/// Returns document of territory breakdown entry. Amounts are stored as strings like amounts in crores.
+fn territory_collection_doc(territory: &TerritoryCollection) -> Document {
+    return doc! {
+        TERRITORY: territory.territory.clone(),
+        MARKET: territory.market.to_string(),
+        BUDGET_CRORES: territory.budget_crores.to_string(),
+        COLLECTION_CRORES: territory.collection_crores.to_string(),
+        VERDICT: territory.verdict.clone(),
+    };
+}
+
+/// Converts BSON Document to Territory Collection Structure.
+fn doc_to_territory_collection(doc: &Document) -> Result<TerritoryCollection> {
+    let territory_collection = TerritoryCollection {
+        territory: doc.get_str(TERRITORY)?.to_owned(),
+        market: doc.get_str(MARKET)?.parse()?,
+        budget_crores: Decimal::from_str(doc.get_str(BUDGET_CRORES)?)
+            .map_err(|e| InvalidDocumentError(e.to_string()))?,
+        collection_crores: Decimal::from_str(doc.get_str(COLLECTION_CRORES)?)
+            .map_err(|e| InvalidDocumentError(e.to_string()))?,
+        verdict: doc.get_str(VERDICT)?.to_owned(),
+    };
+    Ok(territory_collection)
+}
+
+/// Returns filter matching movies with collection in territory and market of the query
+/// and with its genre, language, certification and tag. Filters left out of the query match every movie.
+fn movie_query_filter(query: &MovieQuery, vocabularies: &Vocabularies) -> Document {
+    let mut filter = Document::new();
+    if query.territory.is_some() || query.market.is_some() {
+        let mut territory = Document::new();
+        if let Some(name) = &query.territory {
+            territory.insert(TERRITORY, name);
+        }
+        if let Some(market) = query.market {
+            territory.insert(MARKET, market.to_string());
+        }
+        filter.insert(TERRITORIES, doc! { "$elemMatch": territory });
+    }
+    let values = vocabularies.filter_values(
+        query.genre.as_deref(),
+        query.language.as_deref(),
+        query.certification.as_deref(),
+        query.tag.as_deref(),
+    );
+    for (field, value) in [GENRES, LANGUAGES, CERTIFICATION, TAGS]
+        .into_iter()
+        .zip(values)
+    {
+        if let Some(value) = value {
+            filter.insert(field, value);
+        }
+    }
+    return filter;
+}
+
+/// Returns document fields of movie metadata. Certification and runtime are null when not given.
+fn metadata_doc(metadata: &MovieMetadata) -> Document {
+    return doc! {
+        GENRES: metadata.genres.clone(),
+        LANGUAGES: metadata.languages.clone(),
+        CERTIFICATION: metadata.certification.clone(),
+        RUNTIME_MINUTES: metadata.runtime_minutes.map(i32::from),
+        TAGS: metadata.tags.clone(),
+    };
+}
+
+/// Reads movie metadata from movie document, movies stored before metadata have none.
+fn doc_to_metadata(doc: &Document) -> MovieMetadata {
+    let strings = |key: &str| -> Vec<String> {
+        doc.get_array(key)
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|value| value.as_str().map(str::to_owned))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    return MovieMetadata {
+        genres: strings(GENRES),
+        languages: strings(LANGUAGES),
+        certification: doc.get_str(CERTIFICATION).ok().map(str::to_owned),
+        runtime_minutes: doc
+            .get_i32(RUNTIME_MINUTES)
+            .ok()
+            .and_then(|runtime_minutes| u16::try_from(runtime_minutes).ok()),
+        tags: strings(TAGS),
+    };
+}
+
+/// Returns document of credit. Character is left out when not given.
+fn credit_doc(credit: &Credit) -> Document {
+    let mut doc = doc! {
+        PERSON_ID: credit.person_id.clone(),
+        NAME: credit.name.clone(),
+        ROLE: credit.role.to_string(),
+    };
+    if let Some(character) = &credit.character {
+        doc.insert(CHARACTER, character.clone());
+    }
+    return doc;
+}
+
+/// Converts BSON Document to Credit Structure.
+fn doc_to_credit(doc: &Document) -> Result<Credit> {
+    let credit = Credit {
+        person_id: doc.get_str(PERSON_ID)?.to_owned(),
+        name: doc.get_str(NAME)?.to_owned(),
+        role: doc.get_str(ROLE)?.parse()?,
+        character: doc.get_str(CHARACTER).ok().map(str::to_owned),
+    };
+    Ok(credit)
+}
+
+/// Returns document of relation to another movie.
+fn movie_relation_doc(relation: &MovieRelation) -> Document {
+    return doc! {
+        KIND: relation.kind.to_string(),
+        MOVIEID: relation.movie_id as i32,
+    };
+}
+
+/// Converts BSON Document to Movie Relation Structure.
+fn doc_to_movie_relation(doc: &Document) -> Result<MovieRelation> {
+    let relation = MovieRelation {
+        kind: doc.get_str(KIND)?.parse()?,
+        movie_id: doc.get_i32(MOVIEID)? as u8,
+    };
+    Ok(relation)
+}
+
+/// Movie Ids are stored as 32-bit integers, like Movie Id of movies.
+fn movie_ids_bson(movie_ids: &[u8]) -> Vec<i32> {
+    return movie_ids.iter().map(|movie_id| *movie_id as i32).collect();
+}
+
+/// Returns document fields of amounts as reported. Amounts are stored as strings like amounts in crores.
+fn reported_amounts_doc(reported_amounts: &MovieAmounts) -> Document {
+    return doc! {
+        CURRENCY: reported_amounts.currency.clone(),
+        UNIT: reported_amounts.unit.to_string(),
+        REPORTED_BUDGET: reported_amounts.budget.to_string(),
+        REPORTED_COLLECTION: reported_amounts.collection.to_string(),
+        EXCHANGE_RATE: reported_amounts.exchange_rate.to_string(),
+        EXCHANGE_RATE_DATE: reported_amounts.exchange_rate_date.clone(),
+    };
 }
@@ -1,4 +1,5 @@
 /// Handles errors in the application.
+use crate::constants::*;
 use mongodb::bson;
 use ron::ser::to_string;
 use serde::{Deserialize, Serialize};
@@ -42,12 +43,120 @@ pub enum BoxOfficeError {
     InvalidDocumentError(String),
     #[error("Document with Movie Id : '{0}' already exist.")]
     InvalidCreateDocumentError(String),
+    #[error("TLS configuration error: '{0}'")]
+    TlsConfigurationError(String),
+    #[error("Unable to start server: '{0}'")]
+    ServerError(String),
+    #[error("Unable to initialise tracing: '{0}'")]
+    TracingError(String),
+    #[error("Unable to initialise logging: '{0}'")]
+    LoggingError(String),
+    #[error("Unable to load configuration: '{0}'")]
+    ConfigurationError(String),
+    #[error("Bad create webhook request : '{0}'")]
+    BadCreateWebhookRequestError(String),
+    #[error("Invalid webhook id used: '{0}'")]
+    InvalidWebhookIDError(String),
+    #[error("Invalid dead letter id used: '{0}'")]
+    InvalidDeadLetterIDError(String),
+    #[error("Invalid outbox id used: '{0}'")]
+    InvalidOutboxIDError(String),
+    #[error("Webhook with id : '{0}' does not exist.")]
+    WebhookNotFoundError(String),
+    #[error("Dead letter with id : '{0}' does not exist.")]
+    DeadLetterNotFoundError(String),
+    #[error("Movie with Movie Id : '{0}' does not exist.")]
+    MovieNotFoundError(String),
+    #[error("Movie with Movie Id : '{0}' already exists.")]
+    MovieAlreadyExistsError(String),
+    #[error("Invalid include value : '{0}', expected one or more of profit, profit_percentage, roi_multiple, break_even_gap, financials or inflation_adjusted")]
+    InvalidIncludeError(String),
+    #[error("Invalid currency : '{0}', expected ISO 4217 code, e.g. INR or USD")]
+    InvalidCurrencyError(String),
+    #[error("Bad exchange rates request : '{0}'")]
+    BadExchangeRatesRequestError(String),
+    #[error("Exchange rate not found : '{0}'")]
+    ExchangeRateNotFoundError(String),
+    #[error("Unable to convert amount : '{0}'")]
+    AmountConversionError(String),
+    #[error("Unable to read or write exchange rates : '{0}'")]
+    ExchangeRatesFileError(String),
+    #[error("Bad CPI request : '{0}'")]
+    BadCpiRequestError(String),
+    #[error("Consumer price index not found : '{0}'")]
+    CpiNotFoundError(String),
+    #[error("Unable to adjust amount for inflation : '{0}'")]
+    InflationAdjustmentError(String),
+    #[error("Unable to read or write CPI : '{0}'")]
+    CpiFileError(String),
+    #[error("Bad collection entry request : '{0}'")]
+    BadCollectionEntryRequestError(String),
+    #[error("Invalid collection entry id : '{0}'")]
+    InvalidCollectionEntryIDError(String),
+    #[error("Collection entry with id : '{0}' does not exist.")]
+    CollectionEntryNotFoundError(String),
+    #[error("Collection entry overlaps existing entry : '{0}'")]
+    CollectionEntryConflictError(String),
+    #[error("Bad territories request : '{0}'")]
+    BadTerritoriesRequestError(String),
+    #[error("Territory breakdown does not add up to the movie : '{0}'")]
+    TerritoryBreakdownMismatchError(String),
+    #[error("Invalid release status transition : '{0}'")]
+    InvalidStatusTransitionError(String),
+    #[error("Release lifecycle does not allow it : '{0}'")]
+    ReleaseLifecycleError(String),
+    #[error("Bad production house request : '{0}'")]
+    BadProductionHouseRequestError(String),
+    #[error("Invalid production house id : '{0}'")]
+    InvalidProductionHouseIDError(String),
+    #[error("Production house with id : '{0}' does not exist.")]
+    ProductionHouseNotFoundError(String),
+    #[error("Production house conflict : '{0}'")]
+    ProductionHouseConflictError(String),
+    #[error("Bad person request : '{0}'")]
+    BadPersonRequestError(String),
+    #[error("Invalid person id : '{0}'")]
+    InvalidPersonIDError(String),
+    #[error("Person with id : '{0}' does not exist.")]
+    PersonNotFoundError(String),
+    #[error("Person conflict : '{0}'")]
+    PersonConflictError(String),
+    #[error("Bad credits request : '{0}'")]
+    BadCreditsRequestError(String),
+    #[error("Credit refers to unknown person : '{0}'")]
+    UnknownCreditedPersonError(String),
+    #[error("Movie metadata is not in the vocabulary : '{0}'")]
+    InvalidMetadataError(String),
+    #[error("Bad franchise request : '{0}'")]
+    BadFranchiseRequestError(String),
+    #[error("Invalid franchise id : '{0}'")]
+    InvalidFranchiseIDError(String),
+    #[error("Franchise with id : '{0}' does not exist.")]
+    FranchiseNotFoundError(String),
+    #[error("Franchise conflict : '{0}'")]
+    FranchiseConflictError(String),
+    #[error("Instalment refers to unknown movie : '{0}'")]
+    UnknownInstalmentError(String),
+    #[error("Bad relations request : '{0}'")]
+    BadRelationsRequestError(String),
+    #[error("Invalid movie relation : '{0}'")]
+    InvalidMovieRelationError(String),
+    #[error("None of the accepted media types is supported : '{0}'")]
+    NotAcceptableError(String),
+    #[error("Unsupported request body media type : '{0}'")]
+    UnsupportedMediaTypeError(String),
+    #[error("Unable to serialize response : '{0}'")]
+    ResponseSerializationError(String),
 }
 
+/// Error response body.
+/// 'error_code' identifies the error so that clients need not parse 'error_message'.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ErrorResponse {
     pub success: bool,
     pub error_message: String,
+    #[serde(default)]
+    pub error_code: String,
 }
 
 #[derive(Serialize, Debug)]
@@ -58,6 +167,98 @@ struct RequestFieldError {
 
 impl warp::reject::Reject for BoxOfficeError {}
 
+impl BoxOfficeError {
+    /// Returns error code sent in error response.
+    /// Errors which are reported as internal server errors share one code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            BoxOfficeError::ValidationError(_) => VALIDATION_ERROR_CODE,
+            BoxOfficeError::BadCreateMovieRequestError(_) => BAD_CREATE_MOVIE_REQUEST_ERROR_CODE,
+            BoxOfficeError::BadDeleteMovieRequestError(_) => BAD_DELETE_MOVIE_REQUEST_ERROR_CODE,
+            BoxOfficeError::InvalidDeleteMovieRequestError(_) => {
+                INVALID_DELETE_MOVIE_REQUEST_ERROR_CODE
+            }
+            BoxOfficeError::InvalidUpdateMovieRequestError(_) => {
+                INVALID_UPDATE_MOVIE_REQUEST_ERROR_CODE
+            }
+            BoxOfficeError::InvalidFieldInCreateMovieRequestError(_) => {
+                INVALID_FIELD_IN_CREATE_MOVIE_REQUEST_ERROR_CODE
+            }
+            BoxOfficeError::BadUpdateMovieRequestError(_) => BAD_UPDATE_MOVIE_REQUEST_ERROR_CODE,
+            BoxOfficeError::ErrorinVerdictCalculation(_) => ERROR_IN_VERDICT_CALCULATION_CODE,
+            BoxOfficeError::MongoDBError(_) => MONGODB_ERROR_CODE,
+            BoxOfficeError::InvalidMovieIDError(_) => INVALID_MOVIE_ID_ERROR_CODE,
+            BoxOfficeError::InvalidDocumentError(_) => INVALID_DOCUMENT_ERROR_CODE,
+            BoxOfficeError::InvalidCreateDocumentError(_) => INVALID_CREATE_DOCUMENT_ERROR_CODE,
+            BoxOfficeError::BadCreateWebhookRequestError(_) => {
+                BAD_CREATE_WEBHOOK_REQUEST_ERROR_CODE
+            }
+            BoxOfficeError::InvalidWebhookIDError(_) => INVALID_WEBHOOK_ID_ERROR_CODE,
+            BoxOfficeError::InvalidDeadLetterIDError(_) => INVALID_DEAD_LETTER_ID_ERROR_CODE,
+            BoxOfficeError::InvalidOutboxIDError(_) => INVALID_OUTBOX_ID_ERROR_CODE,
+            BoxOfficeError::WebhookNotFoundError(_) => WEBHOOK_NOT_FOUND_ERROR_CODE,
+            BoxOfficeError::DeadLetterNotFoundError(_) => DEAD_LETTER_NOT_FOUND_ERROR_CODE,
+            BoxOfficeError::MovieNotFoundError(_) => MOVIE_NOT_FOUND_ERROR_CODE,
+            BoxOfficeError::MovieAlreadyExistsError(_) => MOVIE_ALREADY_EXISTS_ERROR_CODE,
+            BoxOfficeError::InvalidIncludeError(_) => INVALID_INCLUDE_ERROR_CODE,
+            BoxOfficeError::InvalidCurrencyError(_) => INVALID_CURRENCY_ERROR_CODE,
+            BoxOfficeError::BadExchangeRatesRequestError(_) => {
+                BAD_EXCHANGE_RATES_REQUEST_ERROR_CODE
+            }
+            BoxOfficeError::ExchangeRateNotFoundError(_) => EXCHANGE_RATE_NOT_FOUND_ERROR_CODE,
+            BoxOfficeError::AmountConversionError(_) => AMOUNT_CONVERSION_ERROR_CODE,
+            BoxOfficeError::ExchangeRatesFileError(_) => EXCHANGE_RATES_FILE_ERROR_CODE,
+            BoxOfficeError::BadCpiRequestError(_) => BAD_CPI_REQUEST_ERROR_CODE,
+            BoxOfficeError::CpiNotFoundError(_) => CPI_NOT_FOUND_ERROR_CODE,
+            BoxOfficeError::InflationAdjustmentError(_) => INFLATION_ADJUSTMENT_ERROR_CODE,
+            BoxOfficeError::CpiFileError(_) => CPI_FILE_ERROR_CODE,
+            BoxOfficeError::BadCollectionEntryRequestError(_) => {
+                BAD_COLLECTION_ENTRY_REQUEST_ERROR_CODE
+            }
+            BoxOfficeError::InvalidCollectionEntryIDError(_) => {
+                INVALID_COLLECTION_ENTRY_ID_ERROR_CODE
+            }
+            BoxOfficeError::CollectionEntryNotFoundError(_) => {
+                COLLECTION_ENTRY_NOT_FOUND_ERROR_CODE
+            }
+            BoxOfficeError::CollectionEntryConflictError(_) => COLLECTION_ENTRY_CONFLICT_ERROR_CODE,
+            BoxOfficeError::BadTerritoriesRequestError(_) => BAD_TERRITORIES_REQUEST_ERROR_CODE,
+            BoxOfficeError::TerritoryBreakdownMismatchError(_) => {
+                TERRITORY_BREAKDOWN_MISMATCH_ERROR_CODE
+            }
+            BoxOfficeError::InvalidStatusTransitionError(_) => INVALID_STATUS_TRANSITION_ERROR_CODE,
+            BoxOfficeError::ReleaseLifecycleError(_) => RELEASE_LIFECYCLE_ERROR_CODE,
+            BoxOfficeError::BadProductionHouseRequestError(_) => {
+                BAD_PRODUCTION_HOUSE_REQUEST_ERROR_CODE
+            }
+            BoxOfficeError::InvalidProductionHouseIDError(_) => {
+                INVALID_PRODUCTION_HOUSE_ID_ERROR_CODE
+            }
+            BoxOfficeError::ProductionHouseNotFoundError(_) => {
+                PRODUCTION_HOUSE_NOT_FOUND_ERROR_CODE
+            }
+            BoxOfficeError::ProductionHouseConflictError(_) => PRODUCTION_HOUSE_CONFLICT_ERROR_CODE,
+            BoxOfficeError::BadPersonRequestError(_) => BAD_PERSON_REQUEST_ERROR_CODE,
+            BoxOfficeError::InvalidPersonIDError(_) => INVALID_PERSON_ID_ERROR_CODE,
+            BoxOfficeError::PersonNotFoundError(_) => PERSON_NOT_FOUND_ERROR_CODE,
+            BoxOfficeError::PersonConflictError(_) => PERSON_CONFLICT_ERROR_CODE,
+            BoxOfficeError::BadCreditsRequestError(_) => BAD_CREDITS_REQUEST_ERROR_CODE,
+            BoxOfficeError::UnknownCreditedPersonError(_) => UNKNOWN_CREDITED_PERSON_ERROR_CODE,
+            BoxOfficeError::InvalidMetadataError(_) => INVALID_METADATA_ERROR_CODE,
+            BoxOfficeError::BadFranchiseRequestError(_) => BAD_FRANCHISE_REQUEST_ERROR_CODE,
+            BoxOfficeError::InvalidFranchiseIDError(_) => INVALID_FRANCHISE_ID_ERROR_CODE,
+            BoxOfficeError::FranchiseNotFoundError(_) => FRANCHISE_NOT_FOUND_ERROR_CODE,
+            BoxOfficeError::FranchiseConflictError(_) => FRANCHISE_CONFLICT_ERROR_CODE,
+            BoxOfficeError::UnknownInstalmentError(_) => UNKNOWN_INSTALMENT_ERROR_CODE,
+            BoxOfficeError::BadRelationsRequestError(_) => BAD_RELATIONS_REQUEST_ERROR_CODE,
+            BoxOfficeError::InvalidMovieRelationError(_) => INVALID_MOVIE_RELATION_ERROR_CODE,
+            BoxOfficeError::NotAcceptableError(_) => NOT_ACCEPTABLE_ERROR_CODE,
+            BoxOfficeError::UnsupportedMediaTypeError(_) => UNSUPPORTED_MEDIA_TYPE_ERROR_CODE,
+            _ => INTERNAL_SERVER_ERROR_CODE,
+        }
+    }
+}
+
 /// Function to handle error.
 pub async fn handle_rejection(err: Rejection) -> std::result::Result<Box<dyn Reply>, Infallible> {
     let code;
@@ -69,11 +270,19 @@ pub async fn handle_rejection(err: Rejection) -> std::result::Result<Box<dyn Rep
         error_message = "Not Found".to_string();
         success = false;
         log::error!("{}", error_message);
-    } else if let Some(_) = err.find::<warp::filters::body::BodyDeserializeError>() {
+    } else if err
+        .find::<warp::filters::body::BodyDeserializeError>()
+        .is_some()
+    {
         code = StatusCode::BAD_REQUEST;
         error_message = "Invalid Request Body".to_string();
         success = false;
         log::error!("{}", error_message);
+    } else if let Some(e) = err.find::<async_graphql_warp::GraphQLBadRequest>() {
+        code = StatusCode::BAD_REQUEST;
+        error_message = e.to_string();
+        success = false;
+        log::error!("{}", error_message);
     } else if let Some(e) = err.find::<BoxOfficeError>() {
         match e {
             BoxOfficeError::BadCreateMovieRequestError(_) => {
@@ -136,6 +345,106 @@ pub async fn handle_rejection(err: Rejection) -> std::result::Result<Box<dyn Rep
                 success = false;
                 log::error!("{}", error_message);
             }
+            BoxOfficeError::BadCreateWebhookRequestError(_)
+            | BoxOfficeError::BadExchangeRatesRequestError(_)
+            | BoxOfficeError::BadCpiRequestError(_)
+            | BoxOfficeError::BadCollectionEntryRequestError(_)
+            | BoxOfficeError::BadTerritoriesRequestError(_)
+            | BoxOfficeError::BadProductionHouseRequestError(_)
+            | BoxOfficeError::BadPersonRequestError(_)
+            | BoxOfficeError::BadCreditsRequestError(_)
+            | BoxOfficeError::BadFranchiseRequestError(_)
+            | BoxOfficeError::BadRelationsRequestError(_) => {
+                code = StatusCode::BAD_REQUEST;
+                error_message = e.to_string();
+                success = false;
+                log::error!("{}", error_message);
+            }
+            BoxOfficeError::InvalidWebhookIDError(_)
+            | BoxOfficeError::InvalidDeadLetterIDError(_)
+            | BoxOfficeError::InvalidOutboxIDError(_)
+            | BoxOfficeError::InvalidCollectionEntryIDError(_)
+            | BoxOfficeError::InvalidProductionHouseIDError(_)
+            | BoxOfficeError::InvalidPersonIDError(_)
+            | BoxOfficeError::InvalidFranchiseIDError(_) => {
+                code = StatusCode::BAD_REQUEST;
+                error_message = e.to_string();
+                success = false;
+                log::error!("{}", error_message);
+            }
+            BoxOfficeError::WebhookNotFoundError(_)
+            | BoxOfficeError::DeadLetterNotFoundError(_)
+            | BoxOfficeError::CollectionEntryNotFoundError(_)
+            | BoxOfficeError::ProductionHouseNotFoundError(_)
+            | BoxOfficeError::PersonNotFoundError(_)
+            | BoxOfficeError::FranchiseNotFoundError(_) => {
+                code = StatusCode::NOT_FOUND;
+                error_message = e.to_string();
+                success = false;
+                log::error!("{}", error_message);
+            }
+            BoxOfficeError::MovieNotFoundError(_) => {
+                code = StatusCode::NOT_FOUND;
+                error_message = e.to_string();
+                success = false;
+                log::error!("{}", error_message);
+            }
+            BoxOfficeError::MovieAlreadyExistsError(_)
+            | BoxOfficeError::CollectionEntryConflictError(_)
+            | BoxOfficeError::InvalidStatusTransitionError(_)
+            | BoxOfficeError::ProductionHouseConflictError(_)
+            | BoxOfficeError::PersonConflictError(_)
+            | BoxOfficeError::FranchiseConflictError(_) => {
+                code = StatusCode::CONFLICT;
+                error_message = e.to_string();
+                success = false;
+                log::error!("{}", error_message);
+            }
+            BoxOfficeError::InvalidIncludeError(_) | BoxOfficeError::InvalidCurrencyError(_) => {
+                code = StatusCode::BAD_REQUEST;
+                error_message = e.to_string();
+                success = false;
+                log::error!("{}", error_message);
+            }
+            BoxOfficeError::ExchangeRateNotFoundError(_)
+            | BoxOfficeError::AmountConversionError(_)
+            | BoxOfficeError::CpiNotFoundError(_)
+            | BoxOfficeError::InflationAdjustmentError(_)
+            | BoxOfficeError::TerritoryBreakdownMismatchError(_)
+            | BoxOfficeError::ReleaseLifecycleError(_)
+            | BoxOfficeError::UnknownCreditedPersonError(_)
+            | BoxOfficeError::InvalidMetadataError(_)
+            | BoxOfficeError::UnknownInstalmentError(_)
+            | BoxOfficeError::InvalidMovieRelationError(_) => {
+                code = StatusCode::UNPROCESSABLE_ENTITY;
+                error_message = e.to_string();
+                success = false;
+                log::error!("{}", error_message);
+            }
+            BoxOfficeError::ExchangeRatesFileError(_) | BoxOfficeError::CpiFileError(_) => {
+                code = StatusCode::INTERNAL_SERVER_ERROR;
+                error_message = e.to_string();
+                success = false;
+                log::error!("{}", error_message);
+            }
+            BoxOfficeError::NotAcceptableError(_) => {
+                code = StatusCode::NOT_ACCEPTABLE;
+                error_message = e.to_string();
+                success = false;
+                log::error!("{}", error_message);
+            }
+            BoxOfficeError::UnsupportedMediaTypeError(_) => {
+                code = StatusCode::UNSUPPORTED_MEDIA_TYPE;
+                error_message = e.to_string();
+                success = false;
+                log::error!("{}", error_message);
+            }
+            BoxOfficeError::ResponseSerializationError(_) => {
+                code = StatusCode::INTERNAL_SERVER_ERROR;
+                error_message = e.to_string();
+                success = false;
+                log::error!("{}", error_message);
+            }
             BoxOfficeError::MongoDBError(_) => {
                 code = StatusCode::INTERNAL_SERVER_ERROR;
                 error_message = e.to_string();
@@ -201,7 +510,12 @@ pub async fn handle_rejection(err: Rejection) -> std::result::Result<Box<dyn Rep
                 log::error!("{}", error_message);
             }
         }
-    } else if let Some(_) = err.find::<warp::reject::MethodNotAllowed>() {
+    } else if let Some(e) = err.find::<warp::reject::InvalidQuery>() {
+        code = StatusCode::BAD_REQUEST;
+        error_message = e.to_string();
+        success = false;
+        log::error!("{}", error_message);
+    } else if err.find::<warp::reject::MethodNotAllowed>().is_some() {
         code = StatusCode::METHOD_NOT_ALLOWED;
         error_message = "Method Not Allowed".to_string();
         success = false;
@@ -215,9 +529,22 @@ pub async fn handle_rejection(err: Rejection) -> std::result::Result<Box<dyn Rep
         log::error!("{}", error_message);
     }
     let read_success_value = success; //This is just to remove warning 'value assigned to success is never read.'
+    let error_code = match err.find::<BoxOfficeError>() {
+        Some(e) => e.code(),
+        None => match code {
+            StatusCode::NOT_FOUND => NOT_FOUND_ERROR_CODE,
+            StatusCode::BAD_REQUEST if err.find::<warp::reject::InvalidQuery>().is_some() => {
+                INVALID_QUERY_ERROR_CODE
+            }
+            StatusCode::BAD_REQUEST => INVALID_REQUEST_BODY_ERROR_CODE,
+            StatusCode::METHOD_NOT_ALLOWED => METHOD_NOT_ALLOWED_ERROR_CODE,
+            _ => INTERNAL_SERVER_ERROR_CODE,
+        },
+    };
     let json = reply::json(&ErrorResponse {
-        error_message: error_message.into(),
-        success: read_success_value.into(),
+        error_message,
+        success: read_success_value,
+        error_code: error_code.to_string(),
     });
 
     Ok(Box::new(reply::with_status(json, code)))
@@ -0,0 +1,250 @@
+/// Real-time movie change notifications.
+/// Write operations in db_layer (or MONGODB change streams) publish events,
+/// Server-Sent Events and WebSocket clients receive the events matching their subscription.
+use crate::{constants::*, db_layer::DB, model::Movie};
+use futures::{SinkExt, Stream, StreamExt};
+use mongodb::bson::{DateTime, Document};
+use mongodb::change_stream::event::{ChangeStreamEvent, OperationType};
+use mongodb::options::{ChangeStreamOptions, FullDocumentBeforeChangeType, FullDocumentType};
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use tokio::sync::broadcast;
+use warp::sse;
+use warp::ws::{Message, WebSocket};
+
+/// Kind of change made to a movie.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MovieEventType {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl MovieEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MovieEventType::Created => MOVIE_CREATED_EVENT,
+            MovieEventType::Updated => MOVIE_UPDATED_EVENT,
+            MovieEventType::Deleted => MOVIE_DELETED_EVENT,
+        }
+    }
+}
+
+/// Event sent to subscribers when a movie is created, updated or deleted.
+/// 'old_verdict' is absent for created movies, 'new_verdict' and 'movie' are absent for deleted movies.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MovieEvent {
+    pub event_type: MovieEventType,
+    pub movie_id: u8,
+    pub production_house: String,
+    pub old_verdict: Option<String>,
+    pub new_verdict: Option<String>,
+    pub movie: Option<Movie>,
+    pub timestamp: String,
+}
+
+impl MovieEvent {
+    /// Creates event from movie before and after the change.
+    pub fn new(
+        event_type: MovieEventType,
+        old_movie: Option<&Movie>,
+        new_movie: Option<Movie>,
+    ) -> Self {
+        let current_movie = new_movie.as_ref().or(old_movie);
+        Self {
+            event_type,
+            movie_id: current_movie
+                .map(|movie| movie.movie_id)
+                .unwrap_or_default(),
+            production_house: current_movie
+                .map(|movie| movie.production_house.clone())
+                .unwrap_or_default(),
+            old_verdict: old_movie.map(|movie| movie.verdict.clone()),
+            new_verdict: new_movie.as_ref().map(|movie| movie.verdict.clone()),
+            movie: new_movie,
+            timestamp: DateTime::now().try_to_rfc3339_string().unwrap_or_default(),
+        }
+    }
+}
+
+/// Subscription filter.
+/// Event is delivered if it matches every given field. Verdict matches old or new verdict.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EventFilter {
+    pub movie_id: Option<u8>,
+    pub production_house: Option<String>,
+    pub verdict: Option<String>,
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &MovieEvent) -> bool {
+        if let Some(movie_id) = self.movie_id {
+            if movie_id != event.movie_id {
+                return false;
+            }
+        }
+        if let Some(production_house) = &self.production_house {
+            if !production_house.eq_ignore_ascii_case(&event.production_house) {
+                return false;
+            }
+        }
+        if let Some(verdict) = &self.verdict {
+            let matches_verdict = |event_verdict: &Option<String>| {
+                event_verdict
+                    .as_ref()
+                    .map(|event_verdict| event_verdict.eq_ignore_ascii_case(verdict))
+                    .unwrap_or(false)
+            };
+            if !matches_verdict(&event.old_verdict) && !matches_verdict(&event.new_verdict) {
+                return false;
+            }
+        }
+        return true;
+    }
+}
+
+/// Broadcasts movie events to all subscribers.
+#[derive(Clone, Debug)]
+pub struct EventBus {
+    sender: broadcast::Sender<MovieEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+}
+
+impl EventBus {
+    /// Sends event to current subscribers. Event is dropped if nobody is subscribed.
+    pub fn publish(&self, event: MovieEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    /// Returns receiver of all events sent from now on.
+    pub fn receiver(&self) -> broadcast::Receiver<MovieEvent> {
+        return self.sender.subscribe();
+    }
+
+    /// Returns stream of events matching the filter.
+    /// Subscribers which fall behind skip missed events instead of disconnecting.
+    pub fn subscribe(&self, filter: EventFilter) -> impl Stream<Item = MovieEvent> {
+        let receiver = self.sender.subscribe();
+        futures::stream::unfold((receiver, filter), |(mut receiver, filter)| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) if filter.matches(&event) => {
+                        return Some((event, (receiver, filter)))
+                    }
+                    Ok(_) => continue,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        log::warn!("Event subscriber lagged, {} events skipped.", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+}
+
+/// Converts movie events to Server-Sent Events named after the event type.
+pub fn sse_stream(
+    events: impl Stream<Item = MovieEvent>,
+) -> impl Stream<Item = std::result::Result<sse::Event, Infallible>> {
+    events.map(|event| {
+        let sse_event = sse::Event::default()
+            .event(event.event_type.as_str())
+            .json_data(&event)
+            .unwrap_or_default();
+        Ok(sse_event)
+    })
+}
+
+/// Sends matching events to WebSocket client as JSON text messages.
+/// Client can change its subscription at any time by sending an 'EventFilter' as JSON text message.
+pub async fn websocket_session(websocket: WebSocket, event_bus: EventBus, filter: EventFilter) {
+    let (mut websocket_sender, mut websocket_receiver) = websocket.split();
+    let mut events = Box::pin(event_bus.subscribe(filter));
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                let Some(event) = event else { break };
+                let text = serde_json::to_string(&event).unwrap_or_default();
+                if websocket_sender.send(Message::text(text)).await.is_err() {
+                    break;
+                }
+            }
+            message = websocket_receiver.next() => {
+                match message {
+                    Some(Ok(message)) if message.is_text() => {
+                        match serde_json::from_str::<EventFilter>(message.to_str().unwrap_or_default()) {
+                            Ok(filter) => events = Box::pin(event_bus.subscribe(filter)),
+                            Err(e) => log::error!("Invalid event subscription: {}", e),
+                        }
+                    }
+                    Some(Ok(message)) if message.is_close() => break,
+                    Some(Ok(_)) => continue,
+                    Some(Err(_)) | None => break,
+                }
+            }
+        }
+    }
+}
+
+/// Publishes events from MONGODB change stream of Movies collection.
+/// Needs MONGODB replica set. Old verdict, and therefore delete events,
+/// are only available when pre-images are enabled on the collection.
+/// Stream is reopened after errors.
+pub fn watch_change_stream(db: DB) {
+    tokio::spawn(async move {
+        loop {
+            let options = ChangeStreamOptions::builder()
+                .full_document(Some(FullDocumentType::UpdateLookup))
+                .full_document_before_change(Some(FullDocumentBeforeChangeType::WhenAvailable))
+                .build();
+            match db.get_collection().watch(None, options).await {
+                Ok(mut change_stream) => {
+                    log::info!("Watching change stream of movies collection.");
+                    while let Some(change) = change_stream.next().await {
+                        match change {
+                            Ok(change) => {
+                                if let Some(event) = change_to_event(&db, change) {
+                                    db.events.publish(event);
+                                }
+                            }
+                            Err(e) => {
+                                log::error!("Change stream error: {}", e);
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(e) => log::error!("Unable to open change stream: {}", e),
+            }
+            tokio::time::sleep(CHANGE_STREAM_RETRY_DELAY).await;
+        }
+    });
+}
+
+/// Converts change stream event to movie event.
+fn change_to_event(db: &DB, change: ChangeStreamEvent<Document>) -> Option<MovieEvent> {
+    let event_type = match change.operation_type {
+        OperationType::Insert => MovieEventType::Created,
+        OperationType::Update | OperationType::Replace => MovieEventType::Updated,
+        OperationType::Delete => MovieEventType::Deleted,
+        _ => return None,
+    };
+    let old_movie = change
+        .full_document_before_change
+        .and_then(|document| db.doc_to_movie(&document).ok());
+    let new_movie = change
+        .full_document
+        .and_then(|document| db.doc_to_movie(&document).ok());
+    if old_movie.is_none() && new_movie.is_none() {
+        return None;
+    }
+    return Some(MovieEvent::new(event_type, old_movie.as_ref(), new_movie));
+}
@@ -0,0 +1,97 @@
+/// Lookup tables kept in memory and saved as a JSON list to the file they were loaded from,
+/// i.e. the exchange rate and consumer price index tables.
+use crate::error::BoxOfficeError;
+use crate::Result;
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+/// Entry of a file-backed table. An entry replaces the entry with the same key.
+pub trait TableEntry: Serialize + DeserializeOwned + Clone {
+    type Key: Ord + Clone + Debug;
+
+    fn key(&self) -> Self::Key;
+}
+
+/// Table of entries ordered by key. Clones share the same table.
+/// Changes are saved to the file before they are applied, so a table which can not be saved stays unchanged.
+#[derive(Debug, Clone)]
+pub struct FileTable<T: TableEntry> {
+    entries: Arc<RwLock<BTreeMap<T::Key, T>>>,
+    file_path: Option<PathBuf>,
+    /// Error reporting a file which can not be read or written.
+    file_error: fn(String) -> BoxOfficeError,
+}
+
+impl<T: TableEntry + Debug> FileTable<T> {
+    /// Returns empty table saved to the file, kept only in memory when no file is given.
+    pub fn new(file_path: Option<PathBuf>, file_error: fn(String) -> BoxOfficeError) -> Self {
+        Self {
+            entries: Arc::default(),
+            file_path,
+            file_error,
+        }
+    }
+
+    /// Loads table from the file. Table is empty if the file does not exist yet.
+    pub fn load(
+        file_path: Option<PathBuf>,
+        file_error: fn(String) -> BoxOfficeError,
+    ) -> Result<Self> {
+        let table = Self::new(file_path, file_error);
+        let file_path = match &table.file_path {
+            Some(file_path) if file_path.exists() => file_path,
+            _ => return Ok(table),
+        };
+        let contents = std::fs::read_to_string(file_path)
+            .map_err(|e| file_error(format!("{}: {}", file_path.display(), e)))?;
+        let entries: Vec<T> = serde_json::from_str(&contents)
+            .map_err(|e| file_error(format!("{}: {}", file_path.display(), e)))?;
+        insert(
+            &mut table.entries.write().unwrap_or_else(|e| e.into_inner()),
+            &entries,
+        );
+        return Ok(table);
+    }
+
+    /// Returns all entries ordered by key.
+    pub fn read(&self) -> Vec<T> {
+        return self.with_entries(|entries| entries.values().cloned().collect());
+    }
+
+    /// Adds entries, replacing entries of the same key, once the changed table is saved to its file.
+    pub fn upsert(&self, entries: &[T]) -> Result<()> {
+        let mut table = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        let mut changed_table = table.clone();
+        insert(&mut changed_table, entries);
+        self.save(&changed_table)?;
+        *table = changed_table;
+        return Ok(());
+    }
+
+    /// Looks entries up while the table is locked for reading.
+    pub fn with_entries<R>(&self, lookup: impl FnOnce(&BTreeMap<T::Key, T>) -> R) -> R {
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+        return lookup(&entries);
+    }
+
+    fn save(&self, entries: &BTreeMap<T::Key, T>) -> Result<()> {
+        let file_path = match &self.file_path {
+            Some(file_path) => file_path,
+            None => return Ok(()),
+        };
+        let contents = serde_json::to_string_pretty(&entries.values().collect::<Vec<&T>>())
+            .map_err(|e| (self.file_error)(e.to_string()))?;
+        std::fs::write(file_path, contents)
+            .map_err(|e| (self.file_error)(format!("{}: {}", file_path.display(), e)))?;
+        return Ok(());
+    }
+}
+
+fn insert<T: TableEntry>(table: &mut BTreeMap<T::Key, T>, entries: &[T]) {
+    for entry in entries {
+        table.insert(entry.key(), entry.clone());
+    }
+}
@@ -0,0 +1,160 @@
+/// Franchises and related titles.
+/// A franchise groups movies of a series as instalments, a movie being an instalment of one franchise at most.
+/// Movies are related to earlier movies as sequels, remakes or dubbed versions of them.
+/// Franchise summary combines budget and collection of the released instalments into one verdict
+/// and shows how collection moves from one instalment to the next.
+use crate::business_layer::{calculate_percentage, calculate_verdict, PercentageRounding};
+use crate::constants::*;
+use crate::error::BoxOfficeError;
+use crate::model::{
+    Franchise, FranchiseInstalment, FranchiseSummary, Movie, MovieRelations, RelatedMovie,
+    RelationKind,
+};
+use crate::release;
+use crate::Result;
+use rust_decimal::Decimal;
+use std::fmt;
+use std::str::FromStr;
+
+impl fmt::Display for RelationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            RelationKind::SequelOf => "sequel-of",
+            RelationKind::RemakeOf => "remake-of",
+            RelationKind::DubbedVersionOf => "dubbed-version-of",
+        };
+        return write!(f, "{}", kind);
+    }
+}
+
+impl FromStr for RelationKind {
+    type Err = BoxOfficeError;
+
+    fn from_str(kind: &str) -> Result<Self> {
+        match kind {
+            "sequel-of" => Ok(RelationKind::SequelOf),
+            "remake-of" => Ok(RelationKind::RemakeOf),
+            "dubbed-version-of" => Ok(RelationKind::DubbedVersionOf),
+            _ => Err(BoxOfficeError::InvalidDocumentError(format!(
+                "unknown relation kind '{}'",
+                kind
+            ))),
+        }
+    }
+}
+
+/// Returns relations of movie in both directions. Relations to movies which no longer exist are left out.
+pub fn relations(movie: &Movie, related_movies: &[Movie], related_by: &[Movie]) -> MovieRelations {
+    let relations = movie
+        .relations
+        .iter()
+        .flatten()
+        .filter_map(|relation| {
+            related_movies
+                .iter()
+                .find(|related_movie| related_movie.movie_id == relation.movie_id)
+                .map(|related_movie| related_movie_of(relation.kind, related_movie))
+        })
+        .collect();
+    let mut related_by: Vec<RelatedMovie> = related_by
+        .iter()
+        .flat_map(|related_movie| {
+            related_movie
+                .relations
+                .iter()
+                .flatten()
+                .filter(|relation| relation.movie_id == movie.movie_id)
+                .map(move |relation| related_movie_of(relation.kind, related_movie))
+        })
+        .collect();
+    related_by.sort_by(|a, b| {
+        a.year_of_release
+            .cmp(&b.year_of_release)
+            .then(a.movie_id.cmp(&b.movie_id))
+            .then(a.kind.cmp(&b.kind))
+    });
+    return MovieRelations {
+        movie_id: movie.movie_id,
+        relations,
+        related_by,
+    };
+}
+
+fn related_movie_of(kind: RelationKind, movie: &Movie) -> RelatedMovie {
+    return RelatedMovie {
+        kind,
+        movie_id: movie.movie_id,
+        title: movie.title.clone(),
+        year_of_release: movie.year_of_release,
+    };
+}
+
+/// Returns summary of franchise over its instalments, in instalment order.
+/// Movies which are not instalments of the franchise are ignored.
+pub fn summary(franchise: &Franchise, movies: &[Movie]) -> Result<FranchiseSummary> {
+    let mut instalments: Vec<FranchiseInstalment> = Vec::new();
+    let mut total_budget_crores = Decimal::ZERO;
+    let mut total_collection_crores = Decimal::ZERO;
+    let mut released_instalment_count = 0;
+    let mut verdict_provisional = false;
+    let mut previous_collection_crores: Option<Decimal> = None;
+    let instalment_movies = franchise
+        .movie_ids
+        .iter()
+        .filter_map(|movie_id| movies.iter().find(|movie| movie.movie_id == *movie_id));
+    for movie in instalment_movies {
+        let verdict = release::verdict(movie.status, movie.budget_crores, movie.collection_crores)?;
+        let mut collection_change_percentage = None;
+        if movie.status.is_released() {
+            released_instalment_count += 1;
+            total_budget_crores += movie.budget_crores;
+            total_collection_crores += movie.collection_crores;
+            verdict_provisional |= movie.status.is_verdict_provisional();
+            collection_change_percentage = match previous_collection_crores {
+                Some(previous) => change_percentage(previous, movie.collection_crores)?,
+                None => None,
+            };
+            previous_collection_crores = Some(movie.collection_crores);
+        }
+        instalments.push(FranchiseInstalment {
+            instalment: instalments.len() + 1,
+            movie_id: movie.movie_id,
+            title: movie.title.clone(),
+            year_of_release: movie.year_of_release,
+            status: movie.status,
+            budget_crores: movie.budget_crores,
+            collection_crores: movie.collection_crores,
+            verdict,
+            verdict_provisional: movie.status.is_verdict_provisional(),
+            collection_change_percentage,
+        });
+    }
+    let verdict = match released_instalment_count {
+        0 => NOT_RELEASED_VERDICT.to_string(),
+        _ => calculate_verdict(total_budget_crores, total_collection_crores)?,
+    };
+    return Ok(FranchiseSummary {
+        franchise_id: franchise.id.clone(),
+        name: franchise.name.clone(),
+        instalment_count: instalments.len(),
+        released_instalment_count,
+        total_budget_crores,
+        total_collection_crores,
+        total_profit_crores: total_collection_crores - total_budget_crores,
+        verdict,
+        verdict_provisional,
+        instalments,
+    });
+}
+
+/// Returns change from previous to current as percentage of previous, negative when it fell, none when previous is zero.
+fn change_percentage(previous: Decimal, current: Decimal) -> Result<Option<Decimal>> {
+    if previous.is_zero() {
+        return Ok(None);
+    }
+    let percentage = calculate_percentage(previous, current, PercentageRounding::FINANCIALS)?;
+    if current < previous {
+        return Ok(Some(-percentage));
+    }
+    return Ok(Some(percentage));
+}
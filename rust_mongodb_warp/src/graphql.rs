@@ -0,0 +1,1237 @@
+/// GraphQL API over the movie catalogue.
+/// Queries select fields of movies, including computed profit, with filtering, sorting and pagination,
+/// and aggregate movies per verdict, production house and territory. Filmographies give verdict records of people.
+/// Amounts can be adjusted for inflation to a base year, so that movies of different eras compare fairly.
+/// Mutations reuse request validation, business validation and verdict calculation of the REST handlers.
+use crate::business_layer::{calculate_percentage, has_territory, PercentageRounding};
+use crate::error::BoxOfficeError;
+use crate::{
+    constants::*,
+    db_layer::DB,
+    franchise, handler,
+    metadata::{self, has_metadata},
+    model::{
+        Credit, FacetCount, Filmography, FilmographyEntry, FranchiseInstalment, FranchiseSummary,
+        InflationAdjustedAmounts, Market, Movie, MovieFacets, MovieRelation, RelationKind,
+        ReleaseStatus, Role, SearchField, SearchHit, SearchResults, Suggestion,
+        TerritoryCollection,
+    },
+    people,
+    request_response_structs::*,
+};
+use async_graphql::{
+    Context, EmptySubscription, Enum, InputObject, Object, Result, Schema, SimpleObject,
+};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use validator::Validate;
+
+pub type BoxOfficeSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+/// Builds GraphQL schema executing against the database.
+pub fn schema(db: DB) -> BoxOfficeSchema {
+    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(db)
+        .limit_depth(GRAPHQL_DEPTH_LIMIT)
+        .finish()
+}
+
+/// Movie with computed profit fields.
+pub struct MovieNode(Movie);
+
+#[Object(name = "Movie")]
+impl MovieNode {
+    async fn id(&self) -> &str {
+        &self.0.id
+    }
+
+    async fn movie_id(&self) -> u8 {
+        self.0.movie_id
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    async fn production_house(&self) -> &str {
+        &self.0.production_house
+    }
+
+    /// ID of the production house, none for movies not linked yet.
+    async fn production_house_id(&self) -> Option<&str> {
+        self.0.production_house_id.as_deref()
+    }
+
+    async fn year_of_release(&self) -> u16 {
+        self.0.year_of_release
+    }
+
+    /// Release date 'YYYY-MM-DD', not set when not announced yet.
+    async fn release_date(&self) -> Option<&str> {
+        self.0.release_date.as_deref()
+    }
+
+    async fn status(&self) -> ReleaseStatusType {
+        self.0.status.into()
+    }
+
+    async fn budget_crores(&self) -> Decimal {
+        self.0.budget_crores
+    }
+
+    async fn collection_crores(&self) -> Decimal {
+        self.0.collection_crores
+    }
+
+    async fn verdict(&self) -> &str {
+        &self.0.verdict
+    }
+
+    /// Verdict may still change as the movie is not closed yet.
+    async fn verdict_provisional(&self) -> bool {
+        self.0.verdict_provisional
+    }
+
+    /// Collection minus budget, negative for a loss.
+    async fn profit_crores(&self) -> Decimal {
+        profit_crores(&self.0)
+    }
+
+    /// Profit as rounded percentage of budget, negative for a loss.
+    async fn profit_percentage(&self) -> Result<i32> {
+        let percentage = calculate_percentage(
+            self.0.budget_crores,
+            self.0.collection_crores,
+            PercentageRounding::VERDICT,
+        )?
+        .to_i32()
+        .ok_or_else(|| {
+            BoxOfficeError::ErrorinVerdictCalculation(format!(
+                "profit percentage of movie '{}' exceeds {}",
+                self.0.movie_id,
+                i32::MAX
+            ))
+        })?;
+        if self.0.budget_crores > self.0.collection_crores {
+            return Ok(-percentage);
+        }
+        return Ok(percentage);
+    }
+
+    /// Budget adjusted for inflation to the base year, the latest CPI year when not given.
+    async fn adjusted_budget_crores(
+        &self,
+        ctx: &Context<'_>,
+        base_year: Option<u16>,
+    ) -> Result<Decimal> {
+        return Ok(adjust_for_inflation(ctx, &self.0, base_year)?.budget_crores);
+    }
+
+    /// Collection adjusted for inflation to the base year, the latest CPI year when not given.
+    async fn adjusted_collection_crores(
+        &self,
+        ctx: &Context<'_>,
+        base_year: Option<u16>,
+    ) -> Result<Decimal> {
+        return Ok(adjust_for_inflation(ctx, &self.0, base_year)?.collection_crores);
+    }
+
+    /// Budget and collection broken down by territory, empty if the movie has no breakdown.
+    async fn territories(&self) -> Vec<TerritoryNode> {
+        self.0
+            .territories
+            .iter()
+            .flatten()
+            .cloned()
+            .map(TerritoryNode::from)
+            .collect()
+    }
+
+    /// Territory breakdown no longer adds up, as budget or collection changed after it was recorded.
+    async fn territories_stale(&self) -> bool {
+        self.0.territories_stale
+    }
+
+    async fn genres(&self) -> &[String] {
+        &self.0.metadata.genres
+    }
+
+    async fn languages(&self) -> &[String] {
+        &self.0.metadata.languages
+    }
+
+    /// CBFC certification, not set when not certified yet.
+    async fn certification(&self) -> Option<&str> {
+        self.0.metadata.certification.as_deref()
+    }
+
+    async fn runtime_minutes(&self) -> Option<u16> {
+        self.0.metadata.runtime_minutes
+    }
+
+    async fn tags(&self) -> &[String] {
+        &self.0.metadata.tags
+    }
+
+    /// Cast and crew of the movie.
+    async fn credits(&self) -> Vec<CreditNode> {
+        self.0
+            .credits
+            .iter()
+            .flatten()
+            .cloned()
+            .map(CreditNode::from)
+            .collect()
+    }
+
+    /// Movies this movie is a sequel, remake or dubbed version of.
+    async fn relations(&self) -> Vec<MovieRelationNode> {
+        self.0
+            .relations
+            .iter()
+            .flatten()
+            .cloned()
+            .map(MovieRelationNode::from)
+            .collect()
+    }
+}
+
+/// Stage of a movie in its release lifecycle.
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "ReleaseStatus", remote = "ReleaseStatus")]
+pub enum ReleaseStatusType {
+    Announced,
+    InProduction,
+    Released,
+    Running,
+    Closed,
+}
+
+/// Market of a territory.
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "Market", remote = "Market")]
+pub enum MarketType {
+    Domestic,
+    Overseas,
+}
+
+/// Budget share and collection of a movie in one territory, with the verdict they earn.
+#[derive(SimpleObject)]
+#[graphql(name = "Territory")]
+pub struct TerritoryNode {
+    territory: String,
+    market: MarketType,
+    budget_crores: Decimal,
+    collection_crores: Decimal,
+    verdict: String,
+}
+
+impl From<TerritoryCollection> for TerritoryNode {
+    fn from(territory: TerritoryCollection) -> Self {
+        Self {
+            territory: territory.territory,
+            market: territory.market.into(),
+            budget_crores: territory.budget_crores,
+            collection_crores: territory.collection_crores,
+            verdict: territory.verdict,
+        }
+    }
+}
+
+/// Role of a person in a movie.
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "Role", remote = "Role")]
+pub enum RoleType {
+    Actor,
+    Director,
+    MusicDirector,
+    Producer,
+}
+
+/// Credit of a person in a role on a movie.
+#[derive(SimpleObject)]
+#[graphql(name = "Credit")]
+pub struct CreditNode {
+    person_id: String,
+    name: String,
+    role: RoleType,
+    character: Option<String>,
+}
+
+impl From<Credit> for CreditNode {
+    fn from(credit: Credit) -> Self {
+        Self {
+            person_id: credit.person_id,
+            name: credit.name,
+            role: credit.role.into(),
+            character: credit.character,
+        }
+    }
+}
+
+/// Kind of relation of a movie to an earlier movie.
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "RelationKind", remote = "RelationKind")]
+pub enum RelationKindType {
+    SequelOf,
+    RemakeOf,
+    DubbedVersionOf,
+}
+
+/// Relation of a movie to another movie.
+#[derive(SimpleObject)]
+#[graphql(name = "MovieRelation")]
+pub struct MovieRelationNode {
+    kind: RelationKindType,
+    movie_id: u8,
+}
+
+impl From<MovieRelation> for MovieRelationNode {
+    fn from(relation: MovieRelation) -> Self {
+        Self {
+            kind: relation.kind.into(),
+            movie_id: relation.movie_id,
+        }
+    }
+}
+
+/// Combined figures of a franchise with the trend over its instalments.
+#[derive(SimpleObject)]
+#[graphql(name = "FranchiseSummary")]
+pub struct FranchiseSummaryNode {
+    franchise_id: String,
+    name: String,
+    instalment_count: usize,
+    released_instalment_count: usize,
+    /// Totals of released instalments only.
+    total_budget_crores: Decimal,
+    total_collection_crores: Decimal,
+    total_profit_crores: Decimal,
+    /// Verdict of the totals, 'NOT RELEASED' when no instalment is released yet.
+    verdict: String,
+    verdict_provisional: bool,
+    instalments: Vec<FranchiseInstalmentNode>,
+}
+
+impl From<FranchiseSummary> for FranchiseSummaryNode {
+    fn from(summary: FranchiseSummary) -> Self {
+        Self {
+            franchise_id: summary.franchise_id,
+            name: summary.name,
+            instalment_count: summary.instalment_count,
+            released_instalment_count: summary.released_instalment_count,
+            total_budget_crores: summary.total_budget_crores,
+            total_collection_crores: summary.total_collection_crores,
+            total_profit_crores: summary.total_profit_crores,
+            verdict: summary.verdict,
+            verdict_provisional: summary.verdict_provisional,
+            instalments: summary
+                .instalments
+                .into_iter()
+                .map(FranchiseInstalmentNode::from)
+                .collect(),
+        }
+    }
+}
+
+/// Instalment of a franchise with its collection change over the previous released instalment.
+#[derive(SimpleObject)]
+#[graphql(name = "FranchiseInstalment")]
+pub struct FranchiseInstalmentNode {
+    instalment: usize,
+    movie_id: u8,
+    title: String,
+    year_of_release: u16,
+    status: ReleaseStatusType,
+    budget_crores: Decimal,
+    collection_crores: Decimal,
+    verdict: String,
+    verdict_provisional: bool,
+    /// Null for the first released instalment and unreleased instalments.
+    collection_change_percentage: Option<Decimal>,
+}
+
+impl From<FranchiseInstalment> for FranchiseInstalmentNode {
+    fn from(instalment: FranchiseInstalment) -> Self {
+        Self {
+            instalment: instalment.instalment,
+            movie_id: instalment.movie_id,
+            title: instalment.title,
+            year_of_release: instalment.year_of_release,
+            status: instalment.status.into(),
+            budget_crores: instalment.budget_crores,
+            collection_crores: instalment.collection_crores,
+            verdict: instalment.verdict,
+            verdict_provisional: instalment.verdict_provisional,
+            collection_change_percentage: instalment.collection_change_percentage,
+        }
+    }
+}
+
+/// Field of a movie matched by search.
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "SearchField", remote = "SearchField")]
+pub enum SearchFieldType {
+    Title,
+    ProductionHouse,
+    Cast,
+}
+
+/// Movies matching a search query, most relevant first.
+#[derive(SimpleObject)]
+#[graphql(name = "SearchResults")]
+pub struct SearchResultsNode {
+    query: String,
+    /// Typo-tolerant matching over all movies was used, as full-text search found fewer movies than the limit.
+    fuzzy: bool,
+    hits: Vec<SearchHitNode>,
+}
+
+impl From<SearchResults> for SearchResultsNode {
+    fn from(search_results: SearchResults) -> Self {
+        Self {
+            query: search_results.query,
+            fuzzy: search_results.fuzzy,
+            hits: search_results
+                .hits
+                .into_iter()
+                .map(SearchHitNode::from)
+                .collect(),
+        }
+    }
+}
+
+/// Movie matching a search query with its relevance.
+#[derive(SimpleObject)]
+#[graphql(name = "SearchHit")]
+pub struct SearchHitNode {
+    movie_id: u8,
+    title: String,
+    production_house: String,
+    year_of_release: u16,
+    verdict: String,
+    /// Relevance, higher is more relevant.
+    score: f64,
+    matched_fields: Vec<SearchFieldType>,
+}
+
+impl From<SearchHit> for SearchHitNode {
+    fn from(hit: SearchHit) -> Self {
+        Self {
+            movie_id: hit.movie_id,
+            title: hit.title,
+            production_house: hit.production_house,
+            year_of_release: hit.year_of_release,
+            verdict: hit.verdict,
+            score: hit.score,
+            matched_fields: hit
+                .matched_fields
+                .into_iter()
+                .map(SearchFieldType::from)
+                .collect(),
+        }
+    }
+}
+
+/// Completion of a partly typed query.
+#[derive(SimpleObject)]
+#[graphql(name = "Suggestion")]
+pub struct SuggestionNode {
+    text: String,
+    field: SearchFieldType,
+    /// Movie Id of suggested titles.
+    movie_id: Option<u8>,
+}
+
+impl From<Suggestion> for SuggestionNode {
+    fn from(suggestion: Suggestion) -> Self {
+        Self {
+            text: suggestion.text,
+            field: suggestion.field.into(),
+            movie_id: suggestion.movie_id,
+        }
+    }
+}
+
+/// Movies of a person with their verdicts and the success rate over the released ones.
+#[derive(SimpleObject)]
+#[graphql(name = "Filmography")]
+pub struct FilmographyNode {
+    person_id: String,
+    name: String,
+    movie_count: usize,
+    released_movie_count: usize,
+    hit_count: usize,
+    /// Hits as percentage of released movies, null when no movie is released yet.
+    success_rate: Option<Decimal>,
+    movies: Vec<FilmographyEntryNode>,
+}
+
+impl From<Filmography> for FilmographyNode {
+    fn from(filmography: Filmography) -> Self {
+        Self {
+            person_id: filmography.person_id,
+            name: filmography.name,
+            movie_count: filmography.movie_count,
+            released_movie_count: filmography.released_movie_count,
+            hit_count: filmography.hit_count,
+            success_rate: filmography.success_rate,
+            movies: filmography
+                .movies
+                .into_iter()
+                .map(FilmographyEntryNode::from)
+                .collect(),
+        }
+    }
+}
+
+/// Movie in a filmography with role of the person in it.
+#[derive(SimpleObject)]
+#[graphql(name = "FilmographyEntry")]
+pub struct FilmographyEntryNode {
+    movie_id: u8,
+    title: String,
+    year_of_release: u16,
+    role: RoleType,
+    character: Option<String>,
+    status: ReleaseStatusType,
+    verdict: String,
+    verdict_provisional: bool,
+}
+
+impl From<FilmographyEntry> for FilmographyEntryNode {
+    fn from(entry: FilmographyEntry) -> Self {
+        Self {
+            movie_id: entry.movie_id,
+            title: entry.title,
+            year_of_release: entry.year_of_release,
+            role: entry.role.into(),
+            character: entry.character,
+            status: entry.status.into(),
+            verdict: entry.verdict,
+            verdict_provisional: entry.verdict_provisional,
+        }
+    }
+}
+
+/// Adjusts amounts of movie to the base year, the latest CPI year when not given.
+fn adjust_for_inflation(
+    ctx: &Context<'_>,
+    movie: &Movie,
+    base_year: Option<u16>,
+) -> Result<InflationAdjustedAmounts> {
+    let db = ctx.data::<DB>()?;
+    let base_year = match base_year {
+        Some(base_year) => base_year,
+        None => db.cpi.latest_year()?,
+    };
+    return Ok(db.cpi.adjust_movie(movie, base_year)?);
+}
+
+fn profit_crores(movie: &Movie) -> Decimal {
+    movie.collection_crores - movie.budget_crores
+}
+
+/// Movies are returned if they match every given field.
+#[derive(InputObject, Default)]
+pub struct MovieFilter {
+    /// Case-insensitive exact match.
+    production_house: Option<String>,
+    /// Case-insensitive exact match.
+    verdict: Option<String>,
+    /// Case-insensitive substring match.
+    title_contains: Option<String>,
+    year_from: Option<u16>,
+    year_to: Option<u16>,
+    /// Movies with collection in the territory, exact match.
+    territory: Option<String>,
+    /// Movies with collection in the market.
+    market: Option<MarketType>,
+    status: Option<ReleaseStatusType>,
+    /// Case-insensitive exact match of one of the genres.
+    genre: Option<String>,
+    /// Case-insensitive exact match of one of the languages.
+    language: Option<String>,
+    /// Case-insensitive exact match.
+    certification: Option<String>,
+    /// Case-insensitive exact match of one of the tags.
+    tag: Option<String>,
+}
+
+impl MovieFilter {
+    fn matches(&self, movie: &Movie) -> bool {
+        let equals = |expected: &Option<String>, actual: &str| {
+            expected
+                .as_ref()
+                .map(|expected| expected.eq_ignore_ascii_case(actual))
+                .unwrap_or(true)
+        };
+        let title_matches = self
+            .title_contains
+            .as_ref()
+            .map(|title| movie.title.to_lowercase().contains(&title.to_lowercase()))
+            .unwrap_or(true);
+        return equals(&self.production_house, &movie.production_house)
+            && equals(&self.verdict, &movie.verdict)
+            && title_matches
+            && self
+                .year_from
+                .map(|year| movie.year_of_release >= year)
+                .unwrap_or(true)
+            && self
+                .year_to
+                .map(|year| movie.year_of_release <= year)
+                .unwrap_or(true)
+            && self
+                .status
+                .map(|status| ReleaseStatus::from(status) == movie.status)
+                .unwrap_or(true)
+            && has_territory(
+                movie,
+                self.territory.as_deref(),
+                self.market.map(Market::from),
+            )
+            && has_metadata(
+                movie,
+                self.genre.as_deref(),
+                self.language.as_deref(),
+                self.certification.as_deref(),
+                self.tag.as_deref(),
+            );
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum MovieSortField {
+    MovieId,
+    Title,
+    YearOfRelease,
+    BudgetCrores,
+    CollectionCrores,
+    ProfitCrores,
+    /// Adjusted amounts order movies the same way for every base year.
+    AdjustedBudgetCrores,
+    AdjustedCollectionCrores,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Default)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(InputObject)]
+pub struct MovieSort {
+    field: MovieSortField,
+    #[graphql(default)]
+    direction: SortDirection,
+}
+
+impl MovieSort {
+    fn compare(&self, first: &Movie, second: &Movie) -> Ordering {
+        let ordering = match self.field {
+            MovieSortField::MovieId => first.movie_id.cmp(&second.movie_id),
+            MovieSortField::Title => first.title.cmp(&second.title),
+            MovieSortField::YearOfRelease => first.year_of_release.cmp(&second.year_of_release),
+            MovieSortField::BudgetCrores => first.budget_crores.cmp(&second.budget_crores),
+            MovieSortField::CollectionCrores => {
+                first.collection_crores.cmp(&second.collection_crores)
+            }
+            MovieSortField::ProfitCrores => profit_crores(first).cmp(&profit_crores(second)),
+            MovieSortField::AdjustedBudgetCrores => {
+                let adjusted_budget = |movie: &Movie| {
+                    movie
+                        .inflation_adjusted
+                        .as_ref()
+                        .map(|amounts| amounts.budget_crores)
+                };
+                adjusted_budget(first).cmp(&adjusted_budget(second))
+            }
+            MovieSortField::AdjustedCollectionCrores => {
+                let adjusted_collection = |movie: &Movie| {
+                    movie
+                        .inflation_adjusted
+                        .as_ref()
+                        .map(|amounts| amounts.collection_crores)
+                };
+                adjusted_collection(first).cmp(&adjusted_collection(second))
+            }
+        };
+        match self.direction {
+            SortDirection::Asc => ordering,
+            SortDirection::Desc => ordering.reverse(),
+        }
+    }
+
+    /// Sorts movies, adjusting them for inflation first when sorted by an adjusted amount.
+    fn sort(&self, ctx: &Context<'_>, movies: &mut [Movie]) -> Result<()> {
+        if matches!(
+            self.field,
+            MovieSortField::AdjustedBudgetCrores | MovieSortField::AdjustedCollectionCrores
+        ) {
+            for movie in movies.iter_mut() {
+                movie.inflation_adjusted = Some(adjust_for_inflation(ctx, movie, None)?);
+            }
+        }
+        movies.sort_by(|first, second| self.compare(first, second));
+        return Ok(());
+    }
+}
+
+/// One page of movies.
+#[derive(SimpleObject)]
+pub struct MoviePage {
+    /// Number of movies matching the filter, across all pages.
+    total_count: usize,
+    offset: usize,
+    limit: usize,
+    items: Vec<MovieNode>,
+    /// Number of movies per metadata value, across all pages.
+    facets: MovieFacetsNode,
+}
+
+/// Number of movies having a metadata value.
+#[derive(SimpleObject)]
+#[graphql(name = "FacetCount")]
+pub struct FacetCountNode {
+    value: String,
+    count: usize,
+}
+
+impl From<FacetCount> for FacetCountNode {
+    fn from(facet_count: FacetCount) -> Self {
+        Self {
+            value: facet_count.value,
+            count: facet_count.count,
+        }
+    }
+}
+
+/// Number of movies per genre, language, certification and tag, most frequent value first.
+#[derive(SimpleObject)]
+#[graphql(name = "MovieFacets")]
+pub struct MovieFacetsNode {
+    genres: Vec<FacetCountNode>,
+    languages: Vec<FacetCountNode>,
+    certifications: Vec<FacetCountNode>,
+    tags: Vec<FacetCountNode>,
+}
+
+impl From<MovieFacets> for MovieFacetsNode {
+    fn from(facets: MovieFacets) -> Self {
+        let nodes = |facet_counts: Vec<FacetCount>| {
+            facet_counts.into_iter().map(FacetCountNode::from).collect()
+        };
+        Self {
+            genres: nodes(facets.genres),
+            languages: nodes(facets.languages),
+            certifications: nodes(facets.certifications),
+            tags: nodes(facets.tags),
+        }
+    }
+}
+
+/// Number of movies having a verdict.
+#[derive(SimpleObject)]
+pub struct VerdictCount {
+    verdict: String,
+    movie_count: usize,
+}
+
+/// Totals over a set of movies.
+#[derive(SimpleObject)]
+pub struct MovieStats {
+    movie_count: usize,
+    total_budget_crores: Decimal,
+    total_collection_crores: Decimal,
+    total_profit_crores: Decimal,
+    verdicts: Vec<VerdictCount>,
+    /// Totals adjusted for inflation, set when a base year is given.
+    inflation_adjusted: Option<InflationAdjustedTotals>,
+}
+
+/// Totals with amounts of every movie adjusted for inflation to the base year.
+#[derive(SimpleObject)]
+pub struct InflationAdjustedTotals {
+    base_year: u16,
+    total_budget_crores: Decimal,
+    total_collection_crores: Decimal,
+    total_profit_crores: Decimal,
+}
+
+impl MovieStats {
+    fn new(movies: &[Movie]) -> Self {
+        return Self::from_figures(movies.iter().map(|movie| {
+            (
+                movie.budget_crores,
+                movie.collection_crores,
+                &*movie.verdict,
+            )
+        }));
+    }
+
+    /// Totals over budget, collection and verdict of every movie.
+    fn from_figures<'a>(figures: impl Iterator<Item = (Decimal, Decimal, &'a str)>) -> Self {
+        let mut movie_count = 0;
+        let mut total_budget_crores = Decimal::ZERO;
+        let mut total_collection_crores = Decimal::ZERO;
+        let mut verdict_counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for (budget_crores, collection_crores, verdict) in figures {
+            movie_count += 1;
+            total_budget_crores += budget_crores;
+            total_collection_crores += collection_crores;
+            *verdict_counts.entry(verdict).or_default() += 1;
+        }
+        Self {
+            movie_count,
+            total_budget_crores,
+            total_collection_crores,
+            total_profit_crores: total_collection_crores - total_budget_crores,
+            verdicts: verdict_counts
+                .into_iter()
+                .map(|(verdict, movie_count)| VerdictCount {
+                    verdict: verdict.to_string(),
+                    movie_count,
+                })
+                .collect(),
+            inflation_adjusted: None,
+        }
+    }
+
+    /// Adds totals adjusted for inflation to the base year.
+    fn with_inflation_adjustment(
+        mut self,
+        ctx: &Context<'_>,
+        movies: &[Movie],
+        base_year: Option<u16>,
+    ) -> Result<Self> {
+        let base_year = match base_year {
+            Some(base_year) => base_year,
+            None => return Ok(self),
+        };
+        let adjusted = movies
+            .iter()
+            .map(|movie| adjust_for_inflation(ctx, movie, Some(base_year)))
+            .collect::<Result<Vec<InflationAdjustedAmounts>>>()?;
+        let total_budget_crores: Decimal =
+            adjusted.iter().map(|amounts| amounts.budget_crores).sum();
+        let total_collection_crores: Decimal = adjusted
+            .iter()
+            .map(|amounts| amounts.collection_crores)
+            .sum();
+        self.inflation_adjusted = Some(InflationAdjustedTotals {
+            base_year,
+            total_budget_crores,
+            total_collection_crores,
+            total_profit_crores: total_collection_crores - total_budget_crores,
+        });
+        return Ok(self);
+    }
+}
+
+/// Movies of one production house with their totals.
+pub struct ProductionHouseGroup {
+    production_house: String,
+    movies: Vec<Movie>,
+}
+
+#[Object]
+impl ProductionHouseGroup {
+    async fn production_house(&self) -> &str {
+        &self.production_house
+    }
+
+    /// Totals of the production house, also adjusted for inflation when a base year is given.
+    async fn stats(&self, ctx: &Context<'_>, base_year: Option<u16>) -> Result<MovieStats> {
+        MovieStats::new(&self.movies).with_inflation_adjustment(ctx, &self.movies, base_year)
+    }
+
+    async fn movies(&self, ctx: &Context<'_>, sort: Option<MovieSort>) -> Result<Vec<MovieNode>> {
+        let mut movies = self.movies.clone();
+        if let Some(sort) = sort {
+            sort.sort(ctx, &mut movies)?;
+        }
+        return Ok(movies.into_iter().map(MovieNode).collect());
+    }
+}
+
+/// Territory breakdowns of movies in one territory with their totals.
+/// Verdicts are those the movies earn in the territory.
+pub struct TerritoryGroup {
+    territory: String,
+    market: Market,
+    territories: Vec<TerritoryCollection>,
+}
+
+#[Object]
+impl TerritoryGroup {
+    async fn territory(&self) -> &str {
+        &self.territory
+    }
+
+    async fn market(&self) -> MarketType {
+        self.market.into()
+    }
+
+    /// Totals of the territory over movies having it in their breakdown.
+    async fn stats(&self) -> MovieStats {
+        MovieStats::from_figures(self.territories.iter().map(|territory| {
+            (
+                territory.budget_crores,
+                territory.collection_crores,
+                &*territory.verdict,
+            )
+        }))
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Movies matching the filter, sorted and paginated.
+    async fn movies(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<MovieFilter>,
+        sort: Option<MovieSort>,
+        #[graphql(default = 0)] offset: usize,
+        #[graphql(default = 20, validator(maximum = 100))] limit: usize,
+    ) -> Result<MoviePage> {
+        let mut movies = filtered_movies(ctx, filter).await?;
+        if let Some(sort) = sort {
+            sort.sort(ctx, &mut movies)?;
+        }
+        let total_count = movies.len();
+        let facets = MovieFacetsNode::from(metadata::facets(&movies));
+        let items = movies
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(MovieNode)
+            .collect();
+        return Ok(MoviePage {
+            total_count,
+            offset,
+            limit,
+            items,
+            facets,
+        });
+    }
+
+    /// Movie with the given Movie Id.
+    async fn movie(&self, ctx: &Context<'_>, movie_id: u8) -> Result<Option<MovieNode>> {
+        let db = ctx.data::<DB>()?;
+        let id = movie_id.to_string();
+        if !*db.check_movie_exist(&id).await? {
+            return Ok(None);
+        }
+        let movie = db.get_movie_by_id(&id).await?;
+        return Ok(Some(MovieNode(movie)));
+    }
+
+    /// Totals over movies matching the filter, also adjusted for inflation when a base year is given.
+    async fn stats(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<MovieFilter>,
+        base_year: Option<u16>,
+    ) -> Result<MovieStats> {
+        let movies = filtered_movies(ctx, filter).await?;
+        return MovieStats::new(&movies).with_inflation_adjustment(ctx, &movies, base_year);
+    }
+
+    /// Movies matching the filter grouped by production house.
+    async fn production_houses(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<MovieFilter>,
+    ) -> Result<Vec<ProductionHouseGroup>> {
+        let mut groups: BTreeMap<String, Vec<Movie>> = BTreeMap::new();
+        for movie in filtered_movies(ctx, filter).await? {
+            groups
+                .entry(movie.production_house.clone())
+                .or_default()
+                .push(movie);
+        }
+        return Ok(groups
+            .into_iter()
+            .map(|(production_house, movies)| ProductionHouseGroup {
+                production_house,
+                movies,
+            })
+            .collect());
+    }
+
+    /// Territory breakdowns of movies matching the filter grouped by territory,
+    /// only territories of the market when given.
+    async fn territories(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<MovieFilter>,
+        market: Option<MarketType>,
+    ) -> Result<Vec<TerritoryGroup>> {
+        let market = market.map(Market::from);
+        let mut groups: BTreeMap<(Market, String), Vec<TerritoryCollection>> = BTreeMap::new();
+        for movie in filtered_movies(ctx, filter).await? {
+            for territory in movie.territories.into_iter().flatten() {
+                if market
+                    .map(|market| market != territory.market)
+                    .unwrap_or(false)
+                {
+                    continue;
+                }
+                groups
+                    .entry((territory.market, territory.territory.clone()))
+                    .or_default()
+                    .push(territory);
+            }
+        }
+        return Ok(groups
+            .into_iter()
+            .map(|((market, territory), territories)| TerritoryGroup {
+                territory,
+                market,
+                territories,
+            })
+            .collect());
+    }
+
+    /// Filmography of the person with the given ID, only credits in the role when given.
+    async fn filmography(
+        &self,
+        ctx: &Context<'_>,
+        person_id: String,
+        role: Option<RoleType>,
+    ) -> Result<Option<FilmographyNode>> {
+        let db = ctx.data::<DB>()?;
+        let person = match db.get_person(&person_id).await? {
+            Some(person) => person,
+            None => return Ok(None),
+        };
+        let movies = db.read_person_movies(&person.id).await?;
+        let filmography = people::filmography(&person, &movies, role.map(Role::from))?;
+        return Ok(Some(FilmographyNode::from(filmography)));
+    }
+
+    /// Summary of the franchise with the given ID, with the trend over its instalments.
+    async fn franchise(
+        &self,
+        ctx: &Context<'_>,
+        franchise_id: String,
+    ) -> Result<Option<FranchiseSummaryNode>> {
+        let db = ctx.data::<DB>()?;
+        let franchise = match db.get_franchise(&franchise_id).await? {
+            Some(franchise) => franchise,
+            None => return Ok(None),
+        };
+        let movies = db.read_movies_by_ids(&franchise.movie_ids).await?;
+        let summary = franchise::summary(&franchise, &movies)?;
+        return Ok(Some(FranchiseSummaryNode::from(summary)));
+    }
+
+    /// Movies whose title, production house or cast match the query, most relevant first.
+    async fn search(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        limit: Option<usize>,
+    ) -> Result<SearchResultsNode> {
+        let db = ctx.data::<DB>()?;
+        let search_query = SearchQuery { q: query, limit };
+        search_query
+            .validate()
+            .map_err(BoxOfficeError::ValidationError)?;
+        let search_results = handler::search_movies(&search_query, db).await?;
+        return Ok(SearchResultsNode::from(search_results));
+    }
+
+    /// Titles, production houses and people completing the partly typed query.
+    async fn suggest(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        limit: Option<usize>,
+    ) -> Result<Vec<SuggestionNode>> {
+        let db = ctx.data::<DB>()?;
+        let search_query = SearchQuery { q: query, limit };
+        search_query
+            .validate()
+            .map_err(BoxOfficeError::ValidationError)?;
+        return Ok(handler::movie_search_index(db)
+            .await?
+            .suggest(&search_query.q, search_query.limit())
+            .into_iter()
+            .map(SuggestionNode::from)
+            .collect());
+    }
+}
+
+async fn filtered_movies(ctx: &Context<'_>, filter: Option<MovieFilter>) -> Result<Vec<Movie>> {
+    let db = ctx.data::<DB>()?;
+    let filter = filter.unwrap_or_default();
+    let movies = db.read_movies().await?;
+    return Ok(movies
+        .into_iter()
+        .filter(|movie| filter.matches(movie))
+        .collect());
+}
+
+#[derive(InputObject)]
+pub struct CreateMovieInput {
+    movie_id: u8,
+    title: String,
+    production_house: String,
+    year_of_release: u16,
+    /// Release date 'YYYY-MM-DD'.
+    release_date: Option<String>,
+    /// Released when not given.
+    status: Option<ReleaseStatusType>,
+    budget_crores: Decimal,
+    collection_crores: Decimal,
+    /// Genres, languages, certification, runtime and tags, none when not given.
+    metadata: Option<MovieMetadataInput>,
+}
+
+impl From<CreateMovieInput> for CreateMovieRequest {
+    fn from(input: CreateMovieInput) -> Self {
+        Self {
+            movie_id: input.movie_id,
+            title: input.title,
+            production_house: input.production_house,
+            year_of_release: input.year_of_release,
+            release_date: input.release_date,
+            status: input.status.map(ReleaseStatus::from),
+            budget_crores: input.budget_crores,
+            collection_crores: input.collection_crores,
+            // Amounts are INR crores.
+            currency: None,
+            unit: None,
+            exchange_rate_date: None,
+            metadata: input.metadata.map(MovieMetadataRequest::from),
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct UpdateMovieInput {
+    title: String,
+    production_house: String,
+    year_of_release: u16,
+    /// Release date 'YYYY-MM-DD', kept when not given.
+    release_date: Option<String>,
+    /// Kept when not given.
+    status: Option<ReleaseStatusType>,
+    budget_crores: Decimal,
+    collection_crores: Decimal,
+    /// Kept when not given.
+    metadata: Option<MovieMetadataInput>,
+}
+
+impl From<UpdateMovieInput> for UpdateMovieRequest {
+    fn from(input: UpdateMovieInput) -> Self {
+        Self {
+            title: input.title,
+            production_house: input.production_house,
+            year_of_release: input.year_of_release,
+            release_date: input.release_date,
+            status: input.status.map(ReleaseStatus::from),
+            budget_crores: input.budget_crores,
+            collection_crores: input.collection_crores,
+            // Amounts are INR crores.
+            currency: None,
+            unit: None,
+            exchange_rate_date: None,
+            metadata: input.metadata.map(MovieMetadataRequest::from),
+        }
+    }
+}
+
+/// Metadata of a movie, values are checked against the configured vocabularies.
+#[derive(InputObject)]
+pub struct MovieMetadataInput {
+    #[graphql(default)]
+    genres: Vec<String>,
+    #[graphql(default)]
+    languages: Vec<String>,
+    /// CBFC certification, e.g. 'UA 13+'.
+    certification: Option<String>,
+    runtime_minutes: Option<u16>,
+    #[graphql(default)]
+    tags: Vec<String>,
+}
+
+impl From<MovieMetadataInput> for MovieMetadataRequest {
+    fn from(input: MovieMetadataInput) -> Self {
+        Self {
+            genres: input.genres,
+            languages: input.languages,
+            certification: input.certification,
+            runtime_minutes: input.runtime_minutes,
+            tags: input.tags,
+        }
+    }
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    /// Creates movie, verdict is calculated from budget and collection.
+    async fn create_movie(&self, ctx: &Context<'_>, input: CreateMovieInput) -> Result<MovieNode> {
+        let db = ctx.data::<DB>()?;
+        let create_movie_request = CreateMovieRequest::from(input);
+        create_movie_request
+            .validate()
+            .map_err(BoxOfficeError::ValidationError)?;
+        handler::validate_create_movie_request(&create_movie_request, db)
+            .await
+            .map_err(|e| BoxOfficeError::InvalidFieldInCreateMovieRequestError(e.to_string()))?;
+        db.create_movie(&create_movie_request).await?;
+        let created_movie = db
+            .get_movie_by_id(&create_movie_request.movie_id.to_string())
+            .await?;
+        log::info!("Movie with Movie ID {} created.", created_movie.movie_id);
+        return Ok(MovieNode(created_movie));
+    }
+
+    /// Updates movie, verdict is recalculated from budget and collection.
+    async fn update_movie(
+        &self,
+        ctx: &Context<'_>,
+        movie_id: u8,
+        input: UpdateMovieInput,
+    ) -> Result<MovieNode> {
+        let db = ctx.data::<DB>()?;
+        let id = movie_id.to_string();
+        let update_movie_request = UpdateMovieRequest::from(input);
+        update_movie_request
+            .validate()
+            .map_err(BoxOfficeError::ValidationError)?;
+        handler::validate_update_movie_id(&id, db)
+            .await
+            .map_err(|e| BoxOfficeError::BadUpdateMovieRequestError(e.to_string()))?;
+        if db.update_movie(&id, &update_movie_request).await? != 1 {
+            return Err(DB_OPERATION_FAILED.into());
+        }
+        let updated_movie = db.get_movie_by_id(&id).await?;
+        log::info!("Movie with Movie ID {} updated.", id);
+        return Ok(MovieNode(updated_movie));
+    }
+
+    /// Deletes movie, returns Movie Id of the deleted movie.
+    async fn delete_movie(&self, ctx: &Context<'_>, movie_id: u8) -> Result<u8> {
+        let db = ctx.data::<DB>()?;
+        let id = movie_id.to_string();
+        handler::validate_delete_movie_id(&id, db)
+            .await
+            .map_err(|e| BoxOfficeError::BadDeleteMovieRequestError(e.to_string()))?;
+        if db.delete_movie(&id).await? != 1 {
+            return Err(DB_OPERATION_FAILED.into());
+        }
+        log::info!("Movie with Movie ID {} deleted.", id);
+        return Ok(movie_id);
+    }
+}
@@ -0,0 +1,304 @@
+#![allow(clippy::result_large_err)]
+
+/// gRPC API of the movie catalogue.
+/// Served with tonic on its own port, it shares db_layer, request validation,
+/// business validation and verdict calculation with the REST handlers.
+use crate::config::{GrpcConfig, ServerConfig};
+use crate::error::BoxOfficeError;
+use crate::{
+    db_layer::DB,
+    handler,
+    model::{Movie, ReleaseStatus},
+    request_response_structs, shutdown, Result,
+};
+use futures::{Stream, StreamExt};
+use proto::movie_service_server::{MovieService, MovieServiceServer};
+use rust_decimal::Decimal;
+use std::pin::Pin;
+use std::str::FromStr;
+use tokio::sync::oneshot;
+use tonic::{Request, Response, Status};
+use validator::Validate;
+
+/// Code generated from 'proto/movies.proto'.
+#[allow(clippy::all)]
+pub mod proto {
+    tonic::include_proto!("boxoffice.v1");
+}
+
+/// Serves gRPC API until a termination signal is received and in-flight calls are drained.
+/// Does nothing when gRPC is disabled.
+pub async fn run(db: DB, grpc_config: &GrpcConfig, server_config: &ServerConfig) -> Result<()> {
+    if !grpc_config.enabled {
+        return Ok(());
+    }
+    let (shutdown_started_sender, shutdown_started_receiver) = oneshot::channel();
+    let shutdown_signal = async move {
+        shutdown::termination_signal().await;
+        let _ = shutdown_started_sender.send(());
+    };
+    let address = grpc_config.socket_address();
+    let server = tonic::transport::Server::builder()
+        .add_service(MovieServiceServer::new(MovieGrpcService { db }))
+        .serve_with_shutdown(address, shutdown_signal);
+    log::info!("Listening on port {} (gRPC).", address.port());
+    println!("Listening on port {} (gRPC).", address.port());
+    shutdown::run_until_drained(
+        async {
+            if let Err(e) = server.await {
+                log::error!("gRPC server error: {}", e);
+            }
+        },
+        shutdown_started_receiver,
+        server_config.drain_timeout(),
+    )
+    .await;
+    return Ok(());
+}
+
+/// Implementation of 'MovieService'.
+pub struct MovieGrpcService {
+    db: DB,
+}
+
+#[tonic::async_trait]
+impl MovieService for MovieGrpcService {
+    type StreamMoviesStream =
+        Pin<Box<dyn Stream<Item = std::result::Result<proto::Movie, Status>> + Send>>;
+
+    async fn create_movie(
+        &self,
+        request: Request<proto::CreateMovieRequest>,
+    ) -> std::result::Result<Response<proto::Movie>, Status> {
+        let create_movie_request =
+            request_response_structs::CreateMovieRequest::try_from(request.into_inner())?;
+        create_movie_request
+            .validate()
+            .map_err(|e| to_status(BoxOfficeError::ValidationError(e)))?;
+        handler::validate_create_movie_request(&create_movie_request, &self.db)
+            .await
+            .map_err(to_status)?;
+        self.db
+            .create_movie(&create_movie_request)
+            .await
+            .map_err(to_status)?;
+        let created_movie = self
+            .db
+            .get_movie_by_id(&create_movie_request.movie_id.to_string())
+            .await
+            .map_err(to_status)?;
+        log::info!("Movie with Movie ID {} created.", created_movie.movie_id);
+        return Ok(Response::new(created_movie.into()));
+    }
+
+    async fn update_movie(
+        &self,
+        request: Request<proto::UpdateMovieRequest>,
+    ) -> std::result::Result<Response<proto::Movie>, Status> {
+        let request = request.into_inner();
+        let id = request.movie_id.to_string();
+        let update_movie_request = request_response_structs::UpdateMovieRequest::try_from(request)?;
+        update_movie_request
+            .validate()
+            .map_err(|e| to_status(BoxOfficeError::ValidationError(e)))?;
+        handler::validate_update_movie_id(&id, &self.db)
+            .await
+            .map_err(to_status)?;
+        let number_of_movies_updated = self
+            .db
+            .update_movie(&id, &update_movie_request)
+            .await
+            .map_err(to_status)?;
+        if number_of_movies_updated != 1 {
+            return Err(Status::internal(crate::constants::DB_OPERATION_FAILED));
+        }
+        let updated_movie = self.db.get_movie_by_id(&id).await.map_err(to_status)?;
+        log::info!("Movie with Movie ID {} updated.", id);
+        return Ok(Response::new(updated_movie.into()));
+    }
+
+    async fn delete_movie(
+        &self,
+        request: Request<proto::DeleteMovieRequest>,
+    ) -> std::result::Result<Response<proto::DeleteMovieResponse>, Status> {
+        let movie_id = request.into_inner().movie_id;
+        let id = movie_id.to_string();
+        handler::validate_delete_movie_id(&id, &self.db)
+            .await
+            .map_err(to_status)?;
+        let number_of_records_deleted = self.db.delete_movie(&id).await.map_err(to_status)?;
+        if number_of_records_deleted != 1 {
+            return Err(Status::internal(crate::constants::DB_OPERATION_FAILED));
+        }
+        log::info!("Movie with Movie ID {} deleted.", id);
+        return Ok(Response::new(proto::DeleteMovieResponse { movie_id }));
+    }
+
+    async fn get_movie(
+        &self,
+        request: Request<proto::GetMovieRequest>,
+    ) -> std::result::Result<Response<proto::Movie>, Status> {
+        let id = request.into_inner().movie_id.to_string();
+        let is_exist = self.db.check_movie_exist(&id).await.map_err(to_status)?;
+        if !*is_exist {
+            return Err(to_status(BoxOfficeError::InvalidDocumentError(id)));
+        }
+        let movie = self.db.get_movie_by_id(&id).await.map_err(to_status)?;
+        return Ok(Response::new(movie.into()));
+    }
+
+    async fn list_movies(
+        &self,
+        request: Request<proto::ListMoviesRequest>,
+    ) -> std::result::Result<Response<proto::ListMoviesResponse>, Status> {
+        let request = request.into_inner();
+        let fetched_movies = self.db.read_movies().await.map_err(to_status)?;
+        let total_count = fetched_movies.len() as u32;
+        let limit = match request.limit {
+            0 => usize::MAX,
+            limit => limit as usize,
+        };
+        let movies = fetched_movies
+            .into_iter()
+            .skip(request.offset as usize)
+            .take(limit)
+            .map(proto::Movie::from)
+            .collect();
+        return Ok(Response::new(proto::ListMoviesResponse {
+            movies,
+            total_count,
+        }));
+    }
+
+    async fn stream_movies(
+        &self,
+        _request: Request<proto::StreamMoviesRequest>,
+    ) -> std::result::Result<Response<Self::StreamMoviesStream>, Status> {
+        let movies = self.db.stream_movies().await.map_err(to_status)?;
+        let movies = movies.map(|movie| movie.map(proto::Movie::from).map_err(to_status));
+        return Ok(Response::new(Box::pin(movies)));
+    }
+}
+
+impl From<Movie> for proto::Movie {
+    fn from(movie: Movie) -> Self {
+        Self {
+            id: movie.id,
+            movie_id: movie.movie_id as u32,
+            title: movie.title,
+            production_house: movie.production_house,
+            year_of_release: movie.year_of_release as u32,
+            budget_crores: movie.budget_crores.to_string(),
+            collection_crores: movie.collection_crores.to_string(),
+            verdict: movie.verdict,
+            release_date: movie.release_date.unwrap_or_default(),
+            status: movie.status.to_string(),
+            verdict_provisional: movie.verdict_provisional,
+            production_house_id: movie.production_house_id.unwrap_or_default(),
+            genres: movie.metadata.genres,
+            languages: movie.metadata.languages,
+            certification: movie.metadata.certification.unwrap_or_default(),
+            runtime_minutes: movie
+                .metadata
+                .runtime_minutes
+                .map(u32::from)
+                .unwrap_or_default(),
+            tags: movie.metadata.tags,
+        }
+    }
+}
+
+impl TryFrom<proto::CreateMovieRequest> for request_response_structs::CreateMovieRequest {
+    type Error = Status;
+
+    fn try_from(request: proto::CreateMovieRequest) -> std::result::Result<Self, Status> {
+        Ok(Self {
+            movie_id: narrow("movie_id", request.movie_id)?,
+            title: request.title,
+            production_house: request.production_house,
+            year_of_release: narrow("year_of_release", request.year_of_release)?,
+            release_date: non_empty(request.release_date),
+            status: parse_status(&request.status)?,
+            budget_crores: parse_decimal("budget_crores", &request.budget_crores)?,
+            collection_crores: parse_decimal("collection_crores", &request.collection_crores)?,
+            // Amounts are INR crores.
+            currency: None,
+            unit: None,
+            exchange_rate_date: None,
+            // Metadata is set through REST and GraphQL, it is kept on update.
+            metadata: None,
+        })
+    }
+}
+
+impl TryFrom<proto::UpdateMovieRequest> for request_response_structs::UpdateMovieRequest {
+    type Error = Status;
+
+    fn try_from(request: proto::UpdateMovieRequest) -> std::result::Result<Self, Status> {
+        Ok(Self {
+            title: request.title,
+            production_house: request.production_house,
+            year_of_release: narrow("year_of_release", request.year_of_release)?,
+            release_date: non_empty(request.release_date),
+            status: parse_status(&request.status)?,
+            budget_crores: parse_decimal("budget_crores", &request.budget_crores)?,
+            collection_crores: parse_decimal("collection_crores", &request.collection_crores)?,
+            // Amounts are INR crores.
+            currency: None,
+            unit: None,
+            exchange_rate_date: None,
+            // Metadata is set through REST and GraphQL, it is kept on update.
+            metadata: None,
+        })
+    }
+}
+
+/// Converts protobuf integer to the narrower integer used by requests.
+fn narrow<T: TryFrom<u32>>(field: &str, value: u32) -> std::result::Result<T, Status> {
+    T::try_from(value).map_err(|_| Status::invalid_argument(format!("{}: out of range", field)))
+}
+
+/// Empty protobuf string stands for a value which is not given.
+fn non_empty(value: String) -> Option<String> {
+    match value.is_empty() {
+        true => None,
+        false => Some(value),
+    }
+}
+
+fn parse_status(status: &str) -> std::result::Result<Option<ReleaseStatus>, Status> {
+    if status.is_empty() {
+        return Ok(None);
+    }
+    return ReleaseStatus::from_str(status).map(Some).map_err(|_| {
+        Status::invalid_argument(format!("status: unknown release status '{}'", status))
+    });
+}
+
+fn parse_decimal(field: &str, value: &str) -> std::result::Result<Decimal, Status> {
+    Decimal::from_str(value).map_err(|e| Status::invalid_argument(format!("{}: {}", field, e)))
+}
+
+/// Maps application error to gRPC status, in line with HTTP status codes of 'handle_rejection'.
+fn to_status(error: BoxOfficeError) -> Status {
+    let message = error.to_string();
+    log::error!("{}", message);
+    match error {
+        BoxOfficeError::ValidationError(_)
+        | BoxOfficeError::InvalidMovieIDError(_)
+        | BoxOfficeError::InvalidUpdateMovieRequestError(_)
+        | BoxOfficeError::InvalidDeleteMovieRequestError(_)
+        | BoxOfficeError::InvalidMetadataError(_) => Status::invalid_argument(message),
+        BoxOfficeError::InvalidStatusTransitionError(_)
+        | BoxOfficeError::ReleaseLifecycleError(_) => Status::failed_precondition(message),
+        BoxOfficeError::InvalidDocumentError(_) => Status::not_found(message),
+        BoxOfficeError::InvalidCreateDocumentError(_) => Status::already_exists(message),
+        BoxOfficeError::ErrorinVerdictCalculation(_)
+        | BoxOfficeError::ExchangeRateNotFoundError(_)
+        | BoxOfficeError::AmountConversionError(_)
+        | BoxOfficeError::CpiNotFoundError(_)
+        | BoxOfficeError::InflationAdjustmentError(_) => Status::failed_precondition(message),
+        BoxOfficeError::MongoDBError(_) => Status::unavailable(message),
+        _ => Status::internal(message),
+    }
+}
@@ -1,25 +1,63 @@
+use crate::business_layer::{
+    include_financials, sort_movies, territory_breakdown, FinancialFields,
+};
+use crate::collection_run;
+use crate::constants::*;
 use crate::error::BoxOfficeError;
-use crate::{db_layer::*, request_response_structs::*, response::*, WebResult};
+use crate::events::{self, EventFilter};
+use crate::franchise;
+use crate::graphql::BoxOfficeSchema;
+use crate::metadata;
+use crate::negotiation::{self, RequestFormat, ResponseFormat};
+use crate::people;
+use crate::production_house;
+use crate::search::SearchIndex;
+use crate::shutdown::Readiness;
+use crate::webhooks;
+use crate::{
+    db_layer::*,
+    model::{
+        AmountUnit, CollectionEntry, Credit, Franchise, Movie, MovieRelation, MovieRelations,
+        Person, ProductionHouse, ProductionHouseTrackRecord, Role, SearchResults,
+        TerritoryCollection,
+    },
+    request_response_structs::*,
+    response::*,
+    WebResult,
+};
 /// Functions to get REST Requests and return response.
 use bytes::Buf;
 type Result<T> = std::result::Result<T, BoxOfficeError>;
+use std::collections::BTreeMap;
+use std::sync::Arc;
 use validator::Validate;
-use warp::{http::StatusCode, reject, reply::json, Reply};
+use warp::{
+    http::{header::LOCATION, StatusCode},
+    reject, Reply,
+};
 
 /// Handles Create Movie (POST) request.
-pub async fn create_movie_handler(buf: impl Buf, db: DB) -> WebResult<impl Reply> {
+#[tracing::instrument(skip_all)]
+pub async fn create_movie_handler(
+    query: MovieQuery,
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
     // Deserialize input and map to CreateMovieRequest.
-    let des = &mut serde_json::Deserializer::from_reader(buf.reader());
-    let create_movie_request: CreateMovieRequest = serde_path_to_error::deserialize(des)
-        .map_err(|e| reject::custom(BoxOfficeError::BadCreateMovieRequestError(e.to_string())))?;
+    let create_movie_request: CreateMovieRequest =
+        negotiation::deserialize_body(buf, request_format)
+            .map_err(|e| reject::custom(BoxOfficeError::BadCreateMovieRequestError(e)))?;
 
     // Validate request fields.
     create_movie_request
         .validate()
         .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+    let movie_view = movie_view(&query, &db).map_err(reject::custom)?;
 
     // Business Validation
-    let _validation_result = validate_create_movie_request(&create_movie_request, &db)
+    validate_create_movie_request(&create_movie_request, &db)
         .await
         .map_err(|e| {
             reject::custom(BoxOfficeError::InvalidFieldInCreateMovieRequestError(
@@ -28,54 +66,68 @@ pub async fn create_movie_handler(buf: impl Buf, db: DB) -> WebResult<impl Reply
         })?;
 
     // Create Movie.
-    let _create_movie_result = db
-        .create_movie(&create_movie_request)
+    db.create_movie(&create_movie_request)
         .await
-        .map_err(|e| reject::custom(e))?;
+        .map_err(reject::custom)?;
 
     // Get created Movie.
     let created_movie = db
-        .get_movie_by_id(&create_movie_request.movie_id.to_string().as_str())
+        .get_movie_by_id(create_movie_request.movie_id.to_string().as_str())
         .await
-        .map_err(|e| reject::custom(e))?;
+        .map_err(reject::custom)?;
+
+    log::info!("Movie with Movie ID {} created.", created_movie.movie_id);
 
     // Populate Response
-    let create_movie_response = populate_create_movie_response(created_movie);
+    let create_movie_response =
+        populate_create_movie_response(movie_view.apply(created_movie).map_err(reject::custom)?);
 
     // Return Response.
-    return Ok(warp::reply::with_status(
-        json(&create_movie_response),
-        StatusCode::CREATED,
-    ));
+    return negotiation::reply(&create_movie_response, format, StatusCode::CREATED);
 }
 
 /// Handles Read Movie (GET) request.
-pub async fn read_movies_handler(db: DB) -> WebResult<impl Reply> {
+#[tracing::instrument(skip_all)]
+pub async fn read_movies_handler(
+    query: MovieQuery,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let movie_view = movie_view(&query, &db).map_err(reject::custom)?;
     // Fetch Movies.
-    let fetched_movies = db.read_movies().await.map_err(|e| reject::custom(e))?;
+    let fetched_movies = db.read_movies_with(&query).await.map_err(reject::custom)?;
+    let fetched_movies = movie_view
+        .apply_all(fetched_movies)
+        .map_err(reject::custom)?;
     // Populate Response
     let read_movies_response = populate_read_movies_response(fetched_movies);
     // Return Response.
-    return Ok(warp::reply::with_status(
-        json(&read_movies_response),
-        StatusCode::OK,
-    ));
+    return negotiation::reply(&read_movies_response, format, StatusCode::OK);
 }
 
 /// Handles Update Movie (PUT) request.
-pub async fn update_movie_handler(id: String, buf: impl Buf, db: DB) -> WebResult<impl Reply> {
+#[tracing::instrument(skip(buf, db))]
+pub async fn update_movie_handler(
+    id: String,
+    query: MovieQuery,
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
     // Deserialize input and map to UpdateMovieRequest.
-    let des = &mut serde_json::Deserializer::from_reader(buf.reader());
-    let update_movie_request: UpdateMovieRequest = serde_path_to_error::deserialize(des)
-        .map_err(|e| reject::custom(BoxOfficeError::BadUpdateMovieRequestError(e.to_string())))?;
+    let update_movie_request: UpdateMovieRequest =
+        negotiation::deserialize_body(buf, request_format)
+            .map_err(|e| reject::custom(BoxOfficeError::BadUpdateMovieRequestError(e)))?;
 
     // Validate request fields.
     update_movie_request
         .validate()
         .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+    let movie_view = movie_view(&query, &db).map_err(reject::custom)?;
 
     // Business Validation
-    let _validation_result = validate_update_movie_id(&id, &db)
+    validate_update_movie_id(&id, &db)
         .await
         .map_err(|e| reject::custom(BoxOfficeError::BadUpdateMovieRequestError(e.to_string())))?;
 
@@ -83,66 +135,1450 @@ pub async fn update_movie_handler(id: String, buf: impl Buf, db: DB) -> WebResul
     let number_of_movies_updated = db
         .update_movie(&id, &update_movie_request)
         .await
-        .map_err(|e| reject::custom(e))?;
+        .map_err(reject::custom)?;
     if number_of_movies_updated == 1 {
         let updated_movie = db
-            .get_movie_by_id(&id.to_string().as_str())
+            .get_movie_by_id(id.to_string().as_str())
             .await
-            .map_err(|e| reject::custom(e))?;
+            .map_err(reject::custom)?;
+
+        log::info!("Movie with Movie ID {} updated.", id);
 
         // Populate Movie Response and Returns.
-        let update_movie_response = populate_update_movie_response(updated_movie);
-        return Ok(warp::reply::with_status(
-            json(&update_movie_response),
-            StatusCode::OK,
-        ));
+        let update_movie_response = populate_update_movie_response(
+            movie_view.apply(updated_movie).map_err(reject::custom)?,
+        );
+        return negotiation::reply(&update_movie_response, format, StatusCode::OK);
     } else {
+        log::error!("{}", DB_OPERATION_FAILED);
         let db_operation_failed_error_response = populate_db_operation_failed_error_response();
-        return Ok(warp::reply::with_status(
-            json(&db_operation_failed_error_response),
+        return negotiation::reply(
+            &db_operation_failed_error_response,
+            format,
             StatusCode::INTERNAL_SERVER_ERROR,
-        ));
+        );
     }
 }
 
 /// Handles Delete Movie (DELETE) request.
-pub async fn delete_movie_handler(id: String, db: DB) -> WebResult<impl Reply> {
+#[tracing::instrument(skip(db))]
+pub async fn delete_movie_handler(
+    id: String,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
     // Business validation.
-    let _validation_result = validate_delete_movie_id(&id, &db)
+    validate_delete_movie_id(&id, &db)
         .await
         .map_err(|e| reject::custom(BoxOfficeError::BadDeleteMovieRequestError(e.to_string())))?;
 
     // Delete Movie and Returns Response.
-    let number_of_records_deleted = db.delete_movie(&id).await.map_err(|e| reject::custom(e))?;
+    let number_of_records_deleted = db.delete_movie(&id).await.map_err(reject::custom)?;
     if number_of_records_deleted == 1 {
-        let delete_movie_response = populate_delete_movie_response(&id.as_str());
-        return Ok(warp::reply::with_status(
-            json(&delete_movie_response),
-            StatusCode::OK,
-        ));
+        log::info!("Movie with Movie ID {} deleted.", id);
+        let delete_movie_response = populate_delete_movie_response(id.as_str());
+        return negotiation::reply(&delete_movie_response, format, StatusCode::OK);
     } else {
+        log::error!("{}", DB_OPERATION_FAILED);
         let db_operation_failed_error_response = populate_db_operation_failed_error_response();
-        return Ok(warp::reply::with_status(
-            json(&db_operation_failed_error_response),
+        return negotiation::reply(
+            &db_operation_failed_error_response,
+            format,
             StatusCode::INTERNAL_SERVER_ERROR,
+        );
+    }
+}
+
+/// Handles Create Movie (POST /boxoffice/v2/movies) request.
+/// Returns 201 with the created movie and its location, 409 if Movie Id is taken.
+#[tracing::instrument(skip_all)]
+pub async fn create_movie_v2_handler(
+    query: MovieQuery,
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to CreateMovieRequest.
+    let create_movie_request: CreateMovieRequest =
+        negotiation::deserialize_body(buf, request_format)
+            .map_err(|e| reject::custom(BoxOfficeError::BadCreateMovieRequestError(e)))?;
+
+    // Validate request fields.
+    create_movie_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+    let movie_view = movie_view(&query, &db).map_err(reject::custom)?;
+
+    // Business Validation
+    validate_create_movie_request(&create_movie_request, &db)
+        .await
+        .map_err(|e| match e {
+            BoxOfficeError::InvalidCreateDocumentError(id) => {
+                reject::custom(BoxOfficeError::MovieAlreadyExistsError(id))
+            }
+            e => reject::custom(e),
+        })?;
+
+    // Create Movie.
+    db.create_movie(&create_movie_request)
+        .await
+        .map_err(reject::custom)?;
+
+    // Get created Movie.
+    let created_movie = find_movie(&create_movie_request.movie_id.to_string(), &db)
+        .await
+        .map_err(reject::custom)?;
+
+    log::info!("Movie with Movie ID {} created.", created_movie.movie_id);
+
+    // Return created Movie with its location.
+    let location = format!("{}/{}", MOVIES_V2_PATH, created_movie.movie_id);
+    let created_movie = movie_view.apply(created_movie).map_err(reject::custom)?;
+    let reply = negotiation::reply(&created_movie, format, StatusCode::CREATED)?;
+    return Ok(warp::reply::with_header(reply, LOCATION, location));
+}
+
+/// Handles Read Movies (GET /boxoffice/v2/movies) request.
+#[tracing::instrument(skip_all)]
+pub async fn read_movies_v2_handler(
+    query: MovieQuery,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let movie_view = movie_view(&query, &db).map_err(reject::custom)?;
+    let fetched_movies = db.read_movies_with(&query).await.map_err(reject::custom)?;
+    let fetched_movies = movie_view
+        .apply_all(fetched_movies)
+        .map_err(reject::custom)?;
+    return negotiation::reply(&fetched_movies, format, StatusCode::OK);
+}
+
+/// Handles Read Movie Facets (GET /boxoffice/v2/movies/facets) request.
+/// Facets count movies kept by the filters of the query.
+#[tracing::instrument(skip_all)]
+pub async fn read_movie_facets_handler(
+    query: MovieQuery,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let movie_view = movie_view(&query, &db).map_err(reject::custom)?;
+    let fetched_movies = db.read_movies_with(&query).await.map_err(reject::custom)?;
+    let fetched_movies = movie_view
+        .apply_all(fetched_movies)
+        .map_err(reject::custom)?;
+    let facets = metadata::facets(&fetched_movies);
+    return negotiation::reply(&facets, format, StatusCode::OK);
+}
+
+/// Handles Get Movie (GET /boxoffice/v2/movies/{id}) request.
+#[tracing::instrument(skip(db))]
+pub async fn get_movie_v2_handler(
+    id: String,
+    query: MovieQuery,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let movie_view = movie_view(&query, &db).map_err(reject::custom)?;
+    let movie = find_movie(&id, &db).await.map_err(reject::custom)?;
+    let movie = movie_view.apply(movie).map_err(reject::custom)?;
+    return negotiation::reply(&movie, format, StatusCode::OK);
+}
+
+/// Handles Update Movie (PUT /boxoffice/v2/movies/{id}) request.
+/// Replaces all fields of the movie, verdict is recalculated.
+#[tracing::instrument(skip(buf, db))]
+pub async fn update_movie_v2_handler(
+    id: String,
+    query: MovieQuery,
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to UpdateMovieRequest.
+    let update_movie_request: UpdateMovieRequest =
+        negotiation::deserialize_body(buf, request_format)
+            .map_err(|e| reject::custom(BoxOfficeError::BadUpdateMovieRequestError(e)))?;
+
+    // Validate request fields.
+    update_movie_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    let movie_view = movie_view(&query, &db).map_err(reject::custom)?;
+
+    let movie = find_movie(&id, &db).await.map_err(reject::custom)?;
+    let updated_movie = update_movie(&movie, &update_movie_request, &db)
+        .await
+        .map_err(reject::custom)?;
+    let updated_movie = movie_view.apply(updated_movie).map_err(reject::custom)?;
+    return negotiation::reply(&updated_movie, format, StatusCode::OK);
+}
+
+/// Handles Patch Movie (PATCH /boxoffice/v2/movies/{id}) request.
+/// Changes only given fields of the movie, verdict is recalculated.
+#[tracing::instrument(skip(buf, db))]
+pub async fn patch_movie_v2_handler(
+    id: String,
+    query: MovieQuery,
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to PatchMovieRequest.
+    let patch_movie_request: PatchMovieRequest = negotiation::deserialize_body(buf, request_format)
+        .map_err(|e| reject::custom(BoxOfficeError::BadUpdateMovieRequestError(e)))?;
+    let movie_view = movie_view(&query, &db).map_err(reject::custom)?;
+
+    // Apply patch to the movie and validate resulting fields.
+    let movie = find_movie(&id, &db).await.map_err(reject::custom)?;
+    let update_movie_request = patch_movie_request
+        .apply_to(&movie)
+        .map_err(reject::custom)?;
+    update_movie_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    let updated_movie = update_movie(&movie, &update_movie_request, &db)
+        .await
+        .map_err(reject::custom)?;
+    let updated_movie = movie_view.apply(updated_movie).map_err(reject::custom)?;
+    return negotiation::reply(&updated_movie, format, StatusCode::OK);
+}
+
+/// Handles Delete Movie (DELETE /boxoffice/v2/movies/{id}) request.
+/// Returns 204 without body.
+#[tracing::instrument(skip(db))]
+pub async fn delete_movie_v2_handler(id: String, db: DB) -> WebResult<impl Reply> {
+    let movie = find_movie(&id, &db).await.map_err(reject::custom)?;
+    let number_of_records_deleted = db
+        .delete_movie(&movie.movie_id.to_string())
+        .await
+        .map_err(reject::custom)?;
+    if number_of_records_deleted != 1 {
+        // Deleted by another request in the meantime.
+        return Err(reject::custom(BoxOfficeError::MovieNotFoundError(id)));
+    }
+    log::info!("Movie with Movie ID {} deleted.", movie.movie_id);
+    return Ok(StatusCode::NO_CONTENT);
+}
+
+/// Handles Create Collection Entry (POST /boxoffice/v2/movies/{id}/collections) request.
+/// Entry is rolled up into collection of the movie, whose verdict is recalculated.
+/// Returns 201 with the entry, 409 if an entry of the same period and territory overlaps it,
+/// 422 if the movie is not released or its run is closed.
+#[tracing::instrument(skip(buf, db))]
+pub async fn create_collection_entry_handler(
+    id: String,
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to CreateCollectionEntryRequest.
+    let create_collection_entry_request: CreateCollectionEntryRequest =
+        negotiation::deserialize_body(buf, request_format)
+            .map_err(|e| reject::custom(BoxOfficeError::BadCollectionEntryRequestError(e)))?;
+
+    // Validate request fields.
+    create_collection_entry_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+    collection_run::day_number(&create_collection_entry_request.date).map_err(reject::custom)?;
+
+    // Create Collection Entry and roll it up into collection of the movie, rejected when the movie is not
+    // collecting or the entry overlaps an entry of the same period and territory.
+    let movie = find_movie(&id, &db).await.map_err(reject::custom)?;
+    let created_entry = db
+        .create_collection_entry(movie.movie_id, &create_collection_entry_request)
+        .await
+        .map_err(reject::custom)?;
+    log::info!(
+        "Collection entry {} created for Movie ID {}.",
+        created_entry.id,
+        movie.movie_id
+    );
+
+    return negotiation::reply(&created_entry, format, StatusCode::CREATED);
+}
+
+/// Handles Read Collection Entries (GET /boxoffice/v2/movies/{id}/collections) request.
+/// Returns entries matching the filter, oldest first.
+#[tracing::instrument(skip(db))]
+pub async fn read_collection_entries_handler(
+    id: String,
+    filter: CollectionEntryFilter,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let movie = find_movie(&id, &db).await.map_err(reject::custom)?;
+    let collection_entries: Vec<CollectionEntry> = db
+        .read_collection_entries(movie.movie_id)
+        .await
+        .map_err(reject::custom)?
+        .into_iter()
+        .filter(|entry| filter.matches(entry))
+        .collect();
+    return negotiation::reply(&collection_entries, format, StatusCode::OK);
+}
+
+/// Handles Read Collection Run (GET /boxoffice/v2/movies/{id}/collections/summary) request.
+/// Returns time series, opening weekend and week-over-week drops of the run.
+#[tracing::instrument(skip(db))]
+pub async fn read_collection_run_handler(
+    id: String,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let movie = find_movie(&id, &db).await.map_err(reject::custom)?;
+    let collection_entries = db
+        .read_collection_entries(movie.movie_id)
+        .await
+        .map_err(reject::custom)?;
+    let collection_run = collection_run::collection_run(movie.movie_id, &collection_entries)
+        .map_err(reject::custom)?;
+    return negotiation::reply(&collection_run, format, StatusCode::OK);
+}
+
+/// Handles Delete Collection Entry (DELETE /boxoffice/v2/movies/{id}/collections/{entryId}) request.
+/// Remaining entries are rolled up into collection of the movie. Returns 204 without body,
+/// 422 once the run of the movie is closed.
+#[tracing::instrument(skip(db))]
+pub async fn delete_collection_entry_handler(
+    id: String,
+    entry_id: String,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let movie = find_movie(&id, &db).await.map_err(reject::custom)?;
+    let number_of_entries_deleted = db
+        .delete_collection_entry(movie.movie_id, &entry_id)
+        .await
+        .map_err(reject::custom)?;
+    if number_of_entries_deleted != 1 {
+        return Err(reject::custom(
+            BoxOfficeError::CollectionEntryNotFoundError(entry_id),
         ));
     }
+    log::info!(
+        "Collection entry {} of Movie ID {} deleted.",
+        entry_id,
+        movie.movie_id
+    );
+    return Ok(StatusCode::NO_CONTENT);
+}
+
+/// Handles Read Territories (GET /boxoffice/v2/movies/{id}/territories) request.
+/// Returns territory breakdown of the movie, empty if it has none.
+#[tracing::instrument(skip(db))]
+pub async fn read_territories_handler(
+    id: String,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let movie = find_movie(&id, &db).await.map_err(reject::custom)?;
+    let territories: Vec<TerritoryCollection> = movie.territories.unwrap_or_default();
+    return negotiation::reply(&territories, format, StatusCode::OK);
+}
+
+/// Handles Update Territories (PUT /boxoffice/v2/movies/{id}/territories) request.
+/// Replaces territory breakdown of the movie, an empty list removes it.
+/// Returns the breakdown with verdict of every territory, 422 if it does not add up to the movie totals.
+#[tracing::instrument(skip(buf, db))]
+pub async fn update_territories_handler(
+    id: String,
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to UpdateTerritoriesRequest.
+    let update_territories_request: UpdateTerritoriesRequest =
+        negotiation::deserialize_body(buf, request_format)
+            .map_err(|e| reject::custom(BoxOfficeError::BadTerritoriesRequestError(e)))?;
+
+    // Validate request fields.
+    update_territories_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    // Business Validation
+    let movie = find_movie(&id, &db).await.map_err(reject::custom)?;
+    let territories = territory_breakdown(&movie, &update_territories_request.territories)
+        .map_err(reject::custom)?;
+
+    // Update Territories.
+    db.update_territories(&movie, &territories)
+        .await
+        .map_err(reject::custom)?;
+    log::info!(
+        "Territory breakdown of Movie ID {} updated with {} territories.",
+        movie.movie_id,
+        territories.len()
+    );
+
+    return negotiation::reply(&territories, format, StatusCode::OK);
+}
+
+/// Handles Read Credits (GET /boxoffice/v2/movies/{id}/credits) request.
+/// Returns cast and crew of the movie, empty if it has none.
+#[tracing::instrument(skip(db))]
+pub async fn read_credits_handler(
+    id: String,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let movie = find_movie(&id, &db).await.map_err(reject::custom)?;
+    let credits: Vec<Credit> = movie.credits.unwrap_or_default();
+    return negotiation::reply(&credits, format, StatusCode::OK);
+}
+
+/// Handles Update Credits (PUT /boxoffice/v2/movies/{id}/credits) request.
+/// Replaces cast and crew of the movie, an empty list removes them.
+/// Returns the credits with names of the people, 422 if a credit refers to an unknown person.
+#[tracing::instrument(skip(buf, db))]
+pub async fn update_credits_handler(
+    id: String,
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to UpdateCreditsRequest.
+    let update_credits_request: UpdateCreditsRequest =
+        negotiation::deserialize_body(buf, request_format)
+            .map_err(|e| reject::custom(BoxOfficeError::BadCreditsRequestError(e)))?;
+
+    // Validate request fields.
+    update_credits_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    // Business Validation
+    let movie = find_movie(&id, &db).await.map_err(reject::custom)?;
+    let mut credits: Vec<Credit> = Vec::new();
+    for credit_request in &update_credits_request.credits {
+        let person = db
+            .get_person(&credit_request.person_id)
+            .await
+            .map_err(reject::custom)?
+            .ok_or_else(|| {
+                reject::custom(BoxOfficeError::UnknownCreditedPersonError(
+                    credit_request.person_id.clone(),
+                ))
+            })?;
+        credits.push(Credit {
+            person_id: person.id,
+            name: person.name,
+            role: credit_request.role,
+            character: credit_request.character.clone(),
+        });
+    }
+
+    // Update Credits.
+    db.update_credits(&movie, &credits)
+        .await
+        .map_err(reject::custom)?;
+    log::info!(
+        "Credits of Movie ID {} updated with {} credits.",
+        movie.movie_id,
+        credits.len()
+    );
+
+    return negotiation::reply(&credits, format, StatusCode::OK);
+}
+
+/// Handles Read Relations (GET /boxoffice/v2/movies/{id}/relations) request.
+/// Returns movies the movie is related to and movies related to it, e.g. its sequels.
+#[tracing::instrument(skip(db))]
+pub async fn read_relations_handler(
+    id: String,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let movie = find_movie(&id, &db).await.map_err(reject::custom)?;
+    let relations = movie_relations(&movie, &db).await.map_err(reject::custom)?;
+    return negotiation::reply(&relations, format, StatusCode::OK);
+}
+
+/// Handles Update Relations (PUT /boxoffice/v2/movies/{id}/relations) request.
+/// Replaces relations of the movie to other movies, an empty list removes them.
+/// Returns relations of the movie, 422 if a relation refers to the movie itself or to an unknown movie.
+#[tracing::instrument(skip(buf, db))]
+pub async fn update_relations_handler(
+    id: String,
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to UpdateRelationsRequest.
+    let update_relations_request: UpdateRelationsRequest =
+        negotiation::deserialize_body(buf, request_format)
+            .map_err(|e| reject::custom(BoxOfficeError::BadRelationsRequestError(e)))?;
+
+    // Validate request fields.
+    update_relations_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    // Business Validation
+    let movie = find_movie(&id, &db).await.map_err(reject::custom)?;
+    let relations: Vec<MovieRelation> = update_relations_request
+        .relations
+        .iter()
+        .map(|relation| MovieRelation {
+            kind: relation.kind,
+            movie_id: relation.movie_id,
+        })
+        .collect();
+    if relations
+        .iter()
+        .any(|relation| relation.movie_id == movie.movie_id)
+    {
+        return Err(reject::custom(BoxOfficeError::InvalidMovieRelationError(
+            format!("movie {} can not be related to itself", movie.movie_id),
+        )));
+    }
+    let related_movie_ids: Vec<u8> = relations.iter().map(|relation| relation.movie_id).collect();
+    let related_movies = db
+        .read_movies_by_ids(&related_movie_ids)
+        .await
+        .map_err(reject::custom)?;
+    if let Some(unknown_movie_id) = related_movie_ids.iter().find(|movie_id| {
+        !related_movies
+            .iter()
+            .any(|related_movie| related_movie.movie_id == **movie_id)
+    }) {
+        return Err(reject::custom(BoxOfficeError::InvalidMovieRelationError(
+            format!("movie {} does not exist", unknown_movie_id),
+        )));
+    }
+
+    // Update Relations.
+    let updated_movie = db
+        .update_relations(&movie, &relations)
+        .await
+        .map_err(reject::custom)?;
+    log::info!(
+        "Relations of Movie ID {} updated with {} relations.",
+        movie.movie_id,
+        relations.len()
+    );
+
+    let relations = movie_relations(&updated_movie, &db)
+        .await
+        .map_err(reject::custom)?;
+    return negotiation::reply(&relations, format, StatusCode::OK);
+}
+
+/// Handles Create Person (POST /boxoffice/v2/people) request.
+/// Returns 201 with the created person and its location.
+#[tracing::instrument(skip_all)]
+pub async fn create_person_handler(
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to PersonRequest.
+    let person_request: PersonRequest = negotiation::deserialize_body(buf, request_format)
+        .map_err(|e| reject::custom(BoxOfficeError::BadPersonRequestError(e)))?;
+
+    // Validate request fields.
+    person_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    // Create Person.
+    let created_person = db
+        .create_person(&person_request)
+        .await
+        .map_err(reject::custom)?;
+    log::info!("Person with ID {} created.", created_person.id);
+
+    // Return created Person with its location.
+    let location = format!("{}/{}", PEOPLE_V2_PATH, created_person.id);
+    let reply = negotiation::reply(&created_person, format, StatusCode::CREATED)?;
+    return Ok(warp::reply::with_header(reply, LOCATION, location));
+}
+
+/// Handles Read People (GET /boxoffice/v2/people) request.
+#[tracing::instrument(skip_all)]
+pub async fn read_people_handler(format: ResponseFormat, db: DB) -> WebResult<impl Reply> {
+    let fetched_people = db.read_people().await.map_err(reject::custom)?;
+    return negotiation::reply(&fetched_people, format, StatusCode::OK);
+}
+
+/// Handles Get Person (GET /boxoffice/v2/people/{id}) request.
+#[tracing::instrument(skip(db))]
+pub async fn get_person_handler(
+    id: String,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let person = find_person(&id, &db).await.map_err(reject::custom)?;
+    return negotiation::reply(&person, format, StatusCode::OK);
+}
+
+/// Handles Update Person (PUT /boxoffice/v2/people/{id}) request.
+/// Renames the person, credits of the person are renamed with it.
+#[tracing::instrument(skip(buf, db))]
+pub async fn update_person_handler(
+    id: String,
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to PersonRequest.
+    let person_request: PersonRequest = negotiation::deserialize_body(buf, request_format)
+        .map_err(|e| reject::custom(BoxOfficeError::BadPersonRequestError(e)))?;
+
+    // Validate request fields.
+    person_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    // Update Person.
+    let person = find_person(&id, &db).await.map_err(reject::custom)?;
+    let updated_person = db
+        .update_person(&person, &person_request)
+        .await
+        .map_err(reject::custom)?;
+    log::info!("Person with ID {} updated.", id);
+    return negotiation::reply(&updated_person, format, StatusCode::OK);
+}
+
+/// Handles Delete Person (DELETE /boxoffice/v2/people/{id}) request.
+/// Returns 204 without body, 409 if movies credit the person.
+#[tracing::instrument(skip(db))]
+pub async fn delete_person_handler(id: String, db: DB) -> WebResult<impl Reply> {
+    let number_of_people_deleted = db.delete_person(&id).await.map_err(reject::custom)?;
+    if number_of_people_deleted != 1 {
+        return Err(reject::custom(BoxOfficeError::PersonNotFoundError(id)));
+    }
+    log::info!("Person with ID {} deleted.", id);
+    return Ok(StatusCode::NO_CONTENT);
+}
+
+/// Handles Read Filmography (GET /boxoffice/v2/people/{id}/filmography) request.
+/// Returns movies crediting the person with their verdicts and success rate, limited to 'role' when given.
+#[tracing::instrument(skip(db))]
+pub async fn read_filmography_handler(
+    id: String,
+    query: FilmographyQuery,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let person = find_person(&id, &db).await.map_err(reject::custom)?;
+    let movies = db
+        .read_person_movies(&person.id)
+        .await
+        .map_err(reject::custom)?;
+    let filmography = people::filmography(&person, &movies, query.role).map_err(reject::custom)?;
+    return negotiation::reply(&filmography, format, StatusCode::OK);
+}
+
+/// Handles Read Roles (GET /boxoffice/v2/roles) request.
+/// Returns roles people can be credited in.
+#[tracing::instrument(skip_all)]
+pub async fn read_roles_handler(format: ResponseFormat) -> WebResult<impl Reply> {
+    let roles: Vec<Role> = people::ROLES.to_vec();
+    return negotiation::reply(&roles, format, StatusCode::OK);
+}
+
+/// Handles Create Franchise (POST /boxoffice/v2/franchises) request.
+/// Returns 201 with the created franchise and its location,
+/// 409 if a movie is an instalment of another franchise.
+#[tracing::instrument(skip_all)]
+pub async fn create_franchise_handler(
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to FranchiseRequest.
+    let franchise_request: FranchiseRequest = negotiation::deserialize_body(buf, request_format)
+        .map_err(|e| reject::custom(BoxOfficeError::BadFranchiseRequestError(e)))?;
+
+    // Validate request fields.
+    franchise_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    // Create Franchise.
+    let created_franchise = db
+        .create_franchise(&franchise_request)
+        .await
+        .map_err(reject::custom)?;
+    log::info!("Franchise with ID {} created.", created_franchise.id);
+
+    // Return created Franchise with its location.
+    let location = format!("{}/{}", FRANCHISES_V2_PATH, created_franchise.id);
+    let reply = negotiation::reply(&created_franchise, format, StatusCode::CREATED)?;
+    return Ok(warp::reply::with_header(reply, LOCATION, location));
+}
+
+/// Handles Read Franchises (GET /boxoffice/v2/franchises) request.
+#[tracing::instrument(skip_all)]
+pub async fn read_franchises_handler(format: ResponseFormat, db: DB) -> WebResult<impl Reply> {
+    let fetched_franchises = db.read_franchises().await.map_err(reject::custom)?;
+    return negotiation::reply(&fetched_franchises, format, StatusCode::OK);
+}
+
+/// Handles Get Franchise (GET /boxoffice/v2/franchises/{id}) request.
+#[tracing::instrument(skip(db))]
+pub async fn get_franchise_handler(
+    id: String,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let franchise = find_franchise(&id, &db).await.map_err(reject::custom)?;
+    return negotiation::reply(&franchise, format, StatusCode::OK);
+}
+
+/// Handles Update Franchise (PUT /boxoffice/v2/franchises/{id}) request.
+/// Replaces name and instalments of the franchise.
+#[tracing::instrument(skip(buf, db))]
+pub async fn update_franchise_handler(
+    id: String,
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to FranchiseRequest.
+    let franchise_request: FranchiseRequest = negotiation::deserialize_body(buf, request_format)
+        .map_err(|e| reject::custom(BoxOfficeError::BadFranchiseRequestError(e)))?;
+
+    // Validate request fields.
+    franchise_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    // Update Franchise.
+    let franchise = find_franchise(&id, &db).await.map_err(reject::custom)?;
+    let updated_franchise = db
+        .update_franchise(&franchise, &franchise_request)
+        .await
+        .map_err(reject::custom)?;
+    log::info!("Franchise with ID {} updated.", id);
+    return negotiation::reply(&updated_franchise, format, StatusCode::OK);
+}
+
+/// Handles Delete Franchise (DELETE /boxoffice/v2/franchises/{id}) request.
+/// Returns 204 without body, movies of the franchise are kept.
+#[tracing::instrument(skip(db))]
+pub async fn delete_franchise_handler(id: String, db: DB) -> WebResult<impl Reply> {
+    let number_of_franchises_deleted = db.delete_franchise(&id).await.map_err(reject::custom)?;
+    if number_of_franchises_deleted != 1 {
+        return Err(reject::custom(BoxOfficeError::FranchiseNotFoundError(id)));
+    }
+    log::info!("Franchise with ID {} deleted.", id);
+    return Ok(StatusCode::NO_CONTENT);
+}
+
+/// Handles Read Franchise Summary (GET /boxoffice/v2/franchises/{id}/summary) request.
+/// Returns combined budget, collection and verdict of the franchise with the trend over its instalments.
+#[tracing::instrument(skip(db))]
+pub async fn read_franchise_summary_handler(
+    id: String,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let franchise = find_franchise(&id, &db).await.map_err(reject::custom)?;
+    let movies = db
+        .read_movies_by_ids(&franchise.movie_ids)
+        .await
+        .map_err(reject::custom)?;
+    let summary = franchise::summary(&franchise, &movies).map_err(reject::custom)?;
+    return negotiation::reply(&summary, format, StatusCode::OK);
+}
+
+/// Handles Search (GET /boxoffice/v2/search) request.
+#[tracing::instrument(skip(db))]
+pub async fn search_handler(
+    query: SearchQuery,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Validate request fields.
+    query
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    let search_results = search_movies(&query, &db).await.map_err(reject::custom)?;
+    return negotiation::reply(&search_results, format, StatusCode::OK);
+}
+
+/// Handles Suggest (GET /boxoffice/v2/search/suggest) request.
+/// Returns titles, production houses and people completing the partly typed query.
+#[tracing::instrument(skip(db))]
+pub async fn suggest_handler(
+    query: SearchQuery,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Validate request fields.
+    query
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    let suggestions = movie_search_index(&db)
+        .await
+        .map_err(reject::custom)?
+        .suggest(&query.q, query.limit());
+    return negotiation::reply(&suggestions, format, StatusCode::OK);
+}
+
+/// Searches movies for Search Query, most relevant first.
+/// Movies found by full-text search are ranked by their text score together with in-process relevance.
+/// When full-text search finds no movie, all movies are searched with typo tolerance,
+/// so that misspelt and differently transliterated titles are found.
+pub async fn search_movies(query: &SearchQuery, db: &DB) -> Result<SearchResults> {
+    let text_hits = db.search_movies_text(&query.q, query.limit()).await?;
+    let fuzzy = text_hits.is_empty();
+    let hits = match fuzzy {
+        true => movie_search_index(db)
+            .await?
+            .search(&query.q, query.limit()),
+        false => {
+            let text_scores: BTreeMap<u8, f64> = text_hits
+                .iter()
+                .map(|(movie, text_score)| (movie.movie_id, *text_score))
+                .collect();
+            let movies: Vec<Movie> = text_hits.into_iter().map(|(movie, _)| movie).collect();
+            SearchIndex::new(&movies)
+                .with_text_scores(text_scores)
+                .search(&query.q, query.limit())
+        }
+    };
+    return Ok(SearchResults {
+        query: query.q.clone(),
+        fuzzy,
+        hits,
+    });
+}
+
+/// Returns search index over all movies, cached until a movie changes.
+pub async fn movie_search_index(db: &DB) -> Result<Arc<SearchIndex>> {
+    if let Some(search_index) = db.search_index.get() {
+        return Ok(search_index);
+    }
+    let movies = db.read_movies().await?;
+    let search_index = Arc::new(SearchIndex::new(&movies));
+    db.search_index.set(search_index.clone());
+    return Ok(search_index);
+}
+
+/// Handles Create Production House (POST /boxoffice/v2/productionhouses) request.
+/// Returns 201 with the created production house and its location,
+/// 409 if its name or an alias is a name of another production house.
+#[tracing::instrument(skip_all)]
+pub async fn create_production_house_handler(
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to ProductionHouseRequest.
+    let production_house_request: ProductionHouseRequest =
+        negotiation::deserialize_body(buf, request_format)
+            .map_err(|e| reject::custom(BoxOfficeError::BadProductionHouseRequestError(e)))?;
+
+    // Validate request fields.
+    production_house_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    // Create Production House.
+    let created_production_house = db
+        .create_production_house(&production_house_request)
+        .await
+        .map_err(reject::custom)?;
+    log::info!(
+        "Production house with ID {} created.",
+        created_production_house.id
+    );
+
+    // Return created Production House with its location.
+    let location = format!(
+        "{}/{}",
+        PRODUCTION_HOUSES_V2_PATH, created_production_house.id
+    );
+    let reply = negotiation::reply(&created_production_house, format, StatusCode::CREATED)?;
+    return Ok(warp::reply::with_header(reply, LOCATION, location));
+}
+
+/// Handles Read Production Houses (GET /boxoffice/v2/productionhouses) request.
+#[tracing::instrument(skip_all)]
+pub async fn read_production_houses_handler(
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let fetched_production_houses = db.read_production_houses().await.map_err(reject::custom)?;
+    return negotiation::reply(&fetched_production_houses, format, StatusCode::OK);
+}
+
+/// Handles Get Production House (GET /boxoffice/v2/productionhouses/{id}) request.
+#[tracing::instrument(skip(db))]
+pub async fn get_production_house_handler(
+    id: String,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let production_house = find_production_house(&id, &db)
+        .await
+        .map_err(reject::custom)?;
+    return negotiation::reply(&production_house, format, StatusCode::OK);
+}
+
+/// Handles Update Production House (PUT /boxoffice/v2/productionhouses/{id}) request.
+/// Replaces name and aliases of the production house, its movies are renamed with it.
+#[tracing::instrument(skip(buf, db))]
+pub async fn update_production_house_handler(
+    id: String,
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to ProductionHouseRequest.
+    let production_house_request: ProductionHouseRequest =
+        negotiation::deserialize_body(buf, request_format)
+            .map_err(|e| reject::custom(BoxOfficeError::BadProductionHouseRequestError(e)))?;
+
+    // Validate request fields.
+    production_house_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    // Update Production House.
+    let production_house = find_production_house(&id, &db)
+        .await
+        .map_err(reject::custom)?;
+    let updated_production_house = db
+        .update_production_house(&production_house, &production_house_request)
+        .await
+        .map_err(reject::custom)?;
+    log::info!("Production house with ID {} updated.", id);
+    return negotiation::reply(&updated_production_house, format, StatusCode::OK);
+}
+
+/// Handles Delete Production House (DELETE /boxoffice/v2/productionhouses/{id}) request.
+/// Returns 204 without body, 409 if movies refer to the production house.
+#[tracing::instrument(skip(db))]
+pub async fn delete_production_house_handler(id: String, db: DB) -> WebResult<impl Reply> {
+    let number_of_production_houses_deleted = db
+        .delete_production_house(&id)
+        .await
+        .map_err(reject::custom)?;
+    if number_of_production_houses_deleted != 1 {
+        return Err(reject::custom(
+            BoxOfficeError::ProductionHouseNotFoundError(id),
+        ));
+    }
+    log::info!("Production house with ID {} deleted.", id);
+    return Ok(StatusCode::NO_CONTENT);
+}
+
+/// Handles Merge Production Houses (POST /boxoffice/v2/productionhouses/{id}/merge) request.
+/// Source production houses are merged into the production house, their names become its aliases
+/// and their movies are re-pointed to it. Returns the merged production house.
+#[tracing::instrument(skip(buf, db))]
+pub async fn merge_production_houses_handler(
+    id: String,
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to MergeProductionHousesRequest.
+    let merge_production_houses_request: MergeProductionHousesRequest =
+        negotiation::deserialize_body(buf, request_format)
+            .map_err(|e| reject::custom(BoxOfficeError::BadProductionHouseRequestError(e)))?;
+
+    // Validate request fields.
+    merge_production_houses_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    // Business Validation
+    let production_house = find_production_house(&id, &db)
+        .await
+        .map_err(reject::custom)?;
+    let mut source_production_houses: Vec<ProductionHouse> = Vec::new();
+    for source_id in &merge_production_houses_request.source_ids {
+        if *source_id == production_house.id {
+            return Err(reject::custom(
+                BoxOfficeError::BadProductionHouseRequestError(format!(
+                    "production house {} can not be merged into itself",
+                    source_id
+                )),
+            ));
+        }
+        if source_production_houses
+            .iter()
+            .any(|source_production_house| source_production_house.id == *source_id)
+        {
+            continue;
+        }
+        source_production_houses.push(
+            find_production_house(source_id, &db)
+                .await
+                .map_err(reject::custom)?,
+        );
+    }
+
+    // Merge Production Houses.
+    let merged_production_house = db
+        .merge_production_houses(&production_house, &source_production_houses)
+        .await
+        .map_err(reject::custom)?;
+    return negotiation::reply(&merged_production_house, format, StatusCode::OK);
+}
+
+/// Handles Read Track Records (GET /boxoffice/v2/productionhouses/trackrecords) request.
+/// Returns track record of every production house, ordered by name.
+#[tracing::instrument(skip_all)]
+pub async fn read_track_records_handler(format: ResponseFormat, db: DB) -> WebResult<impl Reply> {
+    let production_houses = db.read_production_houses().await.map_err(reject::custom)?;
+    let movies = db.read_movies().await.map_err(reject::custom)?;
+    let track_records: Vec<ProductionHouseTrackRecord> = production_houses
+        .iter()
+        .map(|production_house| {
+            let production_house_movies: Vec<Movie> = movies
+                .iter()
+                .filter(|movie| movie.production_house_id.as_ref() == Some(&production_house.id))
+                .cloned()
+                .collect();
+            return production_house::track_record(production_house, &production_house_movies);
+        })
+        .collect();
+    return negotiation::reply(&track_records, format, StatusCode::OK);
+}
+
+/// Handles Read Track Record (GET /boxoffice/v2/productionhouses/{id}/trackrecord) request.
+/// Returns number of movies, hits and hit ratio, and totals of released movies of the production house.
+#[tracing::instrument(skip(db))]
+pub async fn read_track_record_handler(
+    id: String,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let production_house = find_production_house(&id, &db)
+        .await
+        .map_err(reject::custom)?;
+    let movies = db
+        .read_production_house_movies(&production_house.id)
+        .await
+        .map_err(reject::custom)?;
+    let track_record = production_house::track_record(&production_house, &movies);
+    return negotiation::reply(&track_record, format, StatusCode::OK);
+}
+
+/// Fetches movie of v2 item routes.
+/// Returns InvalidMovieIDError if id is not a Movie Id, MovieNotFoundError if there is no such movie.
+async fn find_movie(id: &str, db: &DB) -> Result<Movie> {
+    let movie_id = id
+        .parse::<u8>()
+        .map_err(|_| BoxOfficeError::InvalidMovieIDError(id.to_string()))?;
+    return db
+        .find_movie_by_id(movie_id)
+        .await?
+        .ok_or_else(|| BoxOfficeError::MovieNotFoundError(id.to_string()));
+}
+
+/// Returns production house with Production House Id, ProductionHouseNotFoundError if there is none.
+async fn find_production_house(id: &str, db: &DB) -> Result<ProductionHouse> {
+    return db
+        .get_production_house(id)
+        .await?
+        .ok_or_else(|| BoxOfficeError::ProductionHouseNotFoundError(id.to_string()));
+}
+
+/// Returns person with Person Id, PersonNotFoundError if there is none.
+async fn find_person(id: &str, db: &DB) -> Result<Person> {
+    return db
+        .get_person(id)
+        .await?
+        .ok_or_else(|| BoxOfficeError::PersonNotFoundError(id.to_string()));
+}
+
+/// Returns franchise with Franchise Id, FranchiseNotFoundError if there is none.
+async fn find_franchise(id: &str, db: &DB) -> Result<Franchise> {
+    return db
+        .get_franchise(id)
+        .await?
+        .ok_or_else(|| BoxOfficeError::FranchiseNotFoundError(id.to_string()));
+}
+
+/// Returns relations of movie in both directions, with titles of the related movies.
+async fn movie_relations(movie: &Movie, db: &DB) -> Result<MovieRelations> {
+    let related_movie_ids: Vec<u8> = movie
+        .relations
+        .iter()
+        .flatten()
+        .map(|relation| relation.movie_id)
+        .collect();
+    let related_movies = db.read_movies_by_ids(&related_movie_ids).await?;
+    let related_by = db.read_related_movies(movie.movie_id).await?;
+    return Ok(franchise::relations(movie, &related_movies, &related_by));
+}
+
+/// Additions to movies requested with query parameters of movie routes.
+struct MovieView {
+    financial_fields: FinancialFields,
+    requested_currency: Option<(String, AmountUnit)>,
+    inflation_base_year: Option<u16>,
+    sort: Option<(MovieSortKey, SortOrder)>,
+    db: DB,
+}
+
+/// Reads query parameters of movie routes.
+/// Exchange rate of requested currency and CPI of base year are checked here,
+/// so that requests fail before changing a movie.
+fn movie_view(query: &MovieQuery, db: &DB) -> Result<MovieView> {
+    let financial_fields = query.financial_fields()?;
+    let requested_currency = query.requested_currency()?;
+    if let Some((currency, _)) = &requested_currency {
+        db.exchange_rates.rate(currency, None)?;
+    }
+    let inflation_base_year = match query.is_inflation_adjustment_requested() {
+        true => Some(match query.base_year {
+            Some(base_year) => base_year,
+            None => db.cpi.latest_year()?,
+        }),
+        false => None,
+    };
+    if let Some(base_year) = inflation_base_year {
+        db.cpi.index(base_year)?;
+    }
+    return Ok(MovieView {
+        financial_fields,
+        requested_currency,
+        inflation_base_year,
+        sort: query.sort.map(|key| (key, query.order)),
+        db: db.clone(),
+    });
+}
+
+impl MovieView {
+    /// Adds requested financial figures and amounts in requested currency to movie.
+    fn apply(&self, movie: Movie) -> Result<Movie> {
+        let mut movie = include_financials(movie, &self.financial_fields);
+        if let Some((currency, unit)) = &self.requested_currency {
+            movie.requested_amounts = Some(self.db.exchange_rates.convert(
+                movie.budget_crores,
+                movie.collection_crores,
+                currency,
+                *unit,
+            )?);
+        }
+        if let Some(base_year) = self.inflation_base_year {
+            movie.inflation_adjusted = Some(self.db.cpi.adjust_movie(&movie, base_year)?);
+        }
+        return Ok(movie);
+    }
+
+    /// Adds requested figures to movies and sorts them as requested.
+    /// Movies are already kept by territory, market and metadata filters when read.
+    fn apply_all(&self, movies: Vec<Movie>) -> Result<Vec<Movie>> {
+        let mut movies = movies
+            .into_iter()
+            .map(|movie| self.apply(movie))
+            .collect::<Result<Vec<Movie>>>()?;
+        if let Some((key, order)) = self.sort {
+            sort_movies(&mut movies, key, order);
+        }
+        return Ok(movies);
+    }
+}
+
+/// Updates movie and returns it as stored.
+/// Update which does not change any field succeeds as well.
+async fn update_movie(
+    movie: &Movie,
+    update_movie_request: &UpdateMovieRequest,
+    db: &DB,
+) -> Result<Movie> {
+    let id = movie.movie_id.to_string();
+    db.update_movie(&id, update_movie_request).await?;
+    log::info!("Movie with Movie ID {} updated.", id);
+    return find_movie(&id, db).await;
+}
+
+/// Handles movie events (GET) request.
+/// Streams created, updated and deleted movie events matching the filter as Server-Sent Events.
+pub async fn movie_events_handler(filter: EventFilter, db: DB) -> WebResult<impl Reply> {
+    let events = db.events.subscribe(filter);
+    return Ok(warp::sse::reply(
+        warp::sse::keep_alive().stream(events::sse_stream(events)),
+    ));
+}
+
+/// Handles movie events WebSocket upgrade request.
+/// Sends movie events matching the filter as JSON text messages.
+pub async fn movie_events_websocket_handler(
+    websocket: warp::ws::Ws,
+    filter: EventFilter,
+    db: DB,
+) -> WebResult<impl Reply> {
+    return Ok(websocket
+        .on_upgrade(move |socket| events::websocket_session(socket, db.events.clone(), filter)));
+}
+
+/// Handles Create Webhook (POST) request.
+/// Secret used to sign payloads is generated unless given in the request.
+#[tracing::instrument(skip_all)]
+pub async fn create_webhook_handler(
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to CreateWebhookRequest.
+    let create_webhook_request: CreateWebhookRequest =
+        negotiation::deserialize_body(buf, request_format)
+            .map_err(|e| reject::custom(BoxOfficeError::BadCreateWebhookRequestError(e)))?;
+
+    // Validate request fields.
+    create_webhook_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    // Create Webhook.
+    let secret = create_webhook_request
+        .secret
+        .clone()
+        .unwrap_or_else(webhooks::generate_secret);
+    let created_webhook = db
+        .create_webhook(&create_webhook_request, &secret)
+        .await
+        .map_err(reject::custom)?;
+
+    log::info!(
+        "Webhook with Webhook ID {} created for {}.",
+        created_webhook.id,
+        created_webhook.url
+    );
+
+    // Populate Response
+    let create_webhook_response = populate_create_webhook_response(created_webhook, secret);
+
+    // Return Response.
+    return negotiation::reply(&create_webhook_response, format, StatusCode::CREATED);
+}
+
+/// Handles Read Webhooks (GET) request.
+#[tracing::instrument(skip_all)]
+pub async fn read_webhooks_handler(format: ResponseFormat, db: DB) -> WebResult<impl Reply> {
+    // Fetch Webhooks.
+    let fetched_webhooks = db.read_webhooks().await.map_err(reject::custom)?;
+    // Populate Response
+    let read_webhooks_response = populate_read_webhooks_response(fetched_webhooks);
+    // Return Response.
+    return negotiation::reply(&read_webhooks_response, format, StatusCode::OK);
+}
+
+/// Handles Delete Webhook (DELETE) request.
+#[tracing::instrument(skip(db))]
+pub async fn delete_webhook_handler(
+    id: String,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let number_of_webhooks_deleted = db.delete_webhook(&id).await.map_err(reject::custom)?;
+    if number_of_webhooks_deleted == 1 {
+        log::info!("Webhook with Webhook ID {} deleted.", id);
+        let delete_webhook_response = populate_delete_webhook_response(id.as_str());
+        return negotiation::reply(&delete_webhook_response, format, StatusCode::OK);
+    } else {
+        return Err(reject::custom(BoxOfficeError::WebhookNotFoundError(id)));
+    }
+}
+
+/// Handles Read Webhook Deliveries (GET) request.
+/// Returns every delivery attempt made to the webhook, newest first.
+#[tracing::instrument(skip(db))]
+pub async fn read_webhook_deliveries_handler(
+    id: String,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let fetched_deliveries = db
+        .read_webhook_deliveries(&id)
+        .await
+        .map_err(reject::custom)?;
+    let read_webhook_deliveries_response =
+        populate_read_webhook_deliveries_response(fetched_deliveries);
+    return negotiation::reply(&read_webhook_deliveries_response, format, StatusCode::OK);
+}
+
+/// Handles Read Dead Letters (GET) request.
+#[tracing::instrument(skip_all)]
+pub async fn read_dead_letters_handler(format: ResponseFormat, db: DB) -> WebResult<impl Reply> {
+    let fetched_dead_letters = db.read_dead_letters().await.map_err(reject::custom)?;
+    let read_dead_letters_response = populate_read_dead_letters_response(fetched_dead_letters);
+    return negotiation::reply(&read_dead_letters_response, format, StatusCode::OK);
+}
+
+/// Handles Retry Dead Letter (POST) request.
+/// Dead letter is delivered again with a fresh set of attempts.
+#[tracing::instrument(skip(db))]
+pub async fn retry_dead_letter_handler(
+    id: String,
+    format: ResponseFormat,
+    db: DB,
+) -> WebResult<impl Reply> {
+    let number_of_dead_letters_retried = db.retry_dead_letter(&id).await.map_err(reject::custom)?;
+    if number_of_dead_letters_retried == 1 {
+        log::info!("Dead letter {} scheduled for delivery.", id);
+        let retry_dead_letter_response = populate_retry_dead_letter_response(id.as_str());
+        return negotiation::reply(&retry_dead_letter_response, format, StatusCode::ACCEPTED);
+    } else {
+        return Err(reject::custom(BoxOfficeError::DeadLetterNotFoundError(id)));
+    }
+}
+
+/// Handles Read Exchange Rates (GET) request.
+#[tracing::instrument(skip_all)]
+pub async fn read_exchange_rates_handler(format: ResponseFormat, db: DB) -> WebResult<impl Reply> {
+    let exchange_rates = db.exchange_rates.read();
+    let read_exchange_rates_response = populate_read_exchange_rates_response(exchange_rates);
+    return negotiation::reply(&read_exchange_rates_response, format, StatusCode::OK);
+}
+
+/// Handles Upsert Exchange Rates (PUT) request.
+/// Adds rates to the exchange rate table, replacing rates of the same currency and date.
+/// Stored movies keep amounts converted with the rates they were stored with.
+#[tracing::instrument(skip_all)]
+pub async fn upsert_exchange_rates_handler(
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to UpsertExchangeRatesRequest.
+    let upsert_exchange_rates_request: UpsertExchangeRatesRequest =
+        negotiation::deserialize_body(buf, request_format)
+            .map_err(|e| reject::custom(BoxOfficeError::BadExchangeRatesRequestError(e)))?;
+
+    // Validate request fields.
+    upsert_exchange_rates_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    // Upsert Exchange Rates.
+    db.exchange_rates
+        .upsert(&upsert_exchange_rates_request.rates)
+        .map_err(reject::custom)?;
+    log::info!(
+        "{} exchange rates upserted.",
+        upsert_exchange_rates_request.rates.len()
+    );
+
+    let upsert_exchange_rates_response =
+        populate_upsert_exchange_rates_response(upsert_exchange_rates_request.rates.len());
+    return negotiation::reply(&upsert_exchange_rates_response, format, StatusCode::OK);
+}
+
+/// Handles Read CPI (GET) request.
+#[tracing::instrument(skip_all)]
+pub async fn read_cpi_handler(format: ResponseFormat, db: DB) -> WebResult<impl Reply> {
+    let cpi = db.cpi.read();
+    let read_cpi_response = populate_read_cpi_response(cpi);
+    return negotiation::reply(&read_cpi_response, format, StatusCode::OK);
+}
+
+/// Handles Upsert CPI (PUT) request.
+/// Adds consumer price index of years, replacing index of the same year.
+/// Inflation adjusted amounts are computed on read, so they reflect the change right away.
+#[tracing::instrument(skip_all)]
+pub async fn upsert_cpi_handler(
+    format: ResponseFormat,
+    request_format: RequestFormat,
+    buf: impl Buf,
+    db: DB,
+) -> WebResult<impl Reply> {
+    // Deserialize input and map to UpsertCpiRequest.
+    let upsert_cpi_request: UpsertCpiRequest =
+        negotiation::deserialize_body(buf, request_format)
+            .map_err(|e| reject::custom(BoxOfficeError::BadCpiRequestError(e)))?;
+
+    // Validate request fields.
+    upsert_cpi_request
+        .validate()
+        .map_err(|e| reject::custom(BoxOfficeError::ValidationError(e)))?;
+
+    // Upsert CPI.
+    db.cpi
+        .upsert(&upsert_cpi_request.cpi)
+        .map_err(reject::custom)?;
+    log::info!(
+        "Consumer price index of {} years upserted.",
+        upsert_cpi_request.cpi.len()
+    );
+
+    let upsert_cpi_response = populate_upsert_cpi_response(upsert_cpi_request.cpi.len());
+    return negotiation::reply(&upsert_cpi_response, format, StatusCode::OK);
+}
+
+/// Handles GraphQL (POST) request.
+#[tracing::instrument(skip_all)]
+pub async fn graphql_handler(
+    (schema, request): (BoxOfficeSchema, async_graphql::Request),
+) -> WebResult<impl Reply> {
+    let graphql_response = schema.execute(request).await;
+    return Ok(async_graphql_warp::GraphQLResponse::from(graphql_response));
+}
+
+/// Handles GraphiQL playground (GET) request.
+pub async fn graphiql_handler() -> WebResult<impl Reply> {
+    let playground = async_graphql::http::GraphiQLSource::build()
+        .endpoint(GRAPHQL_ENDPOINT)
+        .finish();
+    return Ok(warp::reply::html(playground));
+}
+
+/// Handles liveness (GET) request.
+/// Server is alive as long as it can answer requests.
+pub async fn liveness_handler(format: ResponseFormat) -> WebResult<impl Reply> {
+    let liveness_response = populate_health_response(true, SERVER_ALIVE);
+    return negotiation::reply(&liveness_response, format, StatusCode::OK);
+}
+
+/// Handles readiness (GET) request.
+/// Returns 503 once shutdown has started so that no new requests are routed to this server.
+pub async fn readiness_handler(
+    format: ResponseFormat,
+    readiness: Readiness,
+) -> WebResult<impl Reply> {
+    if readiness.is_ready() {
+        let readiness_response = populate_health_response(true, SERVER_READY);
+        return negotiation::reply(&readiness_response, format, StatusCode::OK);
+    } else {
+        let readiness_response = populate_health_response(false, SERVER_NOT_READY);
+        return negotiation::reply(&readiness_response, format, StatusCode::SERVICE_UNAVAILABLE);
+    }
 }
 
 /// Business validation for Create Movie Request (POST).
 /// Checks whether Movie is already present in the Collecton or not.
 /// If present then we can not create another movie with same Movie Id.
-async fn validate_create_movie_request(
+#[tracing::instrument(skip_all)]
+pub async fn validate_create_movie_request(
     create_movie_request: &CreateMovieRequest,
     db: &DB,
 ) -> Result<()> {
     let id_string = &create_movie_request.movie_id.to_string();
     let id = id_string.as_str();
     let movie_result = db
-        .check_movie_exist(&create_movie_request.movie_id.to_string().as_str())
+        .check_movie_exist(create_movie_request.movie_id.to_string().as_str())
         .await;
-    if movie_result.is_ok() {
-        let movie = movie_result.unwrap();
+    if let Ok(movie) = movie_result {
         if *movie {
             return Err(BoxOfficeError::InvalidCreateDocumentError(id.to_string()));
         } else {
@@ -156,8 +1592,8 @@ async fn validate_create_movie_request(
 /// Business validation for Update Movie (PUT).
 /// Checks whether Movie is present in the Collecton or not.
 /// If present then only we can update it.
-
-async fn validate_update_movie_id(id: &String, db: &DB) -> Result<()> {
+#[tracing::instrument(skip(db))]
+pub async fn validate_update_movie_id(id: &String, db: &DB) -> Result<()> {
     let int_id = id.parse::<i32>();
     let valid_id = if let Ok(unwrapped_id) = int_id {
         unwrapped_id
@@ -167,9 +1603,8 @@ async fn validate_update_movie_id(id: &String, db: &DB) -> Result<()> {
         ));
     };
 
-    let movie_result = db.check_movie_exist(&valid_id.to_string().as_str()).await;
-    if movie_result.is_ok() {
-        let movie = movie_result.unwrap();
+    let movie_result = db.check_movie_exist(valid_id.to_string().as_str()).await;
+    if let Ok(movie) = movie_result {
         if *movie {
             return Ok(());
         } else {
@@ -183,7 +1618,8 @@ async fn validate_update_movie_id(id: &String, db: &DB) -> Result<()> {
 /// Business validation for Delete Movie (DELETE).
 /// Checks whether Movie is present in the Collecton or not.
 /// If present then only we can delete it.
-async fn validate_delete_movie_id(id: &String, db: &DB) -> Result<()> {
+#[tracing::instrument(skip(db))]
+pub async fn validate_delete_movie_id(id: &String, db: &DB) -> Result<()> {
     let int_id = id.parse::<i32>();
     let valid_id = if let Ok(unwrapped_id) = int_id {
         unwrapped_id
@@ -193,9 +1629,8 @@ async fn validate_delete_movie_id(id: &String, db: &DB) -> Result<()> {
         ));
     };
 
-    let movie_result = db.check_movie_exist(&valid_id.to_string().as_str()).await;
-    if movie_result.is_ok() {
-        let movie = movie_result.unwrap();
+    let movie_result = db.check_movie_exist(valid_id.to_string().as_str()).await;
+    if let Ok(movie) = movie_result {
         if *movie {
             return Ok(());
         } else {
@@ -0,0 +1,132 @@
+/// Inflation adjustment of box office amounts.
+/// Amounts are adjusted from year of release to a base year with the consumer price index table,
+/// which is loaded from a JSON file and can be changed through the CPI endpoint.
+use crate::config::InflationConfig;
+use crate::error::BoxOfficeError;
+use crate::file_table::{FileTable, TableEntry};
+use crate::model::{ConsumerPriceIndex, InflationAdjustedAmounts, Movie};
+use crate::{constants::*, Result};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+impl TableEntry for ConsumerPriceIndex {
+    type Key = u16;
+
+    fn key(&self) -> Self::Key {
+        self.year
+    }
+}
+
+/// Consumer price index table, index per year.
+/// Clones share the same table. Changes are saved to the file the table was loaded from.
+#[derive(Debug, Clone)]
+pub struct CpiTable {
+    cpi: FileTable<ConsumerPriceIndex>,
+}
+
+impl Default for CpiTable {
+    fn default() -> Self {
+        Self {
+            cpi: FileTable::new(None, BoxOfficeError::CpiFileError),
+        }
+    }
+}
+
+impl CpiTable {
+    /// Loads consumer price index from the configured file.
+    /// Table is empty, i.e. amounts can not be adjusted, if the file does not exist yet.
+    pub fn load(inflation_config: &InflationConfig) -> Result<Self> {
+        let cpi = FileTable::load(
+            inflation_config.cpi_file_path.clone(),
+            BoxOfficeError::CpiFileError,
+        )?;
+        if let Some(file_path) = inflation_config
+            .cpi_file_path
+            .as_ref()
+            .filter(|file_path| file_path.exists())
+        {
+            log::info!(
+                "Consumer price index of {} years loaded from {}.",
+                cpi.read().len(),
+                file_path.display()
+            );
+        }
+        return Ok(Self { cpi });
+    }
+
+    /// Returns consumer price index of every year, oldest first.
+    pub fn read(&self) -> Vec<ConsumerPriceIndex> {
+        return self.cpi.read();
+    }
+
+    /// Adds consumer price index of years, replacing index of the same year,
+    /// once the changed table is saved to its file.
+    pub fn upsert(&self, cpi: &[ConsumerPriceIndex]) -> Result<()> {
+        return self.cpi.upsert(cpi);
+    }
+
+    /// Returns consumer price index of the year.
+    pub fn index(&self, year: u16) -> Result<Decimal> {
+        return self
+            .cpi
+            .with_entries(|cpi| cpi.get(&year).map(|entry| entry.index))
+            .ok_or_else(|| BoxOfficeError::CpiNotFoundError(year.to_string()));
+    }
+
+    /// Returns base year used when none is requested, i.e. the latest year in the table.
+    pub fn latest_year(&self) -> Result<u16> {
+        return self
+            .cpi
+            .with_entries(|cpi| cpi.keys().next_back().copied())
+            .ok_or_else(|| BoxOfficeError::CpiNotFoundError("table is empty".to_string()));
+    }
+
+    /// Adjusts budget and collection in INR crores released in 'year_of_release' to 'base_year' prices.
+    /// Adjusted amounts are rounded to 'INFLATION_ADJUSTED_AMOUNT_DECIMAL_PLACES' decimal places.
+    pub fn adjust(
+        &self,
+        budget_crores: Decimal,
+        collection_crores: Decimal,
+        year_of_release: u16,
+        base_year: u16,
+    ) -> Result<InflationAdjustedAmounts> {
+        let release_index = self.index(year_of_release)?;
+        let base_index = self.index(base_year)?;
+        let to_base_year = |amount_crores: Decimal| {
+            amount_crores
+                .checked_mul(base_index)
+                .and_then(|amount| amount.checked_div(release_index))
+                .or_else(|| {
+                    amount_crores
+                        .checked_div(release_index)
+                        .and_then(|amount| amount.checked_mul(base_index))
+                })
+                .map(|amount| {
+                    amount.round_dp_with_strategy(
+                        INFLATION_ADJUSTED_AMOUNT_DECIMAL_PLACES,
+                        RoundingStrategy::MidpointAwayFromZero,
+                    )
+                })
+                .ok_or_else(|| {
+                    BoxOfficeError::InflationAdjustmentError(format!(
+                        "{} INR crores of {} is out of range in {} prices",
+                        amount_crores, year_of_release, base_year
+                    ))
+                })
+        };
+        return Ok(InflationAdjustedAmounts {
+            base_year,
+            budget_crores: to_base_year(budget_crores)?,
+            collection_crores: to_base_year(collection_crores)?,
+        });
+    }
+
+    /// Adjusts budget and collection of movie to 'base_year' prices.
+    pub fn adjust_movie(&self, movie: &Movie, base_year: u16) -> Result<InflationAdjustedAmounts> {
+        return self.adjust(
+            movie.budget_crores,
+            movie.collection_crores,
+            movie.year_of_release,
+            base_year,
+        );
+    }
+}
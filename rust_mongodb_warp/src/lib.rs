@@ -0,0 +1,149 @@
+#![allow(clippy::needless_return)]
+
+/// Box office library.
+/// Verdict calculation, Movie model, request and response types and the database layer
+/// can be used on their own. 'build_routes' returns the REST, GraphQL and event routes as a warp filter
+/// and 'run' serves them like the 'rust_mongodb_warp' binary does.
+use crate::{
+    config::Config, currency::ExchangeRates, inflation::CpiTable, metadata::Vocabularies,
+    shutdown::Readiness,
+};
+use std::convert::Infallible;
+use warp::{Filter, Rejection, Reply};
+pub type Result<T> = std::result::Result<T, error::BoxOfficeError>;
+pub type WebResult<T> = std::result::Result<T, Rejection>;
+
+pub use business_layer::calculate_verdict;
+pub use db_layer::DB;
+pub use error::BoxOfficeError;
+pub use model::Movie;
+
+/// module contains business logic.
+/// Function to decide verdict depending upon calculated profit or loss.
+pub mod business_layer;
+/// module contains typed client of the REST API.
+#[cfg(feature = "client")]
+pub mod client;
+/// module to roll up dated collection entries into the theatrical run of a movie.
+pub mod collection_run;
+/// module to load application configuration.
+pub mod config;
+/// module lists all the constants in the application.
+pub mod constants;
+/// module to convert amounts between currencies and units with the exchange rate table.
+pub mod currency;
+/// module performs database operations.
+pub mod db_layer;
+/// module to handle errors.
+pub mod error;
+/// module to publish movie change events to Server-Sent Events and WebSocket subscribers.
+pub mod events;
+/// module to keep lookup tables in memory and save them to JSON files.
+mod file_table;
+/// module to relate movies and summarize franchises over their instalments.
+pub mod franchise;
+/// module to serve GraphQL queries and mutations over movies.
+pub mod graphql;
+/// module to serve gRPC API over movies.
+pub mod grpc;
+/// module contains request handling functions.
+mod handler;
+/// module to adjust amounts for inflation with the consumer price index table.
+pub mod inflation;
+/// module to check movie metadata against vocabularies and count facets.
+pub mod metadata;
+/// module to apply versioned database migrations.
+pub mod migrations;
+/// module to map mongodb fields to rust structure.
+pub mod model;
+/// module to render responses and read request bodies in the negotiated format.
+pub mod negotiation;
+/// module to compute filmographies of cast and crew.
+pub mod people;
+/// module to normalize production house names and compute their track records.
+pub mod production_house;
+/// module to check release lifecycle of movies.
+pub mod release;
+/// module to propagate request id to logs and responses.
+pub mod request_context;
+/// module lists request and response structures.
+pub mod request_response_structs;
+/// module contains functions which return response.
+mod response;
+/// module to route REST request. It calls appropriate function depending upon request.
+mod routes;
+/// module to search movies by title, production house and cast with typo tolerance.
+pub mod search;
+/// module to start HTTP or HTTPS server.
+mod server;
+/// module to handle termination signals and server readiness.
+pub mod shutdown;
+/// module to export OpenTelemetry traces.
+pub mod telemetry;
+/// module for TLS termination and certificate reloading.
+pub mod tls;
+/// module to deliver movie events to registered webhooks.
+pub mod webhooks;
+
+/// Connects to MONGODB with exchange rates, consumer price index and vocabularies loaded as configured.
+pub async fn init_db(config: &Config) -> Result<DB> {
+    let db = DB::init(&config.database)
+        .await?
+        .with_exchange_rates(ExchangeRates::load(&config.exchange_rates)?)
+        .with_cpi(CpiTable::load(&config.inflation)?)
+        .with_vocabularies(Vocabularies::new(&config.metadata));
+    return Ok(db);
+}
+
+/// Returns all routes with errors converted to responses, ready to be served by warp.
+/// No background task is started, see 'build_routes_with_db' and 'start_background_tasks'.
+/// Readiness endpoint always reports ready, the embedding server decides when to stop routing requests.
+pub async fn build_routes(
+    config: &Config,
+) -> Result<impl Filter<Extract = (impl Reply,), Error = Infallible> + Clone> {
+    let db = init_db(config).await?;
+    return Ok(build_routes_with_db(db));
+}
+
+/// Returns all routes over the given database, with errors converted to responses.
+/// Pass the same database to 'start_background_tasks' so that change stream events reach event subscribers.
+pub fn build_routes_with_db(
+    db: DB,
+) -> impl Filter<Extract = (impl Reply,), Error = Infallible> + Clone {
+    let readiness = Readiness::default();
+    readiness.set_ready();
+    return routes::assets_filter(db, readiness).recover(error::handle_rejection);
+}
+
+/// Starts webhook delivery and, when change streams are enabled, change stream watching.
+/// Call once per process, every call starts another set of tasks.
+pub fn start_background_tasks(config: &Config, db: &DB) {
+    if config.database.change_streams {
+        events::watch_change_stream(db.clone());
+    }
+    webhooks::start_dispatcher(db.clone(), config.webhooks.clone());
+}
+
+/// Checks whether database is up and running, then serves HTTP and gRPC APIs
+/// until SIGTERM or SIGINT is received and in-flight requests are drained.
+/// Background tasks are started as well.
+pub async fn run(config: &Config) -> Result<()> {
+    let is_connected_to_db = DB::check_mongodb_is_running(&config.database).await?;
+    if !is_connected_to_db {
+        log::error!("Can not start server.");
+        println!("Can not start server.");
+        return Ok(());
+    }
+    let db = init_db(config).await?;
+    start_background_tasks(config, &db);
+    let readiness = Readiness::default();
+    let routes =
+        routes::assets_filter(db.clone(), readiness.clone()).recover(error::handle_rejection);
+    tokio::try_join!(
+        server::run(routes, &config.server, readiness),
+        grpc::run(db, &config.grpc, &config.server),
+    )?;
+    log::info!("Server stopped.");
+    println!("Server stopped.");
+    return Ok(());
+}
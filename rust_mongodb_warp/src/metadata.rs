@@ -0,0 +1,175 @@
+/// Descriptive metadata of movies: genres, languages, CBFC certification, runtime and tags.
+/// Genres, languages, certifications and tags are checked against vocabularies configured in 'metadata' section
+/// and stored spelled as in the vocabulary, whatever the case they are given in.
+/// Movie lists are summarized as facets, i.e. number of movies per value.
+use crate::config::MetadataConfig;
+use crate::error::BoxOfficeError;
+use crate::model::{FacetCount, Movie, MovieFacets, MovieMetadata};
+use crate::request_response_structs::MovieMetadataRequest;
+use crate::Result;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+impl MovieMetadata {
+    /// Returns whether no metadata is set.
+    pub fn is_empty(&self) -> bool {
+        self.genres.is_empty()
+            && self.languages.is_empty()
+            && self.certification.is_none()
+            && self.runtime_minutes.is_none()
+            && self.tags.is_empty()
+    }
+}
+
+/// Vocabularies of movie metadata. Clones share the same vocabularies.
+#[derive(Debug, Clone)]
+pub struct Vocabularies {
+    config: Arc<MetadataConfig>,
+}
+
+impl Default for Vocabularies {
+    fn default() -> Self {
+        Self::new(&MetadataConfig::default())
+    }
+}
+
+impl Vocabularies {
+    pub fn new(metadata_config: &MetadataConfig) -> Self {
+        Self {
+            config: Arc::new(metadata_config.clone()),
+        }
+    }
+
+    /// Returns metadata of the request spelled as in the vocabularies, each value once.
+    /// Returns InvalidMetadataError naming the first value which is not in its vocabulary.
+    pub fn normalize(&self, metadata_request: &MovieMetadataRequest) -> Result<MovieMetadata> {
+        let certification = match &metadata_request.certification {
+            Some(certification) => Some(vocabulary_value(
+                "certification",
+                certification,
+                &self.config.certifications,
+            )?),
+            None => None,
+        };
+        return Ok(MovieMetadata {
+            genres: vocabulary_values("genre", &metadata_request.genres, &self.config.genres)?,
+            languages: vocabulary_values(
+                "language",
+                &metadata_request.languages,
+                &self.config.languages,
+            )?,
+            certification,
+            runtime_minutes: metadata_request.runtime_minutes,
+            tags: vocabulary_values("tag", &metadata_request.tags, &self.config.tags)?,
+        });
+    }
+
+    /// Returns genre, language, certification and tag filters spelled as stored, so that stored values can be matched as they are.
+    /// Values which are not in their vocabulary are kept as given and match no movie.
+    pub fn filter_values(
+        &self,
+        genre: Option<&str>,
+        language: Option<&str>,
+        certification: Option<&str>,
+        tag: Option<&str>,
+    ) -> [Option<String>; 4] {
+        let spelled = |kind: &str, value: Option<&str>, vocabulary: &[String]| {
+            value.map(|value| {
+                vocabulary_value(kind, value, vocabulary).unwrap_or_else(|_| value.to_owned())
+            })
+        };
+        return [
+            spelled("genre", genre, &self.config.genres),
+            spelled("language", language, &self.config.languages),
+            spelled("certification", certification, &self.config.certifications),
+            spelled("tag", tag, &self.config.tags),
+        ];
+    }
+}
+
+/// Returns values spelled as in the vocabulary, leaving out repeated values.
+fn vocabulary_values(kind: &str, values: &[String], vocabulary: &[String]) -> Result<Vec<String>> {
+    let mut vocabulary_values: Vec<String> = Vec::new();
+    for value in values {
+        let vocabulary_value = vocabulary_value(kind, value, vocabulary)?;
+        if !vocabulary_values.contains(&vocabulary_value) {
+            vocabulary_values.push(vocabulary_value);
+        }
+    }
+    return Ok(vocabulary_values);
+}
+
+/// Returns value spelled as in the vocabulary. Any value is accepted, in lowercase, when the vocabulary is empty.
+fn vocabulary_value(kind: &str, value: &str, vocabulary: &[String]) -> Result<String> {
+    let value = value.trim();
+    if vocabulary.is_empty() {
+        return Ok(value.to_lowercase());
+    }
+    return vocabulary
+        .iter()
+        .find(|vocabulary_value| vocabulary_value.eq_ignore_ascii_case(value))
+        .cloned()
+        .ok_or_else(|| {
+            BoxOfficeError::InvalidMetadataError(format!(
+                "unknown {} '{}', expected one of {}",
+                kind,
+                value,
+                vocabulary.join(", ")
+            ))
+        });
+}
+
+/// Returns whether movie has the genre, language, certification and tag, any of them may be left out.
+/// Values are compared ignoring case.
+pub fn has_metadata(
+    movie: &Movie,
+    genre: Option<&str>,
+    language: Option<&str>,
+    certification: Option<&str>,
+    tag: Option<&str>,
+) -> bool {
+    let metadata = &movie.metadata;
+    return contains(&metadata.genres, genre)
+        && contains(&metadata.languages, language)
+        && contains(metadata.certification.as_slice(), certification)
+        && contains(&metadata.tags, tag);
+}
+
+fn contains(values: &[String], value: Option<&str>) -> bool {
+    return value
+        .map(|value| values.iter().any(|v| v.eq_ignore_ascii_case(value)))
+        .unwrap_or(true);
+}
+
+/// Returns number of movies per genre, language, certification and tag.
+pub fn facets(movies: &[Movie]) -> MovieFacets {
+    return MovieFacets {
+        genres: facet_counts(movies.iter().map(|movie| &movie.metadata.genres[..])),
+        languages: facet_counts(movies.iter().map(|movie| &movie.metadata.languages[..])),
+        certifications: facet_counts(
+            movies
+                .iter()
+                .map(|movie| movie.metadata.certification.as_slice()),
+        ),
+        tags: facet_counts(movies.iter().map(|movie| &movie.metadata.tags[..])),
+    };
+}
+
+/// Counts values over movies, most frequent value first and values of the same count in alphabetical order.
+fn facet_counts<'a>(values_of_movies: impl Iterator<Item = &'a [String]>) -> Vec<FacetCount> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for values in values_of_movies {
+        for value in values {
+            *counts.entry(value.as_str()).or_default() += 1;
+        }
+    }
+    let mut facet_counts: Vec<FacetCount> = counts
+        .into_iter()
+        .map(|(value, count)| FacetCount {
+            value: value.to_string(),
+            count,
+        })
+        .collect();
+    facet_counts.sort_by_key(|facet_count| std::cmp::Reverse(facet_count.count));
+    return facet_counts;
+}
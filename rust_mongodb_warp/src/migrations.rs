@@ -0,0 +1,138 @@
+/// Versioned database migrations.
+/// Applied migrations are recorded in 'Migrations' collection so that every migration runs once.
+/// Migrations are applied in version order and must be safe to apply to already migrated documents.
+use crate::{constants::*, db_layer::DB, model::ReleaseStatus, Result};
+use mongodb::bson::doc;
+
+/// Database migration.
+#[derive(Debug)]
+pub struct Migration {
+    pub version: i32,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+/// All migrations, in version order.
+pub const MIGRATIONS: [Migration; 6] = [
+    Migration {
+        version: 1,
+        name: "movie_amounts_as_strings",
+        description: "Stores budget and collection entered as numbers as decimal strings.",
+    },
+    Migration {
+        version: 2,
+        name: "movie_numbers_as_int32",
+        description: "Stores Movie Id and year of release entered as double or long as int32.",
+    },
+    Migration {
+        version: 3,
+        name: "movie_amounts_with_currency",
+        description:
+            "Stores budget and collection of movies without currency as reported in INR crores.",
+    },
+    Migration {
+        version: 4,
+        name: "movie_release_status",
+        description: "Marks movies stored before release lifecycle as closed.",
+    },
+    Migration {
+        version: 5,
+        name: "movie_production_house_ids",
+        description:
+            "Links movies to production houses by name, creating production houses as needed.",
+    },
+    Migration {
+        version: 6,
+        name: "collection_entry_days",
+        description:
+            "Stores days covered by collection entries, so that overlapping entries are rejected.",
+    },
+];
+
+/// Returns migrations which are not applied yet.
+pub async fn pending(db: &DB) -> Result<Vec<&'static Migration>> {
+    let applied_versions = db.read_applied_migrations().await?;
+    return Ok(MIGRATIONS
+        .iter()
+        .filter(|migration| !applied_versions.contains(&migration.version))
+        .collect());
+}
+
+/// Applies pending migrations.
+/// Returns applied migrations with number of documents each one modified.
+pub async fn run(db: &DB) -> Result<Vec<(&'static Migration, u64)>> {
+    let mut applied: Vec<(&'static Migration, u64)> = Vec::new();
+    for migration in pending(db).await? {
+        let number_of_documents_modified = apply(db, migration).await?;
+        db.record_migration(migration.version, migration.name)
+            .await?;
+        log::info!(
+            "Migration {} '{}' applied, {} documents modified.",
+            migration.version,
+            migration.name,
+            number_of_documents_modified
+        );
+        applied.push((migration, number_of_documents_modified));
+    }
+    return Ok(applied);
+}
+
+async fn apply(db: &DB, migration: &Migration) -> Result<u64> {
+    match migration.version {
+        1 => {
+            let filter = doc! {
+                "$or": [
+                    { BUDGET_CRORES: { "$type": "number" } },
+                    { COLLECTION_CRORES: { "$type": "number" } },
+                ],
+            };
+            let pipeline = vec![doc! {
+                "$set": {
+                    BUDGET_CRORES: { "$toString": format!("${}", BUDGET_CRORES) },
+                    COLLECTION_CRORES: { "$toString": format!("${}", COLLECTION_CRORES) },
+                },
+            }];
+            return db.update_movies_with_pipeline(filter, pipeline).await;
+        }
+        2 => {
+            let filter = doc! {
+                "$or": [
+                    { MOVIEID: { "$not": { "$type": "int" } } },
+                    { YEAR_OF_RELEASE: { "$not": { "$type": "int" } } },
+                ],
+            };
+            let pipeline = vec![doc! {
+                "$set": {
+                    MOVIEID: { "$toInt": format!("${}", MOVIEID) },
+                    YEAR_OF_RELEASE: { "$toInt": format!("${}", YEAR_OF_RELEASE) },
+                },
+            }];
+            return db.update_movies_with_pipeline(filter, pipeline).await;
+        }
+        3 => {
+            let filter = doc! { CURRENCY: { "$exists": false } };
+            let pipeline = vec![doc! {
+                "$set": {
+                    CURRENCY: INR,
+                    UNIT: CRORE,
+                    REPORTED_BUDGET: format!("${}", BUDGET_CRORES),
+                    REPORTED_COLLECTION: format!("${}", COLLECTION_CRORES),
+                    EXCHANGE_RATE: "1",
+                },
+            }];
+            return db.update_movies_with_pipeline(filter, pipeline).await;
+        }
+        4 => {
+            let filter = doc! { STATUS: { "$exists": false } };
+            let pipeline = vec![doc! {
+                "$set": {
+                    STATUS: ReleaseStatus::Closed.to_string(),
+                },
+            }];
+            return db.update_movies_with_pipeline(filter, pipeline).await;
+        }
+        5 => return db.link_movies_to_production_houses().await,
+        6 => return db.store_collection_entry_days().await,
+        _ => return Ok(0),
+    }
+}
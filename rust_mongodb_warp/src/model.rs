@@ -1,16 +1,536 @@
 /// Map mongodb document fields to rust structure.
+use crate::error::BoxOfficeError;
+use crate::Result;
 use rust_decimal::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// This structure is used to map mongodb document fields to rust structure.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Movie {
     pub id: String,
     pub movie_id: u8,
     pub title: String,
     pub production_house: String,
+    /// Id of the production house, not set for movies stored before production houses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub production_house_id: Option<String>,
     pub year_of_release: u16,
+    /// Release date 'YYYY-MM-DD', not set when not announced yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_date: Option<String>,
+    pub status: ReleaseStatus,
     pub budget_crores: Decimal,
     pub collection_crores: Decimal,
     pub verdict: String,
+    /// Verdict may still change as the movie is not closed yet.
+    pub verdict_provisional: bool,
+    /// Computed financial figures, set only when requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub financials: Option<MovieFinancials>,
+    /// Budget and collection as reported, in their own currency and unit.
+    /// Not set for movies stored before amounts had a currency, which are in INR crores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reported_amounts: Option<MovieAmounts>,
+    /// Budget and collection in the currency and unit requested with 'currency' and 'unit' query parameters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested_amounts: Option<MovieAmounts>,
+    /// Budget and collection adjusted for inflation to a base year, set only when requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inflation_adjusted: Option<InflationAdjustedAmounts>,
+    /// Budget and collection broken down by territory, parts add up to the movie totals.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub territories: Option<Vec<TerritoryCollection>>,
+    /// Territory breakdown no longer adds up, as budget or collection changed after it was recorded.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub territories_stale: bool,
+    /// Genres, languages, certification, runtime and tags, not set for movies without metadata.
+    #[serde(default, skip_serializing_if = "MovieMetadata::is_empty")]
+    pub metadata: MovieMetadata,
+    /// Cast and crew of the movie.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credits: Option<Vec<Credit>>,
+    /// Movies this movie is a sequel, remake or dubbed version of.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relations: Option<Vec<MovieRelation>>,
+}
+
+/// Stage of a movie in its release lifecycle, in lifecycle order.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReleaseStatus {
+    Announced,
+    InProduction,
+    Released,
+    Running,
+    Closed,
+}
+
+/// Descriptive metadata of a movie.
+/// Genres, languages, certification and tags are spelled as in the configured vocabularies.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct MovieMetadata {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub genres: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
+    /// CBFC certification, e.g. 'UA 13+'.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub certification: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime_minutes: Option<u16>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// Number of movies per metadata value of a list of movies, most frequent value first.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct MovieFacets {
+    pub genres: Vec<FacetCount>,
+    pub languages: Vec<FacetCount>,
+    pub certifications: Vec<FacetCount>,
+    pub tags: Vec<FacetCount>,
+}
+
+/// Number of movies with a metadata value.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: usize,
+}
+
+/// Budget share and collection of a movie in one territory, with the verdict they earn.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TerritoryCollection {
+    /// Domestic circuit or overseas market, e.g. 'mumbai' or 'usa'.
+    pub territory: String,
+    pub market: Market,
+    /// Part of the budget recovered from the territory, e.g. price of its distribution rights.
+    pub budget_crores: Decimal,
+    pub collection_crores: Decimal,
+    pub verdict: String,
+}
+
+/// Market of a territory.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Market {
+    Domestic,
+    Overseas,
+}
+
+impl fmt::Display for Market {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let market = match self {
+            Market::Domestic => "domestic",
+            Market::Overseas => "overseas",
+        };
+        return write!(f, "{}", market);
+    }
+}
+
+impl FromStr for Market {
+    type Err = BoxOfficeError;
+
+    fn from_str(market: &str) -> Result<Self> {
+        match market {
+            "domestic" => Ok(Market::Domestic),
+            "overseas" => Ok(Market::Overseas),
+            _ => Err(BoxOfficeError::InvalidDocumentError(format!(
+                "unknown market '{}'",
+                market
+            ))),
+        }
+    }
+}
+
+/// Budget and collection in INR crores adjusted for inflation from year of release to a base year.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct InflationAdjustedAmounts {
+    pub base_year: u16,
+    pub budget_crores: Decimal,
+    pub collection_crores: Decimal,
+}
+
+/// Budget and collection in a currency and unit.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MovieAmounts {
+    /// ISO 4217 currency code, e.g. INR or USD.
+    pub currency: String,
+    pub unit: AmountUnit,
+    pub budget: Decimal,
+    pub collection: Decimal,
+    /// INR for one unit of currency used for conversion.
+    pub exchange_rate: Decimal,
+    /// Date of exchange rate, not set for INR.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exchange_rate_date: Option<String>,
+}
+
+/// Unit of box office amounts.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AmountUnit {
+    /// 10,000,000
+    #[default]
+    Crore,
+    /// 100,000
+    Lakh,
+    /// 1,000,000
+    Million,
+}
+
+/// This structure is used to map exchange rate table entries to rust structure.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeRate {
+    /// ISO 4217 currency code.
+    pub currency: String,
+    /// Date from which the rate applies, 'YYYY-MM-DD'.
+    pub date: String,
+    /// INR for one unit of currency.
+    pub rate_to_inr: Decimal,
+}
+
+/// This structure is used to map consumer price index table entries to rust structure.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsumerPriceIndex {
+    pub year: u16,
+    /// Average consumer price index of the year.
+    pub index: Decimal,
+}
+
+/// This structure is used to map collection entry document to rust structure.
+/// Collection entry is collection of a movie in one territory over a day, weekend or week.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CollectionEntry {
+    pub id: String,
+    pub movie_id: u8,
+    /// First day of the period, 'YYYY-MM-DD'.
+    pub date: String,
+    pub period: CollectionPeriod,
+    pub territory: String,
+    pub collection_crores: Decimal,
+    pub created_at: String,
+}
+
+/// Period covered by a collection entry.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum CollectionPeriod {
+    /// One day.
+    Day,
+    /// Three days from Friday to Sunday.
+    Weekend,
+    /// Seven days from the first day of the week.
+    Week,
+}
+
+/// Theatrical run of a movie computed from its collection entries.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CollectionRun {
+    pub movie_id: u8,
+    /// Total of the entries rolled up into collection of the movie.
+    pub total_collection_crores: Decimal,
+    /// Collection of the first weekend, not set until it is known.
+    pub opening_weekend_crores: Option<Decimal>,
+    /// Rolled up entries summed over territories, oldest first.
+    pub series: Vec<CollectionPoint>,
+    pub weeks: Vec<WeekCollection>,
+}
+
+/// Collection over a period starting on a date, summed over territories.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CollectionPoint {
+    pub date: String,
+    pub period: CollectionPeriod,
+    pub collection_crores: Decimal,
+}
+
+/// Collection of one week of the run, weeks are counted from the first day of the run.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WeekCollection {
+    pub week: u32,
+    pub start_date: String,
+    pub collection_crores: Decimal,
+    /// Drop from the previous week as percentage of it, negative when collection grew.
+    /// Not set for the first week or after a week without collection.
+    pub drop_percentage: Option<Decimal>,
+}
+
+/// Financial figures computed from budget and collection of a movie.
+/// Only figures requested with 'include' query parameter are set.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct MovieFinancials {
+    /// Collection minus budget, negative for loss.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profit_crores: Option<Decimal>,
+    /// Profit as percentage of budget, negative for loss.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profit_percentage: Option<Decimal>,
+    /// Collection as multiple of budget.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roi_multiple: Option<Decimal>,
+    /// Collection still needed to recover budget, zero once budget is recovered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub break_even_gap_crores: Option<Decimal>,
+}
+
+/// Studio producing movies.
+/// Production house names of movies matching its name or an alias are replaced by its name.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProductionHouse {
+    pub id: String,
+    pub name: String,
+    /// Other spellings of the name, e.g. 'Ram Gopal Varma Films' for 'RGV Films'.
+    pub aliases: Vec<String>,
+    pub created_at: String,
+}
+
+/// Track record of a production house over its released movies.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProductionHouseTrackRecord {
+    pub production_house_id: String,
+    pub name: String,
+    pub movie_count: usize,
+    pub released_movie_count: usize,
+    /// Released movies with verdict HIT or better.
+    pub hit_count: usize,
+    /// Hits as percentage of released movies, not known when no movie is released yet.
+    pub hit_ratio: Option<Decimal>,
+    pub total_budget_crores: Decimal,
+    pub total_collection_crores: Decimal,
+    pub total_profit_crores: Decimal,
+}
+
+/// This structure is used to map person document to rust structure.
+/// A person is credited on movies as cast or crew.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Person {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+}
+
+/// Role of a person in a movie.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Actor,
+    Director,
+    MusicDirector,
+    Producer,
+}
+
+/// Credit of a person in a role on a movie.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Credit {
+    pub person_id: String,
+    /// Name of the person, kept in step with the person.
+    pub name: String,
+    pub role: Role,
+    /// Character played, only for actors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub character: Option<String>,
+}
+
+/// Movies of a person with their verdicts and the success rate over them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Filmography {
+    pub person_id: String,
+    pub name: String,
+    /// Role the filmography is limited to, all roles when not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<Role>,
+    pub movie_count: usize,
+    pub released_movie_count: usize,
+    /// Released movies with verdict HIT or better.
+    pub hit_count: usize,
+    /// Hits as percentage of released movies, not known when no movie is released yet.
+    pub success_rate: Option<Decimal>,
+    /// Credits of the person, newest movie first.
+    pub movies: Vec<FilmographyEntry>,
+}
+
+/// Movie in a filmography with role of the person in it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FilmographyEntry {
+    pub movie_id: u8,
+    pub title: String,
+    pub year_of_release: u16,
+    pub role: Role,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub character: Option<String>,
+    pub status: ReleaseStatus,
+    pub verdict: String,
+    pub verdict_provisional: bool,
+}
+
+/// Kind of relation of a movie to an earlier movie.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+pub enum RelationKind {
+    SequelOf,
+    RemakeOf,
+    DubbedVersionOf,
+}
+
+/// Relation of a movie to another movie, e.g. sequel-of movie 3.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MovieRelation {
+    pub kind: RelationKind,
+    pub movie_id: u8,
+}
+
+/// Relations of a movie in both directions, with titles of the related movies.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MovieRelations {
+    pub movie_id: u8,
+    /// Movies this movie is related to.
+    pub relations: Vec<RelatedMovie>,
+    /// Movies related to this movie, e.g. its sequels.
+    pub related_by: Vec<RelatedMovie>,
+}
+
+/// Related movie with the kind of relation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RelatedMovie {
+    pub kind: RelationKind,
+    pub movie_id: u8,
+    pub title: String,
+    pub year_of_release: u16,
+}
+
+/// This structure is used to map franchise document to rust structure.
+/// A franchise groups movies of a series as instalments, in instalment order.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Franchise {
+    pub id: String,
+    pub name: String,
+    /// Movie Ids of the instalments, first instalment first.
+    pub movie_ids: Vec<u8>,
+    pub created_at: String,
+}
+
+/// Combined figures of a franchise and the trend over its instalments.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FranchiseSummary {
+    pub franchise_id: String,
+    pub name: String,
+    pub instalment_count: usize,
+    pub released_instalment_count: usize,
+    /// Totals of released instalments only, as others have no collection yet.
+    pub total_budget_crores: Decimal,
+    pub total_collection_crores: Decimal,
+    pub total_profit_crores: Decimal,
+    /// Verdict of the totals, 'NOT RELEASED' when no instalment is released yet.
+    pub verdict: String,
+    /// Verdict may still change as a released instalment is not closed yet.
+    pub verdict_provisional: bool,
+    pub instalments: Vec<FranchiseInstalment>,
+}
+
+/// Instalment of a franchise with its change over the previous released instalment.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FranchiseInstalment {
+    /// Position in the franchise, starting at 1.
+    pub instalment: usize,
+    pub movie_id: u8,
+    pub title: String,
+    pub year_of_release: u16,
+    pub status: ReleaseStatus,
+    pub budget_crores: Decimal,
+    pub collection_crores: Decimal,
+    pub verdict: String,
+    pub verdict_provisional: bool,
+    /// Collection change as percentage of collection of the previous released instalment,
+    /// not known for the first released instalment, unreleased instalments and after a zero collection.
+    pub collection_change_percentage: Option<Decimal>,
+}
+
+/// Field of a movie matched by search.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchField {
+    Title,
+    ProductionHouse,
+    /// Names of people credited on the movie, in any role.
+    Cast,
+}
+
+/// Movies matching a search query, most relevant first.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SearchResults {
+    pub query: String,
+    /// Typo-tolerant matching over all movies was used, as full-text search found no movie.
+    pub fuzzy: bool,
+    pub hits: Vec<SearchHit>,
+}
+
+/// Movie matching a search query with its relevance.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub movie_id: u8,
+    pub title: String,
+    pub production_house: String,
+    pub year_of_release: u16,
+    pub verdict: String,
+    /// Relevance, higher is more relevant.
+    pub score: f64,
+    pub matched_fields: Vec<SearchField>,
+}
+
+/// Completion of a partly typed query.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub text: String,
+    pub field: SearchField,
+    /// Movie Id of suggested titles.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub movie_id: Option<u8>,
+}
+
+/// This structure is used to map webhook registration document to rust structure.
+/// Secret is never returned in responses after registration.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    pub events: Vec<String>,
+    pub active: bool,
+    pub created_at: String,
+    #[serde(skip_serializing, default)]
+    pub secret: String,
+}
+
+/// This structure is used to map webhook outbox document to rust structure.
+/// Outbox entry is one pending, delivered or dead-lettered delivery of an event to a webhook.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OutboxEntry {
+    pub id: String,
+    pub webhook_id: String,
+    pub event_type: String,
+    pub payload: String,
+    pub status: String,
+    pub attempts: u32,
+    pub next_attempt_at: String,
+    pub last_error: String,
+    pub created_at: String,
+}
+
+/// This structure is used to map webhook delivery log document to rust structure.
+/// Every delivery attempt is logged.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub webhook_id: String,
+    pub outbox_id: String,
+    pub event_type: String,
+    pub attempt: u32,
+    pub success: bool,
+    pub status_code: Option<u16>,
+    pub error: String,
+    pub duration_ms: u64,
+    pub delivered_at: String,
 }
@@ -0,0 +1,595 @@
+/// Content negotiation.
+/// Responses are rendered as JSON, MessagePack, CBOR, RON or, for list responses, CSV
+/// depending upon 'Accept' header. Request bodies are read in the format given by 'Content-Type' header.
+/// Requests without these headers use JSON. Error responses are always JSON.
+use crate::error::BoxOfficeError;
+use crate::model::{
+    AmountUnit, CollectionEntry, CollectionRun, ConsumerPriceIndex, Credit, ExchangeRate,
+    Filmography, Franchise, FranchiseSummary, Movie, MovieFacets, MovieRelations, OutboxEntry,
+    Person, ProductionHouse, ProductionHouseTrackRecord, ReleaseStatus, Role, SearchHit,
+    SearchResults, Suggestion, TerritoryCollection, Webhook, WebhookDelivery,
+};
+use crate::{constants::*, request_response_structs::*, WebResult};
+use bytes::Buf;
+use rust_decimal::Decimal;
+use serde::{de::DeserializeOwned, Serialize};
+use warp::http::{header::CONTENT_TYPE, Response, StatusCode};
+use warp::{reject, Filter, Rejection};
+
+/// Format of response body chosen from 'Accept' header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseFormat {
+    Json,
+    MessagePack,
+    Cbor,
+    Ron,
+    Csv,
+}
+
+/// Format of request body given by 'Content-Type' header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestFormat {
+    Json,
+    MessagePack,
+    Cbor,
+    Ron,
+}
+
+/// Formats matched by '*/*' and 'application/*', in order of preference.
+const WILDCARD_FORMATS: [ResponseFormat; 4] = [
+    ResponseFormat::Json,
+    ResponseFormat::MessagePack,
+    ResponseFormat::Cbor,
+    ResponseFormat::Ron,
+];
+
+impl ResponseFormat {
+    fn content_type(&self) -> &'static str {
+        match self {
+            ResponseFormat::Json => JSON_CONTENT_TYPE,
+            ResponseFormat::MessagePack => MESSAGEPACK_CONTENT_TYPE,
+            ResponseFormat::Cbor => CBOR_CONTENT_TYPE,
+            ResponseFormat::Ron => RON_CONTENT_TYPE,
+            ResponseFormat::Csv => CSV_CONTENT_TYPE,
+        }
+    }
+
+    /// Maps media range of 'Accept' header to format. CSV is offered only for list responses.
+    fn from_media_range(media_range: &str, is_list: bool) -> Option<Self> {
+        match media_range {
+            ANY_MEDIA_TYPE | ANY_APPLICATION_MEDIA_TYPE | JSON_CONTENT_TYPE => {
+                Some(ResponseFormat::Json)
+            }
+            MESSAGEPACK_CONTENT_TYPE | X_MESSAGEPACK_CONTENT_TYPE => {
+                Some(ResponseFormat::MessagePack)
+            }
+            CBOR_CONTENT_TYPE => Some(ResponseFormat::Cbor),
+            RON_CONTENT_TYPE => Some(ResponseFormat::Ron),
+            CSV_CONTENT_TYPE | ANY_TEXT_MEDIA_TYPE if is_list => Some(ResponseFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+impl RequestFormat {
+    fn from_content_type(content_type: &str) -> Option<Self> {
+        match content_type {
+            JSON_CONTENT_TYPE => Some(RequestFormat::Json),
+            MESSAGEPACK_CONTENT_TYPE | X_MESSAGEPACK_CONTENT_TYPE => {
+                Some(RequestFormat::MessagePack)
+            }
+            CBOR_CONTENT_TYPE => Some(RequestFormat::Cbor),
+            RON_CONTENT_TYPE => Some(RequestFormat::Ron),
+            _ => None,
+        }
+    }
+}
+
+/// Chooses response format from 'Accept' header, rejecting with 406 when no acceptable format is offered.
+/// 'is_list' is set for routes returning list responses, which can also be rendered as CSV.
+pub fn response_format(
+    is_list: bool,
+) -> impl Filter<Extract = (ResponseFormat,), Error = Rejection> + Clone {
+    warp::header::optional::<String>(ACCEPT_HEADER).and_then(
+        move |accept: Option<String>| async move {
+            match accept {
+                None => Ok(ResponseFormat::Json),
+                Some(accept) => negotiate(&accept, is_list)
+                    .ok_or_else(|| reject::custom(BoxOfficeError::NotAcceptableError(accept))),
+            }
+        },
+    )
+}
+
+/// Reads request format from 'Content-Type' header, rejecting with 415 when the format is not supported.
+pub fn request_format() -> impl Filter<Extract = (RequestFormat,), Error = Rejection> + Clone {
+    warp::header::optional::<String>(CONTENT_TYPE_HEADER).and_then(
+        |content_type: Option<String>| async move {
+            match content_type {
+                None => Ok(RequestFormat::Json),
+                Some(content_type) => RequestFormat::from_content_type(&media_type(&content_type))
+                    .ok_or_else(|| {
+                        reject::custom(BoxOfficeError::UnsupportedMediaTypeError(content_type))
+                    }),
+            }
+        },
+    )
+}
+
+/// Picks the supported media range with the highest quality, earlier ranges win ties.
+/// Wildcards match the first format, in order of 'WILDCARD_FORMATS', which is not refused with 'q=0'.
+fn negotiate(accept: &str, is_list: bool) -> Option<ResponseFormat> {
+    let media_ranges: Vec<(String, f32)> = accept
+        .split(',')
+        .map(|media_range| {
+            let mut parameters = media_range.split(';');
+            let media_type = parameters.next().unwrap_or_default().trim().to_lowercase();
+            let quality = parameters
+                .filter_map(|parameter| parameter.trim().strip_prefix("q="))
+                .find_map(|quality| quality.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (media_type, quality)
+        })
+        .collect();
+    let refused_formats: Vec<ResponseFormat> = media_ranges
+        .iter()
+        .filter(|(media_type, quality)| *quality <= 0.0 && !media_type.contains('*'))
+        .filter_map(|(media_type, _)| ResponseFormat::from_media_range(media_type, is_list))
+        .collect();
+    let mut best: Option<(ResponseFormat, f32)> = None;
+    for (media_type, quality) in &media_ranges {
+        if *quality <= 0.0 {
+            continue;
+        }
+        let format = match ResponseFormat::from_media_range(media_type, is_list) {
+            Some(ResponseFormat::Json) if media_type.contains('*') => WILDCARD_FORMATS
+                .into_iter()
+                .find(|format| !refused_formats.contains(format)),
+            format => format,
+        };
+        if let Some(format) = format {
+            if best.is_none_or(|(_, best_quality)| *quality > best_quality) {
+                best = Some((format, *quality));
+            }
+        }
+    }
+    return best.map(|(format, _)| format);
+}
+
+/// Returns media type without parameters, e.g. 'application/json' for 'application/json; charset=utf-8'.
+fn media_type(content_type: &str) -> String {
+    return content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+}
+
+/// Deserializes request body in the given format.
+/// Error message names the field which could not be read, where the format allows.
+pub fn deserialize_body<T: DeserializeOwned>(
+    mut buf: impl Buf,
+    format: RequestFormat,
+) -> std::result::Result<T, String> {
+    let body = buf.copy_to_bytes(buf.remaining());
+    match format {
+        RequestFormat::Json => {
+            let des = &mut serde_json::Deserializer::from_slice(&body);
+            return serde_path_to_error::deserialize(des).map_err(|e| e.to_string());
+        }
+        RequestFormat::MessagePack => {
+            let des = &mut rmp_serde::Deserializer::from_read_ref(&body[..]);
+            return serde_path_to_error::deserialize(des).map_err(|e| e.to_string());
+        }
+        RequestFormat::Cbor => {
+            return ciborium::de::from_reader(&body[..]).map_err(|e| e.to_string());
+        }
+        RequestFormat::Ron => {
+            let des = &mut ron::Deserializer::from_bytes(&body).map_err(|e| e.to_string())?;
+            return serde_path_to_error::deserialize(des).map_err(|e| e.to_string());
+        }
+    }
+}
+
+/// Response which can be rendered in every negotiated format.
+/// List responses override 'to_csv' to be rendered as CSV.
+pub trait NegotiatedResponse: Serialize {
+    /// Renders list as CSV with one row per item, None for responses which are not lists.
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        None
+    }
+}
+
+/// Renders response in the negotiated format with the given status code.
+pub fn reply<T: NegotiatedResponse>(
+    response: &T,
+    format: ResponseFormat,
+    status: StatusCode,
+) -> WebResult<warp::reply::Response> {
+    let body = match format {
+        ResponseFormat::Json => serde_json::to_vec(response).map_err(|e| e.to_string()),
+        ResponseFormat::MessagePack => rmp_serde::to_vec_named(response).map_err(|e| e.to_string()),
+        ResponseFormat::Cbor => {
+            let mut body = Vec::new();
+            ciborium::ser::into_writer(response, &mut body)
+                .map(|_| body)
+                .map_err(|e| e.to_string())
+        }
+        ResponseFormat::Ron => ron::ser::to_string(response)
+            .map(String::into_bytes)
+            .map_err(|e| e.to_string()),
+        ResponseFormat::Csv => match response.to_csv() {
+            Some(body) => body,
+            None => {
+                return Err(reject::custom(BoxOfficeError::NotAcceptableError(
+                    CSV_CONTENT_TYPE.to_string(),
+                )))
+            }
+        },
+    }
+    .map_err(|e| reject::custom(BoxOfficeError::ResponseSerializationError(e)))?;
+    let response = Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, format.content_type())
+        .body(body.into())
+        .map_err(|e| reject::custom(BoxOfficeError::ResponseSerializationError(e.to_string())))?;
+    return Ok(response);
+}
+
+/// Writes rows as CSV with header row.
+fn write_csv<R: Serialize>(
+    rows: impl IntoIterator<Item = R>,
+) -> std::result::Result<Vec<u8>, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        writer.serialize(row).map_err(|e| e.to_string())?;
+    }
+    return writer.into_inner().map_err(|e| e.to_string());
+}
+
+/// CSV row of a webhook. Events are separated by ';' as CSV cells can not hold lists.
+#[derive(Serialize)]
+struct WebhookCsvRow<'a> {
+    id: &'a str,
+    url: &'a str,
+    events: String,
+    active: bool,
+    created_at: &'a str,
+}
+
+impl<'a> From<&'a Webhook> for WebhookCsvRow<'a> {
+    fn from(webhook: &'a Webhook) -> Self {
+        Self {
+            id: &webhook.id,
+            url: &webhook.url,
+            events: webhook.events.join(CSV_LIST_SEPARATOR),
+            active: webhook.active,
+            created_at: &webhook.created_at,
+        }
+    }
+}
+
+/// CSV row of a production house. Aliases are separated by ';' as CSV cells can not hold lists.
+#[derive(Serialize)]
+struct ProductionHouseCsvRow<'a> {
+    id: &'a str,
+    name: &'a str,
+    aliases: String,
+    created_at: &'a str,
+}
+
+impl<'a> From<&'a ProductionHouse> for ProductionHouseCsvRow<'a> {
+    fn from(production_house: &'a ProductionHouse) -> Self {
+        Self {
+            id: &production_house.id,
+            name: &production_house.name,
+            aliases: production_house.aliases.join(CSV_LIST_SEPARATOR),
+            created_at: &production_house.created_at,
+        }
+    }
+}
+
+/// CSV row of a franchise. Movie Ids of instalments are separated by ';' as CSV cells can not hold lists.
+#[derive(Serialize)]
+struct FranchiseCsvRow<'a> {
+    id: &'a str,
+    name: &'a str,
+    movie_ids: String,
+    created_at: &'a str,
+}
+
+impl<'a> From<&'a Franchise> for FranchiseCsvRow<'a> {
+    fn from(franchise: &'a Franchise) -> Self {
+        Self {
+            id: &franchise.id,
+            name: &franchise.name,
+            movie_ids: franchise
+                .movie_ids
+                .iter()
+                .map(u8::to_string)
+                .collect::<Vec<String>>()
+                .join(CSV_LIST_SEPARATOR),
+            created_at: &franchise.created_at,
+        }
+    }
+}
+
+/// CSV row of a credit. Character is left empty for credits without one so that every row has the same columns.
+#[derive(Serialize)]
+struct CreditCsvRow<'a> {
+    person_id: &'a str,
+    name: &'a str,
+    role: Role,
+    character: &'a str,
+}
+
+impl<'a> From<&'a Credit> for CreditCsvRow<'a> {
+    fn from(credit: &'a Credit) -> Self {
+        Self {
+            person_id: &credit.person_id,
+            name: &credit.name,
+            role: credit.role,
+            character: credit.character.as_deref().unwrap_or_default(),
+        }
+    }
+}
+
+/// CSV row of a search hit. Matched fields are separated by ';' as CSV cells can not hold lists.
+#[derive(Serialize)]
+struct SearchHitCsvRow<'a> {
+    movie_id: u8,
+    title: &'a str,
+    production_house: &'a str,
+    year_of_release: u16,
+    verdict: &'a str,
+    score: f64,
+    matched_fields: String,
+}
+
+impl<'a> From<&'a SearchHit> for SearchHitCsvRow<'a> {
+    fn from(hit: &'a SearchHit) -> Self {
+        Self {
+            movie_id: hit.movie_id,
+            title: &hit.title,
+            production_house: &hit.production_house,
+            year_of_release: hit.year_of_release,
+            verdict: &hit.verdict,
+            score: hit.score,
+            matched_fields: hit
+                .matched_fields
+                .iter()
+                .map(|field| field.to_string())
+                .collect::<Vec<String>>()
+                .join(CSV_LIST_SEPARATOR),
+        }
+    }
+}
+
+/// CSV row of a suggestion. Movie Id is left empty for production houses and people so that every row has the same columns.
+#[derive(Serialize)]
+struct SuggestionCsvRow<'a> {
+    text: &'a str,
+    field: String,
+    movie_id: Option<u8>,
+}
+
+impl<'a> From<&'a Suggestion> for SuggestionCsvRow<'a> {
+    fn from(suggestion: &'a Suggestion) -> Self {
+        Self {
+            text: &suggestion.text,
+            field: suggestion.field.to_string(),
+            movie_id: suggestion.movie_id,
+        }
+    }
+}
+
+/// CSV row of a movie. Financial figures are flattened into columns as CSV cells can not hold nested values.
+/// Financial and requested amount columns are written only for movies with financials or requested amounts, figures not computed are left empty
+/// so that every row has the same columns.
+#[derive(Serialize)]
+struct MovieCsvRow<'a> {
+    id: &'a str,
+    movie_id: u8,
+    title: &'a str,
+    production_house: &'a str,
+    production_house_id: Option<&'a str>,
+    year_of_release: u16,
+    release_date: Option<&'a str>,
+    status: ReleaseStatus,
+    budget_crores: Decimal,
+    collection_crores: Decimal,
+    verdict: &'a str,
+    verdict_provisional: bool,
+    genres: String,
+    languages: String,
+    certification: Option<&'a str>,
+    runtime_minutes: Option<u16>,
+    tags: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profit_crores: Option<Option<Decimal>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profit_percentage: Option<Option<Decimal>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    roi_multiple: Option<Option<Decimal>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    break_even_gap_crores: Option<Option<Decimal>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requested_currency: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requested_unit: Option<AmountUnit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requested_budget: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requested_collection: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inflation_base_year: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    adjusted_budget_crores: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    adjusted_collection_crores: Option<Decimal>,
+}
+
+impl<'a> From<&'a Movie> for MovieCsvRow<'a> {
+    fn from(movie: &'a Movie) -> Self {
+        let financials = movie.financials.as_ref();
+        let requested_amounts = movie.requested_amounts.as_ref();
+        let inflation_adjusted = movie.inflation_adjusted.as_ref();
+        Self {
+            id: &movie.id,
+            movie_id: movie.movie_id,
+            title: &movie.title,
+            production_house: &movie.production_house,
+            production_house_id: movie.production_house_id.as_deref(),
+            year_of_release: movie.year_of_release,
+            release_date: movie.release_date.as_deref(),
+            status: movie.status,
+            budget_crores: movie.budget_crores,
+            collection_crores: movie.collection_crores,
+            verdict: &movie.verdict,
+            verdict_provisional: movie.verdict_provisional,
+            genres: movie.metadata.genres.join(CSV_LIST_SEPARATOR),
+            languages: movie.metadata.languages.join(CSV_LIST_SEPARATOR),
+            certification: movie.metadata.certification.as_deref(),
+            runtime_minutes: movie.metadata.runtime_minutes,
+            tags: movie.metadata.tags.join(CSV_LIST_SEPARATOR),
+            profit_crores: financials.map(|financials| financials.profit_crores),
+            profit_percentage: financials.map(|financials| financials.profit_percentage),
+            roi_multiple: financials.map(|financials| financials.roi_multiple),
+            break_even_gap_crores: financials.map(|financials| financials.break_even_gap_crores),
+            requested_currency: requested_amounts.map(|amounts| amounts.currency.as_str()),
+            requested_unit: requested_amounts.map(|amounts| amounts.unit),
+            requested_budget: requested_amounts.map(|amounts| amounts.budget),
+            requested_collection: requested_amounts.map(|amounts| amounts.collection),
+            inflation_base_year: inflation_adjusted.map(|amounts| amounts.base_year),
+            adjusted_budget_crores: inflation_adjusted.map(|amounts| amounts.budget_crores),
+            adjusted_collection_crores: inflation_adjusted.map(|amounts| amounts.collection_crores),
+        }
+    }
+}
+
+impl NegotiatedResponse for Movie {}
+impl NegotiatedResponse for CreateMovieResponse {}
+impl NegotiatedResponse for UpdateMovieResponse {}
+impl NegotiatedResponse for DeleteMovieResponse {}
+impl NegotiatedResponse for DatabaseOperationFailedErrorResponse {}
+impl NegotiatedResponse for HealthResponse {}
+impl NegotiatedResponse for CreateWebhookResponse {}
+impl NegotiatedResponse for DeleteWebhookResponse {}
+impl NegotiatedResponse for RetryDeadLetterResponse {}
+impl NegotiatedResponse for UpsertExchangeRatesResponse {}
+impl NegotiatedResponse for UpsertCpiResponse {}
+impl NegotiatedResponse for CollectionEntry {}
+impl NegotiatedResponse for CollectionRun {}
+impl NegotiatedResponse for ProductionHouse {}
+impl NegotiatedResponse for ProductionHouseTrackRecord {}
+impl NegotiatedResponse for Person {}
+impl NegotiatedResponse for Filmography {}
+impl NegotiatedResponse for Vec<Role> {}
+impl NegotiatedResponse for MovieFacets {}
+impl NegotiatedResponse for Franchise {}
+impl NegotiatedResponse for MovieRelations {}
+
+/// CSV of franchise summary lists its instalments, the per-instalment trend.
+impl NegotiatedResponse for FranchiseSummary {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv(&self.instalments))
+    }
+}
+
+/// CSV of search results lists the hits, most relevant first.
+impl NegotiatedResponse for SearchResults {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv(self.hits.iter().map(SearchHitCsvRow::from)))
+    }
+}
+
+impl NegotiatedResponse for Vec<Suggestion> {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv(self.iter().map(SuggestionCsvRow::from)))
+    }
+}
+
+impl NegotiatedResponse for Vec<Franchise> {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv(self.iter().map(FranchiseCsvRow::from)))
+    }
+}
+
+impl NegotiatedResponse for ReadMoviesResponse {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv(self.data.iter().map(MovieCsvRow::from)))
+    }
+}
+
+impl NegotiatedResponse for Vec<Movie> {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv(self.iter().map(MovieCsvRow::from)))
+    }
+}
+
+impl NegotiatedResponse for Vec<CollectionEntry> {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv(self))
+    }
+}
+
+impl NegotiatedResponse for Vec<TerritoryCollection> {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv(self))
+    }
+}
+
+impl NegotiatedResponse for Vec<ProductionHouse> {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv(self.iter().map(ProductionHouseCsvRow::from)))
+    }
+}
+
+impl NegotiatedResponse for Vec<ProductionHouseTrackRecord> {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv(self))
+    }
+}
+
+impl NegotiatedResponse for Vec<Person> {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv(self))
+    }
+}
+
+impl NegotiatedResponse for Vec<Credit> {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv(self.iter().map(CreditCsvRow::from)))
+    }
+}
+
+impl NegotiatedResponse for ReadWebhooksResponse {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv(self.data.iter().map(WebhookCsvRow::from)))
+    }
+}
+
+impl NegotiatedResponse for ReadWebhookDeliveriesResponse {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv::<&WebhookDelivery>(&self.data))
+    }
+}
+
+impl NegotiatedResponse for ReadDeadLettersResponse {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv::<&OutboxEntry>(&self.data))
+    }
+}
+
+impl NegotiatedResponse for ReadExchangeRatesResponse {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv::<&ExchangeRate>(&self.data))
+    }
+}
+
+impl NegotiatedResponse for ReadCpiResponse {
+    fn to_csv(&self) -> Option<std::result::Result<Vec<u8>, String>> {
+        Some(write_csv::<&ConsumerPriceIndex>(&self.data))
+    }
+}
@@ -0,0 +1,107 @@
+/// Cast and crew of movies.
+/// People are credited on movies in roles; a person may hold several roles, even on the same movie.
+/// Filmography of a person lists the credited movies with verdicts from the verdict engine
+/// and the success rate over the released ones.
+use crate::business_layer::{hit_ratio, is_hit};
+use crate::error::BoxOfficeError;
+use crate::model::{Filmography, FilmographyEntry, Movie, Person, Role};
+use crate::release;
+use crate::Result;
+use std::fmt;
+use std::str::FromStr;
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let role = match self {
+            Role::Actor => "actor",
+            Role::Director => "director",
+            Role::MusicDirector => "music_director",
+            Role::Producer => "producer",
+        };
+        return write!(f, "{}", role);
+    }
+}
+
+impl FromStr for Role {
+    type Err = BoxOfficeError;
+
+    fn from_str(role: &str) -> Result<Self> {
+        match role {
+            "actor" => Ok(Role::Actor),
+            "director" => Ok(Role::Director),
+            "music_director" => Ok(Role::MusicDirector),
+            "producer" => Ok(Role::Producer),
+            _ => Err(BoxOfficeError::InvalidDocumentError(format!(
+                "unknown role '{}'",
+                role
+            ))),
+        }
+    }
+}
+
+/// All roles, in the order they are listed.
+pub const ROLES: [Role; 4] = [
+    Role::Actor,
+    Role::Director,
+    Role::MusicDirector,
+    Role::Producer,
+];
+
+/// Returns filmography of person over the movies crediting them, limited to 'role' when given.
+/// Movies are counted once even when the person has several roles in them.
+pub fn filmography(person: &Person, movies: &[Movie], role: Option<Role>) -> Result<Filmography> {
+    let mut entries: Vec<FilmographyEntry> = Vec::new();
+    let mut movie_count = 0;
+    let mut released_movie_count = 0;
+    let mut hit_count = 0;
+    for movie in movies {
+        let credits: Vec<_> = movie
+            .credits
+            .iter()
+            .flatten()
+            .filter(|credit| {
+                credit.person_id == person.id
+                    && role.map(|role| role == credit.role).unwrap_or(true)
+            })
+            .collect();
+        if credits.is_empty() {
+            continue;
+        }
+        let verdict = release::verdict(movie.status, movie.budget_crores, movie.collection_crores)?;
+        movie_count += 1;
+        if movie.status.is_released() {
+            released_movie_count += 1;
+            if is_hit(&verdict) {
+                hit_count += 1;
+            }
+        }
+        for credit in credits {
+            entries.push(FilmographyEntry {
+                movie_id: movie.movie_id,
+                title: movie.title.clone(),
+                year_of_release: movie.year_of_release,
+                role: credit.role,
+                character: credit.character.clone(),
+                status: movie.status,
+                verdict: verdict.clone(),
+                verdict_provisional: movie.status.is_verdict_provisional(),
+            });
+        }
+    }
+    entries.sort_by(|a, b| {
+        b.year_of_release
+            .cmp(&a.year_of_release)
+            .then(b.movie_id.cmp(&a.movie_id))
+            .then(a.role.cmp(&b.role))
+    });
+    return Ok(Filmography {
+        person_id: person.id.clone(),
+        name: person.name.clone(),
+        role,
+        movie_count,
+        released_movie_count,
+        hit_count,
+        success_rate: hit_ratio(hit_count, released_movie_count),
+        movies: entries,
+    });
+}
@@ -0,0 +1,84 @@
+/// Production houses of movies.
+/// Names are compared by their key, i.e. lowercase letters and digits only, so that 'RGV Films'
+/// and 'R.G.V. Films' are the same production house. Aliases cover other spellings of the name.
+use crate::business_layer::{hit_ratio, is_hit};
+use crate::model::{Movie, ProductionHouse, ProductionHouseTrackRecord};
+use rust_decimal::Decimal;
+
+/// Returns key of production house name, lowercase letters and digits of the name.
+pub fn name_key(name: &str) -> String {
+    return name
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect();
+}
+
+/// Returns keys of name and aliases of a production house, each key once.
+pub fn name_keys(name: &str, aliases: &[String]) -> Vec<String> {
+    let mut keys: Vec<String> = std::iter::once(name)
+        .chain(aliases.iter().map(String::as_str))
+        .map(name_key)
+        .collect();
+    keys.sort();
+    keys.dedup();
+    return keys;
+}
+
+/// Returns aliases of production house merged with names and aliases of other production houses.
+/// Aliases spelled like the name or an earlier alias are left out.
+pub fn merged_aliases(
+    production_house: &ProductionHouse,
+    others: &[ProductionHouse],
+) -> Vec<String> {
+    let mut keys = vec![name_key(&production_house.name)];
+    let mut aliases: Vec<String> = Vec::new();
+    let names = production_house.aliases.iter().chain(
+        others
+            .iter()
+            .flat_map(|other| std::iter::once(&other.name).chain(other.aliases.iter())),
+    );
+    for alias in names {
+        let key = name_key(alias);
+        if !keys.contains(&key) {
+            keys.push(key);
+            aliases.push(alias.clone());
+        }
+    }
+    return aliases;
+}
+
+/// Returns track record of production house over its movies.
+/// Budget, collection and profit are totals of released movies only, as others have no collection yet.
+pub fn track_record(
+    production_house: &ProductionHouse,
+    movies: &[Movie],
+) -> ProductionHouseTrackRecord {
+    let released_movies: Vec<&Movie> = movies
+        .iter()
+        .filter(|movie| movie.status.is_released())
+        .collect();
+    let hit_count = released_movies
+        .iter()
+        .filter(|movie| is_hit(&movie.verdict))
+        .count();
+    let total_budget_crores: Decimal = released_movies
+        .iter()
+        .map(|movie| movie.budget_crores)
+        .sum();
+    let total_collection_crores: Decimal = released_movies
+        .iter()
+        .map(|movie| movie.collection_crores)
+        .sum();
+    return ProductionHouseTrackRecord {
+        production_house_id: production_house.id.clone(),
+        name: production_house.name.clone(),
+        movie_count: movies.len(),
+        released_movie_count: released_movies.len(),
+        hit_count,
+        hit_ratio: hit_ratio(hit_count, released_movies.len()),
+        total_budget_crores,
+        total_collection_crores,
+        total_profit_crores: total_collection_crores - total_budget_crores,
+    };
+}
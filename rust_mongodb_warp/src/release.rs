@@ -0,0 +1,172 @@
+/// Release lifecycle of movies.
+/// Movies move forward through announced, in-production, released, running and closed.
+/// Collections are recorded only once a movie is released, its verdict stays provisional until the run is closed.
+/// Year of release is checked against the current date, so that upcoming movies can be registered.
+use crate::business_layer::calculate_verdict;
+use crate::collection_run::{date_of_day, day_number};
+use crate::error::BoxOfficeError;
+use crate::model::{Movie, ReleaseStatus};
+use crate::{constants::*, Result};
+use mongodb::bson::DateTime;
+use rust_decimal::Decimal;
+use std::fmt;
+use std::str::FromStr;
+
+impl ReleaseStatus {
+    /// Returns whether the movie has reached theatres, i.e. it can have collection.
+    pub fn is_released(&self) -> bool {
+        *self >= ReleaseStatus::Released
+    }
+
+    /// Returns whether collections of the movie can be recorded, i.e. it is released and its run is not closed.
+    pub fn is_collecting(&self) -> bool {
+        matches!(self, ReleaseStatus::Released | ReleaseStatus::Running)
+    }
+
+    /// Returns whether verdict of the movie may still change.
+    pub fn is_verdict_provisional(&self) -> bool {
+        *self != ReleaseStatus::Closed
+    }
+}
+
+impl fmt::Display for ReleaseStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            ReleaseStatus::Announced => "announced",
+            ReleaseStatus::InProduction => "in-production",
+            ReleaseStatus::Released => "released",
+            ReleaseStatus::Running => "running",
+            ReleaseStatus::Closed => "closed",
+        };
+        return write!(f, "{}", status);
+    }
+}
+
+impl FromStr for ReleaseStatus {
+    type Err = BoxOfficeError;
+
+    fn from_str(status: &str) -> Result<Self> {
+        match status {
+            "announced" => Ok(ReleaseStatus::Announced),
+            "in-production" => Ok(ReleaseStatus::InProduction),
+            "released" => Ok(ReleaseStatus::Released),
+            "running" => Ok(ReleaseStatus::Running),
+            "closed" => Ok(ReleaseStatus::Closed),
+            _ => Err(BoxOfficeError::InvalidDocumentError(format!(
+                "unknown release status '{}'",
+                status
+            ))),
+        }
+    }
+}
+
+/// Returns today's date in UTC, 'YYYY-MM-DD'.
+pub fn today() -> String {
+    return date_of_day(
+        DateTime::now()
+            .timestamp_millis()
+            .div_euclid(MILLISECONDS_IN_DAY),
+    );
+}
+
+/// Returns the current year in UTC.
+pub fn current_year() -> u16 {
+    return today()[..4].parse().unwrap_or(MINIMUM_YEAR_OF_RELEASE);
+}
+
+/// Returns the latest year of release which can be registered.
+pub fn maximum_year_of_release() -> u16 {
+    return current_year() + MAXIMUM_YEARS_UNTIL_RELEASE;
+}
+
+/// Checks that movie can move from 'status' to 'next_status'.
+/// Status only moves forward, stages may be skipped, e.g. a movie can be announced as released.
+/// Closed run is final.
+pub fn check_transition(status: ReleaseStatus, next_status: ReleaseStatus) -> Result<()> {
+    if next_status < status {
+        return Err(BoxOfficeError::InvalidStatusTransitionError(format!(
+            "{} movie can not become {}",
+            status, next_status
+        )));
+    }
+    return Ok(());
+}
+
+/// Checks release date, year of release and collection of movie against its status.
+/// Release date falls in year of release. Released movies are not released in future,
+/// movies which are not released yet have no collection.
+pub fn check_release(
+    status: ReleaseStatus,
+    release_date: Option<&str>,
+    year_of_release: u16,
+    collection_crores: Decimal,
+) -> Result<()> {
+    if let Some(release_date) = release_date {
+        day_number(release_date).map_err(|_| {
+            BoxOfficeError::ReleaseLifecycleError(format!("no such date '{}'", release_date))
+        })?;
+        if !release_date.starts_with(&format!("{:04}-", year_of_release)) {
+            return Err(BoxOfficeError::ReleaseLifecycleError(format!(
+                "release date {} is not in year of release {}",
+                release_date, year_of_release
+            )));
+        }
+        if status.is_released() && release_date > today().as_str() {
+            return Err(BoxOfficeError::ReleaseLifecycleError(format!(
+                "{} movie can not have release date {} in future",
+                status, release_date
+            )));
+        }
+    }
+    if status.is_released() && year_of_release > current_year() {
+        return Err(BoxOfficeError::ReleaseLifecycleError(format!(
+            "{} movie can not have year of release {} in future",
+            status, year_of_release
+        )));
+    }
+    if !status.is_released() && collection_crores != Decimal::ZERO {
+        return Err(BoxOfficeError::ReleaseLifecycleError(format!(
+            "{}, movie is {}",
+            COLLECTION_BEFORE_RELEASE_ERROR, status
+        )));
+    }
+    return Ok(());
+}
+
+/// Returns verdict of movie with its status, movies which are not released yet have none.
+pub fn verdict(
+    status: ReleaseStatus,
+    budget_crores: Decimal,
+    collection_crores: Decimal,
+) -> Result<String> {
+    if !status.is_released() {
+        return Ok(NOT_RELEASED_VERDICT.to_string());
+    }
+    return calculate_verdict(budget_crores, collection_crores);
+}
+
+/// Checks that collections of movie can be recorded or removed.
+pub fn check_collecting(movie: &Movie) -> Result<()> {
+    if !movie.status.is_collecting() {
+        return Err(BoxOfficeError::ReleaseLifecycleError(format!(
+            "collections are recorded only for released or running movies, Movie ID {} is {}",
+            movie.movie_id, movie.status
+        )));
+    }
+    return Ok(());
+}
+
+/// Checks that collection of movie on the 'YYYY-MM-DD' date can be recorded,
+/// i.e. movie is collecting and the date is not before its release date.
+pub fn check_collection_entry(movie: &Movie, date: &str) -> Result<()> {
+    check_collecting(movie)?;
+    if let Some(release_date) = &movie.release_date {
+        if date < release_date.as_str() {
+            return Err(BoxOfficeError::ReleaseLifecycleError(format!(
+                "collection on {} is before release date {}",
+                date, release_date
+            )));
+        }
+    }
+    return Ok(());
+}
@@ -0,0 +1,165 @@
+/// Request id propagation and access logging.
+/// Every request gets a request id taken from 'X-Request-Id' header or generated.
+/// The id is available to all log calls made while the request is handled
+/// and is returned in 'X-Request-Id' response header.
+use crate::{constants::*, telemetry};
+use hyper::header::{HeaderMap, HeaderValue};
+use hyper::service::Service;
+use hyper::{Body, Request, Response};
+use log4rs::config::{Deserialize, Deserializers};
+use log4rs::encode::{json::JsonEncoder, Encode, Write};
+use serde::Deserialize as SerdeDeserialize;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::time::Instant;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+tokio::task_local! {
+    /// Request id of the request handled by the current task.
+    pub static REQUEST_ID: String;
+}
+
+/// Returns request id of the request handled by the current task, if any.
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|request_id| request_id.clone()).ok()
+}
+
+/// Accepts incoming request id if it is reasonably short and printable, otherwise generates one.
+fn request_id_from_headers(headers: &HeaderMap) -> String {
+    headers
+        .get(X_REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+        .map(|value| value.to_string())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+/// Returns whether request id is not empty, at most 'MAXIMUM_REQUEST_ID_LENGTH' long
+/// and made of letters, digits, '-', '_', '.' and ':' only, so that it can not inject into logs.
+pub fn is_valid_request_id(request_id: &str) -> bool {
+    !request_id.is_empty()
+        && request_id.len() <= MAXIMUM_REQUEST_ID_LENGTH
+        && request_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.:".contains(c))
+}
+
+/// Converts headers to a map for logging, hiding values of credential carrying headers.
+pub fn redacted_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if SENSITIVE_HEADERS.contains(&name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                value.to_str().unwrap_or(NON_PRINTABLE_HEADER).to_string()
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+/// Handles a request through warp service within request id scope.
+/// Request span continues trace from incoming 'traceparent' header.
+/// Logs method, path, status, elapsed time, remote address and redacted headers.
+pub async fn handle<S>(
+    mut service: S,
+    remote_address: Option<SocketAddr>,
+    mut request: Request<Body>,
+) -> std::result::Result<Response<Body>, Infallible>
+where
+    S: Service<Request<Body>, Response = Response<Body>, Error = Infallible>,
+{
+    let request_id = request_id_from_headers(request.headers());
+    let request_id_header = HeaderValue::from_str(&request_id).unwrap();
+    request
+        .headers_mut()
+        .insert(X_REQUEST_ID, request_id_header.clone());
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let headers = redacted_headers(request.headers());
+    let start = Instant::now();
+    let request_span = tracing::info_span!(
+        "request",
+        http.method = %method,
+        http.target = %path,
+        request_id = %request_id,
+        http.status_code = tracing::field::Empty,
+    );
+    request_span.set_parent(telemetry::extract_context(request.headers()));
+
+    REQUEST_ID
+        .scope(
+            request_id,
+            async move {
+                let mut response = service.call(request).await?;
+                tracing::Span::current().record("http.status_code", response.status().as_u16());
+                response
+                    .headers_mut()
+                    .insert(X_REQUEST_ID, request_id_header);
+                log::info!(
+                    "{} {} {} {:?} from {:?} with {:?}",
+                    method,
+                    path,
+                    response.status(),
+                    start.elapsed(),
+                    remote_address,
+                    headers
+                );
+                Ok(response)
+            }
+            .instrument(request_span),
+        )
+        .await
+}
+
+/// JSON log encoder which adds request id of the current request to 'mdc' field.
+#[derive(Debug, Default)]
+pub struct RequestJsonEncoder {
+    json_encoder: JsonEncoder,
+}
+
+impl Encode for RequestJsonEncoder {
+    fn encode(&self, w: &mut dyn Write, record: &log::Record) -> anyhow::Result<()> {
+        match current_request_id() {
+            Some(request_id) => {
+                log_mdc::insert(REQUEST_ID_LOG_FIELD, request_id);
+                let result = self.json_encoder.encode(w, record);
+                log_mdc::remove(REQUEST_ID_LOG_FIELD);
+                result
+            }
+            None => self.json_encoder.encode(w, record),
+        }
+    }
+}
+
+/// Configuration of request json encoder, it has no options.
+#[derive(SerdeDeserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RequestJsonEncoderConfig {}
+
+/// Makes request json encoder available in log configuration file as 'kind: request_json'.
+pub struct RequestJsonEncoderDeserializer;
+
+impl Deserialize for RequestJsonEncoderDeserializer {
+    type Trait = dyn Encode;
+
+    type Config = RequestJsonEncoderConfig;
+
+    fn deserialize(
+        &self,
+        _: RequestJsonEncoderConfig,
+        _: &Deserializers,
+    ) -> anyhow::Result<Box<dyn Encode>> {
+        Ok(Box::<RequestJsonEncoder>::default())
+    }
+}
+
+/// Log deserializers including request json encoder.
+pub fn log_deserializers() -> Deserializers {
+    let mut deserializers = Deserializers::default();
+    deserializers.insert(REQUEST_JSON_ENCODER, RequestJsonEncoderDeserializer);
+    return deserializers;
+}
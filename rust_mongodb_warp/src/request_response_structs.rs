@@ -1,3 +1,7 @@
+use crate::business_layer::FinancialFields;
+use crate::error::BoxOfficeError;
+use crate::production_house;
+use crate::release::maximum_year_of_release;
 use crate::{constants::*, model::*};
 /// Definitions for request-response structures and custom validation functions.
 use rust_decimal::prelude::*;
@@ -7,35 +11,287 @@ use validator::{Validate, ValidationError};
 ///Request Structure to read Create Movie Request
 #[derive(Serialize, Deserialize, Debug, Validate)]
 #[serde(rename_all = "camelCase")]
+#[validate(schema(function = "validate_create_movie_release"))]
 pub struct CreateMovieRequest {
     #[validate(custom = "validate_movie_id")]
     pub movie_id: u8,
     #[validate(length(min = 1))]
     pub title: String,
-    #[validate(length(min = 1))]
+    #[validate(custom = "validate_production_house_name")]
     pub production_house: String,
     #[validate(custom = "validate_year_of_release")]
     pub year_of_release: u16,
+    /// Release date 'YYYY-MM-DD', in year of release.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(custom = "validate_date")]
+    pub release_date: Option<String>,
+    /// Release status, released when not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ReleaseStatus>,
+    /// Budget in 'currency' and 'unit', INR crores when they are not given.
     #[validate(custom = "validate_budget_greater_than_zero")]
     pub budget_crores: Decimal,
-    #[validate(custom = "validate_collection_greater_than_zero")]
+    /// Collection in 'currency' and 'unit', INR crores when they are not given.
+    /// Greater than zero for released movies, zero for movies not released yet.
+    #[validate(custom = "validate_collection_not_negative")]
     pub collection_crores: Decimal,
+    /// ISO 4217 currency code of budget and collection, INR when not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(custom = "validate_currency")]
+    pub currency: Option<String>,
+    /// Unit of budget and collection, crore when not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<AmountUnit>,
+    /// Date of exchange rate used to convert amounts to INR, 'YYYY-MM-DD'. Latest rate when not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(custom = "validate_date")]
+    pub exchange_rate_date: Option<String>,
+    /// Genres, languages, certification, runtime and tags, none when not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub metadata: Option<MovieMetadataRequest>,
 }
 
 ///Request Structure to read Update Movie Request
 #[derive(Serialize, Deserialize, Debug, Validate)]
 #[serde(rename_all = "camelCase")]
+#[validate(schema(function = "validate_update_movie_release"))]
 pub struct UpdateMovieRequest {
     #[validate(length(min = 1))]
     pub title: String,
-    #[validate(length(min = 1))]
+    #[validate(custom = "validate_production_house_name")]
     pub production_house: String,
     #[validate(custom = "validate_year_of_release")]
     pub year_of_release: u16,
+    /// Release date 'YYYY-MM-DD', in year of release. Kept when not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(custom = "validate_date")]
+    pub release_date: Option<String>,
+    /// Release status, kept when not given. Status only moves forward.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ReleaseStatus>,
+    /// Budget in 'currency' and 'unit', INR crores when they are not given.
     #[validate(custom = "validate_budget_greater_than_zero")]
     pub budget_crores: Decimal,
-    #[validate(custom = "validate_collection_greater_than_zero")]
+    /// Collection in 'currency' and 'unit', INR crores when they are not given.
+    /// Greater than zero for released movies, zero for movies not released yet.
+    #[validate(custom = "validate_collection_not_negative")]
     pub collection_crores: Decimal,
+    /// ISO 4217 currency code of budget and collection, INR when not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(custom = "validate_currency")]
+    pub currency: Option<String>,
+    /// Unit of budget and collection, crore when not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<AmountUnit>,
+    /// Date of exchange rate used to convert amounts to INR, 'YYYY-MM-DD'. Latest rate when not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(custom = "validate_date")]
+    pub exchange_rate_date: Option<String>,
+    /// Genres, languages, certification, runtime and tags, kept when not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub metadata: Option<MovieMetadataRequest>,
+}
+
+///Request Structure to read Patch Movie Request.
+/// Only given fields are changed, Movie Id and verdict can not be patched.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PatchMovieRequest {
+    pub title: Option<String>,
+    pub production_house: Option<String>,
+    pub year_of_release: Option<u16>,
+    pub release_date: Option<String>,
+    pub status: Option<ReleaseStatus>,
+    pub budget_crores: Option<Decimal>,
+    pub collection_crores: Option<Decimal>,
+    pub currency: Option<String>,
+    pub unit: Option<AmountUnit>,
+    pub exchange_rate_date: Option<String>,
+    pub metadata: Option<MovieMetadataRequest>,
+}
+
+impl PatchMovieRequest {
+    /// Returns Update Movie Request with patched fields and remaining fields of the movie.
+    /// Amounts which are not patched keep their reported currency and unit,
+    /// so both amounts must be given to change currency or unit.
+    pub fn apply_to(
+        self,
+        movie: &Movie,
+    ) -> std::result::Result<UpdateMovieRequest, BoxOfficeError> {
+        let reported_amounts = movie.reported_amounts.clone().unwrap_or(MovieAmounts {
+            currency: INR.to_string(),
+            unit: AmountUnit::Crore,
+            budget: movie.budget_crores,
+            collection: movie.collection_crores,
+            exchange_rate: Decimal::ONE,
+            exchange_rate_date: None,
+        });
+        let currency = self.currency.unwrap_or(reported_amounts.currency.clone());
+        let unit = self.unit.unwrap_or(reported_amounts.unit);
+        let is_currency_or_unit_changed =
+            currency != reported_amounts.currency || unit != reported_amounts.unit;
+        if is_currency_or_unit_changed
+            && (self.budget_crores.is_none() || self.collection_crores.is_none())
+        {
+            return Err(BoxOfficeError::BadUpdateMovieRequestError(
+                PATCH_AMOUNTS_REQUIRED_ERROR.to_string(),
+            ));
+        }
+        return Ok(UpdateMovieRequest {
+            title: self.title.unwrap_or_else(|| movie.title.clone()),
+            production_house: self
+                .production_house
+                .unwrap_or_else(|| movie.production_house.clone()),
+            year_of_release: self.year_of_release.unwrap_or(movie.year_of_release),
+            release_date: self.release_date.or_else(|| movie.release_date.clone()),
+            status: Some(self.status.unwrap_or(movie.status)),
+            budget_crores: self.budget_crores.unwrap_or(reported_amounts.budget),
+            collection_crores: self
+                .collection_crores
+                .unwrap_or(reported_amounts.collection),
+            currency: Some(currency),
+            unit: Some(unit),
+            exchange_rate_date: self
+                .exchange_rate_date
+                .or(reported_amounts.exchange_rate_date),
+            metadata: self.metadata,
+        });
+    }
+}
+
+/// Descriptive metadata of a movie, replaced as a whole.
+/// Genres, languages, certification and tags must be in the configured vocabularies, compared ignoring case.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Validate)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MovieMetadataRequest {
+    #[serde(default)]
+    #[validate(custom = "validate_metadata_values")]
+    pub genres: Vec<String>,
+    #[serde(default)]
+    #[validate(custom = "validate_metadata_values")]
+    pub languages: Vec<String>,
+    /// CBFC certification, e.g. 'UA 13+'.
+    #[serde(default)]
+    #[validate(custom = "validate_metadata_value")]
+    pub certification: Option<String>,
+    #[serde(default)]
+    #[validate(custom = "validate_runtime_minutes")]
+    pub runtime_minutes: Option<u16>,
+    #[serde(default)]
+    #[validate(custom = "validate_metadata_values")]
+    pub tags: Vec<String>,
+}
+
+/// Query parameters of routes returning movies.
+/// 'include' lists computed figures to add to movies, e.g. '?include=profit,roi_multiple'.
+/// 'currency' and 'unit' add amounts converted to that currency and unit, e.g. '?currency=USD&unit=million'.
+/// 'baseYear' adds amounts adjusted for inflation to that year, e.g. '?baseYear=2023'.
+/// 'sort' and 'order' sort movies of read routes, e.g. '?sort=adjusted_collection_crores&order=desc'.
+/// 'territory' and 'market' keep only movies with collection in that territory or market on read routes.
+/// 'genre', 'language', 'certification' and 'tag' keep only movies with that metadata on read routes.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MovieQuery {
+    pub include: Option<String>,
+    pub currency: Option<String>,
+    pub unit: Option<AmountUnit>,
+    pub base_year: Option<u16>,
+    pub sort: Option<MovieSortKey>,
+    #[serde(default)]
+    pub order: SortOrder,
+    pub territory: Option<String>,
+    pub market: Option<Market>,
+    pub genre: Option<String>,
+    pub language: Option<String>,
+    pub certification: Option<String>,
+    pub tag: Option<String>,
+}
+
+/// Fields by which movies can be sorted.
+/// Adjusted amounts order movies the same way for every base year.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MovieSortKey {
+    MovieId,
+    Title,
+    YearOfRelease,
+    BudgetCrores,
+    CollectionCrores,
+    AdjustedBudgetCrores,
+    AdjustedCollectionCrores,
+}
+
+impl MovieSortKey {
+    /// Returns whether movies must be adjusted for inflation to be sorted by this key.
+    pub fn is_inflation_adjusted(&self) -> bool {
+        matches!(
+            self,
+            MovieSortKey::AdjustedBudgetCrores | MovieSortKey::AdjustedCollectionCrores
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl MovieQuery {
+    /// Returns financial figures selected by 'include'.
+    pub fn financial_fields(&self) -> std::result::Result<FinancialFields, BoxOfficeError> {
+        let mut fields = FinancialFields::default();
+        for value in self.include_values() {
+            match value {
+                INCLUDE_PROFIT => fields.profit = true,
+                INCLUDE_PROFIT_PERCENTAGE => fields.profit_percentage = true,
+                INCLUDE_ROI_MULTIPLE => fields.roi_multiple = true,
+                INCLUDE_BREAK_EVEN_GAP => fields.break_even_gap = true,
+                INCLUDE_FINANCIALS => fields = FinancialFields::all(),
+                INCLUDE_INFLATION_ADJUSTED => {}
+                _ => return Err(BoxOfficeError::InvalidIncludeError(value.to_string())),
+            }
+        }
+        return Ok(fields);
+    }
+
+    /// Returns whether amounts adjusted for inflation are requested,
+    /// with 'include', 'baseYear' or sorting by an adjusted amount.
+    pub fn is_inflation_adjustment_requested(&self) -> bool {
+        return self.base_year.is_some()
+            || self.sort.is_some_and(|sort| sort.is_inflation_adjusted())
+            || self
+                .include_values()
+                .any(|value| value == INCLUDE_INFLATION_ADJUSTED);
+    }
+
+    fn include_values(&self) -> impl Iterator<Item = &str> {
+        self.include
+            .as_deref()
+            .unwrap_or_default()
+            .split(INCLUDE_SEPARATOR)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Returns currency and unit in which amounts are requested, if any.
+    /// INR or crore is used when only one of them is given.
+    pub fn requested_currency(
+        &self,
+    ) -> std::result::Result<Option<(String, AmountUnit)>, BoxOfficeError> {
+        if self.currency.is_none() && self.unit.is_none() {
+            return Ok(None);
+        }
+        let currency = self.currency.clone().unwrap_or_else(|| INR.to_string());
+        if !is_currency_code(&currency) {
+            return Err(BoxOfficeError::InvalidCurrencyError(currency));
+        }
+        return Ok(Some((currency, self.unit.unwrap_or_default())));
+    }
 }
 
 ///Response Structure to get Create Movie Response
@@ -52,6 +308,9 @@ pub struct ReadMoviesResponse {
     pub success: bool,
     pub message: String,
     pub data: Vec<Movie>,
+    /// Number of movies per genre, language, certification and tag.
+    #[serde(default)]
+    pub facets: MovieFacets,
 }
 
 ///Response Structure to get Update Movie Response
@@ -76,6 +335,279 @@ pub struct DatabaseOperationFailedErrorResponse {
     pub message: String,
 }
 
+/// Response structure to get liveness or readiness of the server.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HealthResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+///Request Structure to read Create Webhook Request.
+/// Secret is generated if not given.
+#[derive(Serialize, Deserialize, Debug, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookRequest {
+    #[validate(url)]
+    pub url: String,
+    #[validate(length(min = 1), custom = "validate_webhook_events")]
+    pub events: Vec<String>,
+    #[validate(length(min = "MINIMUM_WEBHOOK_SECRET_LENGTH"))]
+    pub secret: Option<String>,
+}
+
+///Response Structure to get Create Webhook Response.
+/// Secret used to sign payloads is returned only once, when webhook is created.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreateWebhookResponse {
+    pub success: bool,
+    pub message: String,
+    pub secret: String,
+    pub data: Webhook,
+}
+
+///Response Structure to get Read Webhooks Response
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReadWebhooksResponse {
+    pub success: bool,
+    pub message: String,
+    pub data: Vec<Webhook>,
+}
+
+///Response Structure to get Delete Webhook Response
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DeleteWebhookResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+///Response Structure to get Read Webhook Deliveries Response
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReadWebhookDeliveriesResponse {
+    pub success: bool,
+    pub message: String,
+    pub data: Vec<WebhookDelivery>,
+}
+
+///Response Structure to get Read Dead Letters Response
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReadDeadLettersResponse {
+    pub success: bool,
+    pub message: String,
+    pub data: Vec<OutboxEntry>,
+}
+
+///Request Structure to read Upsert Exchange Rates Request.
+#[derive(Serialize, Deserialize, Debug, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertExchangeRatesRequest {
+    #[validate(length(min = 1), custom = "validate_exchange_rates")]
+    pub rates: Vec<ExchangeRate>,
+}
+
+///Response Structure to get Read Exchange Rates Response
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReadExchangeRatesResponse {
+    pub success: bool,
+    pub message: String,
+    pub data: Vec<ExchangeRate>,
+}
+
+///Response Structure to get Upsert Exchange Rates Response
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpsertExchangeRatesResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+///Request Structure to read Create Collection Entry Request
+#[derive(Serialize, Deserialize, Debug, Validate)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CreateCollectionEntryRequest {
+    /// First day of the period, 'YYYY-MM-DD'.
+    #[validate(custom = "validate_date")]
+    pub date: String,
+    pub period: CollectionPeriod,
+    /// Territory of the collection, 'DEFAULT_TERRITORY' when not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(custom = "validate_territory")]
+    pub territory: Option<String>,
+    /// Collection in INR crores.
+    #[validate(custom = "validate_collection_not_negative")]
+    pub collection_crores: Decimal,
+}
+
+/// Query parameters of Read Collection Entries route, entries are returned if they match every given field.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionEntryFilter {
+    pub territory: Option<String>,
+    pub period: Option<CollectionPeriod>,
+}
+
+impl CollectionEntryFilter {
+    pub fn matches(&self, entry: &CollectionEntry) -> bool {
+        return self
+            .territory
+            .as_ref()
+            .map(|territory| *territory == entry.territory)
+            .unwrap_or(true)
+            && self
+                .period
+                .map(|period| period == entry.period)
+                .unwrap_or(true);
+    }
+}
+
+///Request Structure to read Update Territories Request.
+/// Replaces territory breakdown of a movie, an empty list removes it.
+#[derive(Serialize, Deserialize, Debug, Validate)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct UpdateTerritoriesRequest {
+    #[validate(custom = "validate_territories")]
+    pub territories: Vec<TerritoryRequest>,
+}
+
+/// Budget share and collection of one territory in INR crores.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TerritoryRequest {
+    pub territory: String,
+    pub market: Market,
+    pub budget_crores: Decimal,
+    pub collection_crores: Decimal,
+}
+
+///Request Structure to read Production House Request.
+/// Movies whose production house matches the name or an alias refer to the production house.
+#[derive(Serialize, Deserialize, Debug, Validate)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProductionHouseRequest {
+    #[validate(custom = "validate_production_house_name")]
+    pub name: String,
+    #[serde(default)]
+    #[validate(custom = "validate_production_house_aliases")]
+    pub aliases: Vec<String>,
+}
+
+///Request Structure to read Merge Production Houses Request.
+/// Production houses 'sourceIds' are merged into the production house of the path.
+#[derive(Serialize, Deserialize, Debug, Validate)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MergeProductionHousesRequest {
+    #[validate(custom = "validate_source_production_houses")]
+    pub source_ids: Vec<String>,
+}
+
+///Request Structure to read Person Request.
+#[derive(Serialize, Deserialize, Debug, Validate)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PersonRequest {
+    #[validate(custom = "validate_person_name")]
+    pub name: String,
+}
+
+///Request Structure to read Update Credits Request.
+/// Replaces cast and crew of a movie, an empty list removes them.
+#[derive(Serialize, Deserialize, Debug, Validate)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct UpdateCreditsRequest {
+    #[validate(custom = "validate_credits")]
+    pub credits: Vec<CreditRequest>,
+}
+
+/// Credit of a person in a role, with the character played by actors.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CreditRequest {
+    pub person_id: String,
+    pub role: Role,
+    #[serde(default)]
+    pub character: Option<String>,
+}
+
+/// Query parameters of Read Filmography route.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FilmographyQuery {
+    /// Limits filmography to credits in the role.
+    pub role: Option<Role>,
+}
+
+///Request Structure to read Franchise Request.
+/// Instalments are given as Movie Ids in instalment order.
+#[derive(Serialize, Deserialize, Debug, Validate)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FranchiseRequest {
+    #[validate(custom = "validate_franchise_name")]
+    pub name: String,
+    #[serde(default)]
+    #[validate(custom = "validate_instalments")]
+    pub movie_ids: Vec<u8>,
+}
+
+///Request Structure to read Update Relations Request.
+/// Replaces relations of a movie to other movies, an empty list removes them.
+#[derive(Serialize, Deserialize, Debug, Validate)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct UpdateRelationsRequest {
+    #[validate(custom = "validate_relations")]
+    pub relations: Vec<RelationRequest>,
+}
+
+/// Relation of a movie to another movie, e.g. {"kind":"sequel-of","movieId":3}.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RelationRequest {
+    pub kind: RelationKind,
+    pub movie_id: u8,
+}
+
+/// Query parameters of Search and Suggest routes, e.g. '?q=dilvale&limit=5'.
+#[derive(Serialize, Deserialize, Debug, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchQuery {
+    #[validate(custom = "validate_search_query")]
+    pub q: String,
+    #[validate(custom = "validate_search_limit")]
+    pub limit: Option<usize>,
+}
+
+impl SearchQuery {
+    pub fn limit(&self) -> usize {
+        return self.limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+    }
+}
+
+///Request Structure to read Upsert CPI Request.
+#[derive(Serialize, Deserialize, Debug, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertCpiRequest {
+    #[validate(length(min = 1), custom = "validate_cpi")]
+    pub cpi: Vec<ConsumerPriceIndex>,
+}
+
+///Response Structure to get Read CPI Response
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReadCpiResponse {
+    pub success: bool,
+    pub message: String,
+    pub data: Vec<ConsumerPriceIndex>,
+}
+
+///Response Structure to get Upsert CPI Response
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpsertCpiResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+///Response Structure to get Retry Dead Letter Response
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RetryDeadLetterResponse {
+    pub success: bool,
+    pub message: String,
+}
+
 //*  Custom validation methods for validator type. */
 /// Checks whether budget is greater than zero or not.
 pub fn validate_budget_greater_than_zero(
@@ -97,9 +629,50 @@ pub fn validate_collection_greater_than_zero(
     Ok(())
 }
 
+/// Checks collection of movie against its release status.
+/// Released movies have collection, movies not released yet have none.
+fn validate_release_collection(
+    status: ReleaseStatus,
+    collection_crores: &Decimal,
+) -> std::result::Result<(), ValidationError> {
+    if status.is_released() {
+        return validate_collection_greater_than_zero(collection_crores);
+    }
+    if *collection_crores != Decimal::ZERO {
+        return Err(ValidationError::new(COLLECTION_BEFORE_RELEASE_ERROR));
+    }
+    Ok(())
+}
+
+/// Checks collection of movie to be created against its release status, released when not given.
+pub fn validate_create_movie_release(
+    create_movie_request: &CreateMovieRequest,
+) -> std::result::Result<(), ValidationError> {
+    return validate_release_collection(
+        create_movie_request
+            .status
+            .unwrap_or(ReleaseStatus::Released),
+        &create_movie_request.collection_crores,
+    );
+}
+
+/// Checks collection of movie to be updated against the given release status.
+/// Collection of a movie keeping its status is checked once the status is known.
+pub fn validate_update_movie_release(
+    update_movie_request: &UpdateMovieRequest,
+) -> std::result::Result<(), ValidationError> {
+    return match update_movie_request.status {
+        Some(status) => {
+            validate_release_collection(status, &update_movie_request.collection_crores)
+        }
+        None => Ok(()),
+    };
+}
+
 /// Checks whether year of release is between specified range.
+/// Upcoming movies may be released up to 'MAXIMUM_YEARS_UNTIL_RELEASE' years after the current year.
 pub fn validate_year_of_release(year_of_release: u16) -> std::result::Result<(), ValidationError> {
-    if (year_of_release < MINIMUM_YEAR_OF_RELEASE) || (year_of_release > MAXIMUM_YEAR_OF_RELEASE) {
+    if !(MINIMUM_YEAR_OF_RELEASE..=maximum_year_of_release()).contains(&year_of_release) {
         return Err(ValidationError::new(YEAR_OF_RELEASE_BETWEEN_MIN_AND_MAX));
     }
     Ok(())
@@ -107,8 +680,255 @@ pub fn validate_year_of_release(year_of_release: u16) -> std::result::Result<(),
 
 /// Checks whether Movie Id is greater than zero or not.
 pub fn validate_movie_id(movie_id: u8) -> std::result::Result<(), ValidationError> {
-    if movie_id <= ZERO {
+    if movie_id == ZERO {
         return Err(ValidationError::new(MOVIEID_GREATER_THAN_ZERO_ERROR));
     }
     Ok(())
 }
+
+/// Checks whether every webhook event is a known event.
+pub fn validate_webhook_events(events: &[String]) -> std::result::Result<(), ValidationError> {
+    if !events
+        .iter()
+        .all(|event| WEBHOOK_EVENTS.contains(&event.as_str()))
+    {
+        return Err(ValidationError::new(INVALID_WEBHOOK_EVENT_ERROR));
+    }
+    Ok(())
+}
+
+/// Checks whether currency is ISO 4217 code.
+pub fn validate_currency(currency: &str) -> std::result::Result<(), ValidationError> {
+    if !is_currency_code(currency) {
+        return Err(ValidationError::new(INVALID_CURRENCY_ERROR));
+    }
+    Ok(())
+}
+
+/// Checks whether date is 'YYYY-MM-DD'.
+pub fn validate_date(date: &str) -> std::result::Result<(), ValidationError> {
+    if !is_date(date) {
+        return Err(ValidationError::new(INVALID_DATE_ERROR));
+    }
+    Ok(())
+}
+
+/// Checks currency, date and rate of every exchange rate.
+pub fn validate_exchange_rates(rates: &[ExchangeRate]) -> std::result::Result<(), ValidationError> {
+    for rate in rates {
+        validate_currency(&rate.currency)?;
+        validate_date(&rate.date)?;
+        if rate.rate_to_inr <= Decimal::ZERO {
+            return Err(ValidationError::new(EXCHANGE_RATE_GREATER_THAN_ZERO_ERROR));
+        }
+    }
+    Ok(())
+}
+
+/// Validates name and amounts of every territory, each territory may be given once.
+pub fn validate_territories(
+    territories: &[TerritoryRequest],
+) -> std::result::Result<(), ValidationError> {
+    let mut names: Vec<&str> = Vec::new();
+    for territory in territories {
+        validate_territory(&territory.territory)?;
+        if territory.budget_crores <= Decimal::ZERO {
+            return Err(ValidationError::new(
+                TERRITORY_BUDGET_GREATER_THAN_ZERO_ERROR,
+            ));
+        }
+        validate_collection_not_negative(&territory.collection_crores)?;
+        if names.contains(&territory.territory.as_str()) {
+            return Err(ValidationError::new(DUPLICATE_TERRITORY_ERROR));
+        }
+        names.push(&territory.territory);
+    }
+    Ok(())
+}
+
+/// Validates consumer price index of every year.
+pub fn validate_cpi(cpi: &[ConsumerPriceIndex]) -> std::result::Result<(), ValidationError> {
+    for entry in cpi {
+        if entry.year < MINIMUM_CPI_YEAR || entry.year > MAXIMUM_CPI_YEAR {
+            return Err(ValidationError::new(CPI_YEAR_BETWEEN_MIN_AND_MAX));
+        }
+        if entry.index <= Decimal::ZERO {
+            return Err(ValidationError::new(CPI_GREATER_THAN_ZERO_ERROR));
+        }
+    }
+    Ok(())
+}
+
+/// Validates collection of a period, which may be zero.
+pub fn validate_collection_not_negative(
+    collection_crores: &Decimal,
+) -> std::result::Result<(), ValidationError> {
+    if *collection_crores < Decimal::ZERO {
+        return Err(ValidationError::new(COLLECTION_NOT_NEGATIVE_ERROR));
+    }
+    Ok(())
+}
+
+/// Validates territory name, e.g. 'domestic', 'overseas' or 'mumbai'.
+pub fn validate_territory(territory: &str) -> std::result::Result<(), ValidationError> {
+    if territory.is_empty()
+        || territory.len() > MAXIMUM_TERRITORY_LENGTH
+        || !territory
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+    {
+        return Err(ValidationError::new(INVALID_TERRITORY_ERROR));
+    }
+    Ok(())
+}
+
+/// Validates production house name, which should contain a letter or a digit.
+pub fn validate_production_house_name(name: &str) -> std::result::Result<(), ValidationError> {
+    if production_house::name_key(name).is_empty() {
+        return Err(ValidationError::new(PRODUCTION_HOUSE_NAME_ERROR));
+    }
+    Ok(())
+}
+
+/// Validates every alias of production house as a production house name.
+pub fn validate_production_house_aliases(
+    aliases: &[String],
+) -> std::result::Result<(), ValidationError> {
+    for alias in aliases {
+        validate_production_house_name(alias)?;
+    }
+    Ok(())
+}
+
+/// Validates that at least one production house is merged.
+pub fn validate_source_production_houses(
+    source_ids: &[String],
+) -> std::result::Result<(), ValidationError> {
+    if source_ids.is_empty() {
+        return Err(ValidationError::new(
+            SOURCE_PRODUCTION_HOUSES_REQUIRED_ERROR,
+        ));
+    }
+    Ok(())
+}
+
+/// Validates runtime of a movie in minutes.
+pub fn validate_runtime_minutes(runtime_minutes: u16) -> std::result::Result<(), ValidationError> {
+    if !(MINIMUM_RUNTIME_MINUTES..=MAXIMUM_RUNTIME_MINUTES).contains(&runtime_minutes) {
+        return Err(ValidationError::new(RUNTIME_BETWEEN_MIN_AND_MAX));
+    }
+    Ok(())
+}
+
+/// Validates that metadata value is not blank, vocabularies are checked when the movie is stored.
+pub fn validate_metadata_value(value: &str) -> std::result::Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new(METADATA_VALUE_ERROR));
+    }
+    Ok(())
+}
+
+/// Validates that no metadata value is blank.
+pub fn validate_metadata_values(values: &[String]) -> std::result::Result<(), ValidationError> {
+    for value in values {
+        validate_metadata_value(value)?;
+    }
+    Ok(())
+}
+
+/// Validates person name, which should contain a letter or a digit.
+pub fn validate_person_name(name: &str) -> std::result::Result<(), ValidationError> {
+    if !name.chars().any(char::is_alphanumeric) {
+        return Err(ValidationError::new(PERSON_NAME_ERROR));
+    }
+    Ok(())
+}
+
+/// Validates credits, a person is credited only once in a role.
+pub fn validate_credits(credits: &[CreditRequest]) -> std::result::Result<(), ValidationError> {
+    let mut credited: Vec<(&str, Role)> = Vec::new();
+    for credit in credits {
+        let person_role = (credit.person_id.as_str(), credit.role);
+        if credited.contains(&person_role) {
+            return Err(ValidationError::new(DUPLICATE_CREDIT_ERROR));
+        }
+        credited.push(person_role);
+    }
+    Ok(())
+}
+
+/// Validates franchise name, which should contain a letter or a digit.
+pub fn validate_franchise_name(name: &str) -> std::result::Result<(), ValidationError> {
+    if !name.chars().any(char::is_alphanumeric) {
+        return Err(ValidationError::new(FRANCHISE_NAME_ERROR));
+    }
+    Ok(())
+}
+
+/// Validates instalments, a movie is an instalment only once.
+pub fn validate_instalments(movie_ids: &[u8]) -> std::result::Result<(), ValidationError> {
+    let mut instalments: Vec<u8> = Vec::new();
+    for movie_id in movie_ids {
+        if instalments.contains(movie_id) {
+            return Err(ValidationError::new(DUPLICATE_INSTALMENT_ERROR));
+        }
+        instalments.push(*movie_id);
+    }
+    Ok(())
+}
+
+/// Validates relations, a movie is related only once in a kind.
+pub fn validate_relations(
+    relations: &[RelationRequest],
+) -> std::result::Result<(), ValidationError> {
+    let mut related: Vec<&RelationRequest> = Vec::new();
+    for relation in relations {
+        if related.contains(&relation) {
+            return Err(ValidationError::new(DUPLICATE_RELATION_ERROR));
+        }
+        related.push(relation);
+    }
+    Ok(())
+}
+
+/// Validates search query, which should contain a letter or a digit and be at most 100 characters.
+pub fn validate_search_query(query: &str) -> std::result::Result<(), ValidationError> {
+    if !query.chars().any(char::is_alphanumeric) {
+        return Err(ValidationError::new(SEARCH_QUERY_ERROR));
+    }
+    if query.chars().count() > MAXIMUM_SEARCH_QUERY_LENGTH {
+        return Err(ValidationError::new(SEARCH_QUERY_LENGTH_ERROR));
+    }
+    Ok(())
+}
+
+/// Validates search limit, which should be between 1 and 50.
+pub fn validate_search_limit(limit: usize) -> std::result::Result<(), ValidationError> {
+    if !(1..=MAXIMUM_SEARCH_LIMIT).contains(&limit) {
+        return Err(ValidationError::new(SEARCH_LIMIT_ERROR));
+    }
+    Ok(())
+}
+
+/// Currency codes are three uppercase letters.
+fn is_currency_code(currency: &str) -> bool {
+    currency.len() == 3 && currency.chars().all(|c| c.is_ascii_uppercase())
+}
+
+/// Dates are 'YYYY-MM-DD' with month 01 to 12 and day 01 to 31.
+fn is_date(date: &str) -> bool {
+    let parts: Vec<&str> = date.split('-').collect();
+    if parts.len() != 3
+        || parts[0].len() != 4
+        || parts[1].len() != 2
+        || parts[2].len() != 2
+        || !parts
+            .iter()
+            .all(|part| part.chars().all(|c| c.is_ascii_digit()))
+    {
+        return false;
+    }
+    let month: u8 = parts[1].parse().unwrap_or_default();
+    let day: u8 = parts[2].parse().unwrap_or_default();
+    (1..=12).contains(&month) && (1..=31).contains(&day)
+}
@@ -1,4 +1,5 @@
 use crate::constants::*;
+use crate::metadata;
 use crate::model::*;
 /// Functions to get response.
 use crate::request_response_structs::*;
@@ -21,7 +22,7 @@ pub fn populate_create_movie_response(created_movie: Movie) -> CreateMovieRespon
     return create_movie_response;
 }
 
-/// Function to get Read all Movies response.
+/// Function to get Read all Movies response, with facets of the movies.
 pub fn populate_read_movies_response(fetched_movies: Vec<Movie>) -> ReadMoviesResponse {
     let number_of_movies_fetched = fetched_movies.len().to_string();
     let string_message1 = " '";
@@ -32,6 +33,7 @@ pub fn populate_read_movies_response(fetched_movies: Vec<Movie>) -> ReadMoviesRe
     let read_movies_response = ReadMoviesResponse {
         success: true,
         message: concatenated_response_string.to_string(),
+        facets: metadata::facets(&fetched_movies),
         data: fetched_movies,
     };
 
@@ -87,3 +89,157 @@ pub fn populate_db_operation_failed_error_response() -> DatabaseOperationFailedE
 
     return db_operation_failed_error_response;
 }
+
+/// Function to get liveness or readiness response.
+pub fn populate_health_response(success: bool, message: &str) -> HealthResponse {
+    let health_response = HealthResponse {
+        success,
+        message: message.to_string(),
+    };
+
+    return health_response;
+}
+
+/// Function to get Create Webhook response.
+pub fn populate_create_webhook_response(
+    created_webhook: Webhook,
+    secret: String,
+) -> CreateWebhookResponse {
+    let message = format!(
+        "Webhook with 'Webhook ID : {}' created.",
+        created_webhook.id
+    );
+
+    let create_webhook_response = CreateWebhookResponse {
+        success: true,
+        message,
+        secret,
+        data: created_webhook,
+    };
+
+    return create_webhook_response;
+}
+
+/// Function to get Read all Webhooks response.
+pub fn populate_read_webhooks_response(fetched_webhooks: Vec<Webhook>) -> ReadWebhooksResponse {
+    let message = format!(" '{}' webhooks fetched.", fetched_webhooks.len());
+
+    let read_webhooks_response = ReadWebhooksResponse {
+        success: true,
+        message,
+        data: fetched_webhooks,
+    };
+
+    return read_webhooks_response;
+}
+
+/// Function to get Delete Webhook response.
+pub fn populate_delete_webhook_response(webhook_id: &str) -> DeleteWebhookResponse {
+    let message = format!("Webhook with 'Webhook ID : {}' deleted.", webhook_id);
+
+    let delete_webhook_response = DeleteWebhookResponse {
+        success: true,
+        message,
+    };
+
+    return delete_webhook_response;
+}
+
+/// Function to get Read Webhook Deliveries response.
+pub fn populate_read_webhook_deliveries_response(
+    fetched_deliveries: Vec<WebhookDelivery>,
+) -> ReadWebhookDeliveriesResponse {
+    let message = format!(" '{}' deliveries fetched.", fetched_deliveries.len());
+
+    let read_webhook_deliveries_response = ReadWebhookDeliveriesResponse {
+        success: true,
+        message,
+        data: fetched_deliveries,
+    };
+
+    return read_webhook_deliveries_response;
+}
+
+/// Function to get Read Dead Letters response.
+pub fn populate_read_dead_letters_response(
+    fetched_dead_letters: Vec<OutboxEntry>,
+) -> ReadDeadLettersResponse {
+    let message = format!(" '{}' dead letters fetched.", fetched_dead_letters.len());
+
+    let read_dead_letters_response = ReadDeadLettersResponse {
+        success: true,
+        message,
+        data: fetched_dead_letters,
+    };
+
+    return read_dead_letters_response;
+}
+
+/// Function to get Retry Dead Letter response.
+pub fn populate_retry_dead_letter_response(dead_letter_id: &str) -> RetryDeadLetterResponse {
+    let message = format!(
+        "Dead letter with 'ID : {}' scheduled for delivery.",
+        dead_letter_id
+    );
+
+    let retry_dead_letter_response = RetryDeadLetterResponse {
+        success: true,
+        message,
+    };
+
+    return retry_dead_letter_response;
+}
+
+/// Function to get Read all Exchange Rates response.
+pub fn populate_read_exchange_rates_response(
+    exchange_rates: Vec<ExchangeRate>,
+) -> ReadExchangeRatesResponse {
+    let message = format!(" '{}' exchange rates fetched.", exchange_rates.len());
+
+    let read_exchange_rates_response = ReadExchangeRatesResponse {
+        success: true,
+        message,
+        data: exchange_rates,
+    };
+
+    return read_exchange_rates_response;
+}
+
+/// Function to get Upsert Exchange Rates response.
+pub fn populate_upsert_exchange_rates_response(
+    number_of_rates: usize,
+) -> UpsertExchangeRatesResponse {
+    let message = format!(" '{}' exchange rates upserted.", number_of_rates);
+
+    let upsert_exchange_rates_response = UpsertExchangeRatesResponse {
+        success: true,
+        message,
+    };
+
+    return upsert_exchange_rates_response;
+}
+
+/// Function to get Read CPI response.
+pub fn populate_read_cpi_response(cpi: Vec<ConsumerPriceIndex>) -> ReadCpiResponse {
+    let message = format!(" '{}' consumer price indices fetched.", cpi.len());
+
+    let read_cpi_response = ReadCpiResponse {
+        success: true,
+        message,
+        data: cpi,
+    };
+
+    return read_cpi_response;
+}
+
+/// Function to get Upsert CPI response.
+pub fn populate_upsert_cpi_response(number_of_years: usize) -> UpsertCpiResponse {
+    let message = format!(" '{}' consumer price indices upserted.", number_of_years);
+
+    let upsert_cpi_response = UpsertCpiResponse {
+        success: true,
+        message,
+    };
+
+    return upsert_cpi_response;
+}
@@ -1,47 +1,539 @@
 #![allow(opaque_hidden_inferred_bound)]
 
+use crate::constants::*;
 use crate::db_layer::*;
+use crate::error;
+use crate::events::EventFilter;
+use crate::graphql;
 use crate::handler;
+use crate::negotiation;
+use crate::request_response_structs::{
+    CollectionEntryFilter, FilmographyQuery, MovieQuery, SearchQuery,
+};
+use crate::shutdown::Readiness;
 /// Set endpoints , routes REST requests to handlers.
 use std::convert::Infallible;
-use warp::Filter;
+use warp::http::header::{HeaderMap, HeaderValue, LINK};
+use warp::{Filter, Rejection, Reply};
 
 /// Set endpoints (handlers functions) for REST requests using warp Filter.
-pub async fn assets_filter(
+pub fn assets_filter(
+    db: DB,
+    readiness: Readiness,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
-    let db = DB::init().await.unwrap();
     let movie = warp::path("boxoffice")
         .and(warp::path("movies"))
         .and(warp::path("v1"));
-    let movie_routes = movie
-        .and(warp::post())
+    // Path segment is matched before method so that other paths under /boxoffice/movies/v1
+    // are rejected as not found and reach the event routes.
+    let deprecated_movie_routes = movie
         .and(warp::path("createmovie"))
+        .and(warp::post())
         .and(warp::path::end())
+        .and(warp::query::<MovieQuery>())
+        .and(negotiation::response_format(false))
+        .and(negotiation::request_format())
         .and(warp::body::aggregate())
         .and(with_db(db.clone()))
         .and_then(handler::create_movie_handler)
         .or(movie
-            .and(warp::get())
             .and(warp::path("readmovies"))
+            .and(warp::get())
             .and(warp::path::end())
+            .and(warp::query::<MovieQuery>())
+            .and(negotiation::response_format(true))
             .and(with_db(db.clone()))
             .and_then(handler::read_movies_handler))
         .or(movie
-            .and(warp::put())
             .and(warp::path("updatemovie"))
+            .and(warp::put())
             .and(warp::path::param())
             .and(warp::path::end())
+            .and(warp::query::<MovieQuery>())
+            .and(negotiation::response_format(false))
+            .and(negotiation::request_format())
             .and(warp::body::aggregate())
             .and(with_db(db.clone()))
             .and_then(handler::update_movie_handler))
         .or(movie
-            .and(warp::delete())
             .and(warp::path("deletemovie"))
+            .and(warp::delete())
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and(with_db(db.clone()))
+            .and_then(handler::delete_movie_handler))
+        .recover(handle_deprecated_rejection)
+        .with(warp::reply::with::headers(deprecation_headers()));
+    let movie_event_routes = movie
+        .and(warp::get())
+        .and(warp::path("events"))
+        .and(warp::path::end())
+        .and(warp::query::<EventFilter>())
+        .and(with_db(db.clone()))
+        .and_then(handler::movie_events_handler)
+        .or(movie
+            .and(warp::get())
+            .and(warp::path("events"))
+            .and(warp::path("ws"))
+            .and(warp::path::end())
+            .and(warp::ws())
+            .and(warp::query::<EventFilter>())
+            .and(with_db(db.clone()))
+            .and_then(handler::movie_events_websocket_handler));
+    let movies = warp::path("boxoffice")
+        .and(warp::path("v2"))
+        .and(warp::path("movies"));
+    let movie_v2_routes = movies
+        .and(warp::post())
+        .and(warp::path::end())
+        .and(warp::query::<MovieQuery>())
+        .and(negotiation::response_format(false))
+        .and(negotiation::request_format())
+        .and(warp::body::aggregate())
+        .and(with_db(db.clone()))
+        .and_then(handler::create_movie_v2_handler)
+        .or(movies
+            .and(warp::get())
+            .and(warp::path::end())
+            .and(warp::query::<MovieQuery>())
+            .and(negotiation::response_format(true))
+            .and(with_db(db.clone()))
+            .and_then(handler::read_movies_v2_handler))
+        .or(movies
+            .and(warp::get())
+            .and(warp::path("facets"))
+            .and(warp::path::end())
+            .and(warp::query::<MovieQuery>())
+            .and(negotiation::response_format(false))
+            .and(with_db(db.clone()))
+            .and_then(handler::read_movie_facets_handler))
+        .or(movies
+            .and(warp::get())
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(warp::query::<MovieQuery>())
+            .and(negotiation::response_format(false))
+            .and(with_db(db.clone()))
+            .and_then(handler::get_movie_v2_handler))
+        .or(movies
+            .and(warp::put())
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(warp::query::<MovieQuery>())
+            .and(negotiation::response_format(false))
+            .and(negotiation::request_format())
+            .and(warp::body::aggregate())
+            .and(with_db(db.clone()))
+            .and_then(handler::update_movie_v2_handler))
+        .or(movies
+            .and(warp::patch())
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(warp::query::<MovieQuery>())
+            .and(negotiation::response_format(false))
+            .and(negotiation::request_format())
+            .and(warp::body::aggregate())
+            .and(with_db(db.clone()))
+            .and_then(handler::patch_movie_v2_handler))
+        .or(movies
+            .and(warp::delete())
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(with_db(db.clone()))
+            .and_then(handler::delete_movie_v2_handler));
+    let movie_collections = movies
+        .and(warp::path::param::<String>())
+        .and(warp::path("collections"));
+    let collection_routes = movie_collections
+        .and(warp::post())
+        .and(warp::path::end())
+        .and(negotiation::response_format(false))
+        .and(negotiation::request_format())
+        .and(warp::body::aggregate())
+        .and(with_db(db.clone()))
+        .and_then(handler::create_collection_entry_handler)
+        .or(movie_collections
+            .and(warp::get())
+            .and(warp::path::end())
+            .and(warp::query::<CollectionEntryFilter>())
+            .and(negotiation::response_format(true))
+            .and(with_db(db.clone()))
+            .and_then(handler::read_collection_entries_handler))
+        .or(movie_collections
+            .and(warp::get())
+            .and(warp::path("summary"))
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and(with_db(db.clone()))
+            .and_then(handler::read_collection_run_handler))
+        .or(movie_collections
+            .and(warp::delete())
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(with_db(db.clone()))
+            .and_then(handler::delete_collection_entry_handler));
+    let movie_territories = movies
+        .and(warp::path::param::<String>())
+        .and(warp::path("territories"))
+        .and(warp::path::end());
+    let territory_routes = movie_territories
+        .and(warp::get())
+        .and(negotiation::response_format(true))
+        .and(with_db(db.clone()))
+        .and_then(handler::read_territories_handler)
+        .or(movie_territories
+            .and(warp::put())
+            .and(negotiation::response_format(false))
+            .and(negotiation::request_format())
+            .and(warp::body::aggregate())
+            .and(with_db(db.clone()))
+            .and_then(handler::update_territories_handler));
+    let production_houses = warp::path("boxoffice")
+        .and(warp::path("v2"))
+        .and(warp::path("productionhouses"));
+    let production_house_routes = production_houses
+        .and(warp::post())
+        .and(warp::path::end())
+        .and(negotiation::response_format(false))
+        .and(negotiation::request_format())
+        .and(warp::body::aggregate())
+        .and(with_db(db.clone()))
+        .and_then(handler::create_production_house_handler)
+        .or(production_houses
+            .and(warp::get())
+            .and(warp::path::end())
+            .and(negotiation::response_format(true))
+            .and(with_db(db.clone()))
+            .and_then(handler::read_production_houses_handler))
+        .or(production_houses
+            .and(warp::get())
+            .and(warp::path("trackrecords"))
+            .and(warp::path::end())
+            .and(negotiation::response_format(true))
+            .and(with_db(db.clone()))
+            .and_then(handler::read_track_records_handler))
+        .or(production_houses
+            .and(warp::get())
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and(with_db(db.clone()))
+            .and_then(handler::get_production_house_handler))
+        .or(production_houses
+            .and(warp::put())
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and(negotiation::request_format())
+            .and(warp::body::aggregate())
+            .and(with_db(db.clone()))
+            .and_then(handler::update_production_house_handler))
+        .or(production_houses
+            .and(warp::delete())
             .and(warp::path::param())
             .and(warp::path::end())
             .and(with_db(db.clone()))
-            .and_then(handler::delete_movie_handler));
-    return movie_routes.boxed();
+            .and_then(handler::delete_production_house_handler))
+        .or(production_houses
+            .and(warp::post())
+            .and(warp::path::param())
+            .and(warp::path("merge"))
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and(negotiation::request_format())
+            .and(warp::body::aggregate())
+            .and(with_db(db.clone()))
+            .and_then(handler::merge_production_houses_handler))
+        .or(production_houses
+            .and(warp::get())
+            .and(warp::path::param())
+            .and(warp::path("trackrecord"))
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and(with_db(db.clone()))
+            .and_then(handler::read_track_record_handler));
+    let movie_credits = movies
+        .and(warp::path::param::<String>())
+        .and(warp::path("credits"))
+        .and(warp::path::end());
+    let credit_routes = movie_credits
+        .and(warp::get())
+        .and(negotiation::response_format(true))
+        .and(with_db(db.clone()))
+        .and_then(handler::read_credits_handler)
+        .or(movie_credits
+            .and(warp::put())
+            .and(negotiation::response_format(false))
+            .and(negotiation::request_format())
+            .and(warp::body::aggregate())
+            .and(with_db(db.clone()))
+            .and_then(handler::update_credits_handler));
+    let movie_relations = movies
+        .and(warp::path::param::<String>())
+        .and(warp::path("relations"))
+        .and(warp::path::end());
+    let relation_routes = movie_relations
+        .and(warp::get())
+        .and(negotiation::response_format(false))
+        .and(with_db(db.clone()))
+        .and_then(handler::read_relations_handler)
+        .or(movie_relations
+            .and(warp::put())
+            .and(negotiation::response_format(false))
+            .and(negotiation::request_format())
+            .and(warp::body::aggregate())
+            .and(with_db(db.clone()))
+            .and_then(handler::update_relations_handler));
+    let franchises = warp::path("boxoffice")
+        .and(warp::path("v2"))
+        .and(warp::path("franchises"));
+    let franchise_routes = franchises
+        .and(warp::post())
+        .and(warp::path::end())
+        .and(negotiation::response_format(false))
+        .and(negotiation::request_format())
+        .and(warp::body::aggregate())
+        .and(with_db(db.clone()))
+        .and_then(handler::create_franchise_handler)
+        .or(franchises
+            .and(warp::get())
+            .and(warp::path::end())
+            .and(negotiation::response_format(true))
+            .and(with_db(db.clone()))
+            .and_then(handler::read_franchises_handler))
+        .or(franchises
+            .and(warp::get())
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and(with_db(db.clone()))
+            .and_then(handler::get_franchise_handler))
+        .or(franchises
+            .and(warp::put())
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and(negotiation::request_format())
+            .and(warp::body::aggregate())
+            .and(with_db(db.clone()))
+            .and_then(handler::update_franchise_handler))
+        .or(franchises
+            .and(warp::delete())
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(with_db(db.clone()))
+            .and_then(handler::delete_franchise_handler))
+        .or(franchises
+            .and(warp::get())
+            .and(warp::path::param())
+            .and(warp::path("summary"))
+            .and(warp::path::end())
+            .and(negotiation::response_format(true))
+            .and(with_db(db.clone()))
+            .and_then(handler::read_franchise_summary_handler));
+    let search = warp::path("boxoffice")
+        .and(warp::path("v2"))
+        .and(warp::path("search"));
+    let search_routes = search
+        .and(warp::get())
+        .and(warp::path::end())
+        .and(warp::query::<SearchQuery>())
+        .and(negotiation::response_format(true))
+        .and(with_db(db.clone()))
+        .and_then(handler::search_handler)
+        .or(search
+            .and(warp::get())
+            .and(warp::path("suggest"))
+            .and(warp::path::end())
+            .and(warp::query::<SearchQuery>())
+            .and(negotiation::response_format(true))
+            .and(with_db(db.clone()))
+            .and_then(handler::suggest_handler));
+    let people = warp::path("boxoffice")
+        .and(warp::path("v2"))
+        .and(warp::path("people"));
+    let people_routes = people
+        .and(warp::post())
+        .and(warp::path::end())
+        .and(negotiation::response_format(false))
+        .and(negotiation::request_format())
+        .and(warp::body::aggregate())
+        .and(with_db(db.clone()))
+        .and_then(handler::create_person_handler)
+        .or(people
+            .and(warp::get())
+            .and(warp::path::end())
+            .and(negotiation::response_format(true))
+            .and(with_db(db.clone()))
+            .and_then(handler::read_people_handler))
+        .or(people
+            .and(warp::get())
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and(with_db(db.clone()))
+            .and_then(handler::get_person_handler))
+        .or(people
+            .and(warp::put())
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and(negotiation::request_format())
+            .and(warp::body::aggregate())
+            .and(with_db(db.clone()))
+            .and_then(handler::update_person_handler))
+        .or(people
+            .and(warp::delete())
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(with_db(db.clone()))
+            .and_then(handler::delete_person_handler))
+        .or(people
+            .and(warp::get())
+            .and(warp::path::param())
+            .and(warp::path("filmography"))
+            .and(warp::path::end())
+            .and(warp::query::<FilmographyQuery>())
+            .and(negotiation::response_format(false))
+            .and(with_db(db.clone()))
+            .and_then(handler::read_filmography_handler))
+        .or(warp::path("boxoffice")
+            .and(warp::path("v2"))
+            .and(warp::path("roles"))
+            .and(warp::get())
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and_then(handler::read_roles_handler));
+    let health = warp::path("boxoffice").and(warp::path("health"));
+    let health_routes = health
+        .and(warp::get())
+        .and(warp::path("live"))
+        .and(warp::path::end())
+        .and(negotiation::response_format(false))
+        .and_then(handler::liveness_handler)
+        .or(health
+            .and(warp::get())
+            .and(warp::path("ready"))
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and(with_readiness(readiness))
+            .and_then(handler::readiness_handler));
+    let webhook = warp::path("boxoffice")
+        .and(warp::path("webhooks"))
+        .and(warp::path("v1"));
+    let webhook_routes = webhook
+        .and(warp::post())
+        .and(warp::path("createwebhook"))
+        .and(warp::path::end())
+        .and(negotiation::response_format(false))
+        .and(negotiation::request_format())
+        .and(warp::body::aggregate())
+        .and(with_db(db.clone()))
+        .and_then(handler::create_webhook_handler)
+        .or(webhook
+            .and(warp::get())
+            .and(warp::path("readwebhooks"))
+            .and(warp::path::end())
+            .and(negotiation::response_format(true))
+            .and(with_db(db.clone()))
+            .and_then(handler::read_webhooks_handler))
+        .or(webhook
+            .and(warp::delete())
+            .and(warp::path("deletewebhook"))
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and(with_db(db.clone()))
+            .and_then(handler::delete_webhook_handler))
+        .or(webhook
+            .and(warp::get())
+            .and(warp::path("readdeliveries"))
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(negotiation::response_format(true))
+            .and(with_db(db.clone()))
+            .and_then(handler::read_webhook_deliveries_handler))
+        .or(webhook
+            .and(warp::get())
+            .and(warp::path("readdeadletters"))
+            .and(warp::path::end())
+            .and(negotiation::response_format(true))
+            .and(with_db(db.clone()))
+            .and_then(handler::read_dead_letters_handler))
+        .or(webhook
+            .and(warp::post())
+            .and(warp::path("retrydeadletter"))
+            .and(warp::path::param())
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and(with_db(db.clone()))
+            .and_then(handler::retry_dead_letter_handler));
+    let exchange_rate = warp::path("boxoffice")
+        .and(warp::path("exchangerates"))
+        .and(warp::path("v1"));
+    let exchange_rate_routes = exchange_rate
+        .and(warp::get())
+        .and(warp::path("readexchangerates"))
+        .and(warp::path::end())
+        .and(negotiation::response_format(true))
+        .and(with_db(db.clone()))
+        .and_then(handler::read_exchange_rates_handler)
+        .or(exchange_rate
+            .and(warp::put())
+            .and(warp::path("upsertexchangerates"))
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and(negotiation::request_format())
+            .and(warp::body::aggregate())
+            .and(with_db(db.clone()))
+            .and_then(handler::upsert_exchange_rates_handler));
+    let cpi = warp::path("boxoffice")
+        .and(warp::path("cpi"))
+        .and(warp::path("v1"));
+    let cpi_routes = cpi
+        .and(warp::get())
+        .and(warp::path("readcpi"))
+        .and(warp::path::end())
+        .and(negotiation::response_format(true))
+        .and(with_db(db.clone()))
+        .and_then(handler::read_cpi_handler)
+        .or(cpi
+            .and(warp::put())
+            .and(warp::path("upsertcpi"))
+            .and(warp::path::end())
+            .and(negotiation::response_format(false))
+            .and(negotiation::request_format())
+            .and(warp::body::aggregate())
+            .and(with_db(db.clone()))
+            .and_then(handler::upsert_cpi_handler));
+    let graphql = warp::path("graphql").and(warp::path::end());
+    let graphql_routes = graphql
+        .and(warp::post())
+        .and(async_graphql_warp::graphql(graphql::schema(db.clone())))
+        .and_then(handler::graphql_handler)
+        .or(graphql.and(warp::get()).and_then(handler::graphiql_handler));
+    // Movie routes are boxed apart from the others, so that futures of the route chain
+    // are not nested too deep for the stack of a runtime worker in debug builds.
+    let movie_routes = deprecated_movie_routes
+        .or(movie_event_routes)
+        .or(movie_v2_routes)
+        .or(collection_routes)
+        .or(territory_routes)
+        .or(credit_routes)
+        .or(people_routes)
+        .or(relation_routes)
+        .or(franchise_routes)
+        .or(search_routes)
+        .boxed();
+    return movie_routes
+        .or(production_house_routes)
+        .or(webhook_routes)
+        .or(exchange_rate_routes)
+        .or(cpi_routes)
+        .or(graphql_routes)
+        .or(health_routes)
+        .boxed();
 }
 
 /** Database operations are performed through each handler functions.
@@ -52,3 +544,30 @@ This function is called while setting end-points using warp Filter. */
 fn with_db(db: DB) -> impl Filter<Extract = (DB,), Error = Infallible> + Clone {
     warp::any().map(move || db.clone())
 }
+
+/// Converts rejections of deprecated v1 movie routes to responses, so that error responses
+/// also carry deprecation headers. Requests for other paths are passed on to the remaining routes.
+async fn handle_deprecated_rejection(err: Rejection) -> Result<Box<dyn Reply>, Rejection> {
+    if err.is_not_found() {
+        return Err(err);
+    }
+    return Ok(error::handle_rejection(err)
+        .await
+        .unwrap_or_else(|never| match never {}));
+}
+
+/// Headers announcing that v1 movie routes are replaced by /boxoffice/v2/movies.
+fn deprecation_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(DEPRECATION_HEADER, HeaderValue::from_static(DEPRECATION));
+    headers.insert(SUNSET_HEADER, HeaderValue::from_static(MOVIES_V1_SUNSET));
+    headers.insert(LINK, HeaderValue::from_static(MOVIES_V1_SUCCESSOR_LINK));
+    return headers;
+}
+
+/// Passes shared readiness flag to readiness handler.
+fn with_readiness(
+    readiness: Readiness,
+) -> impl Filter<Extract = (Readiness,), Error = Infallible> + Clone {
+    warp::any().map(move || readiness.clone())
+}
@@ -0,0 +1,401 @@
+/// Search over titles, production houses and cast of movies.
+/// Full-text search runs on the MONGODB text index; this module holds the in-process index which ranks
+/// the movies found, tolerates typos and completes partly typed queries.
+/// Words are folded before they are compared, so that common spellings of transliterated Hindi titles
+/// match each other, e.g. 'Dilwale' and 'Dilvaale', or 'Dhoom' and 'Dhum'.
+use crate::constants::*;
+use crate::events::{EventBus, MovieEvent};
+use crate::model::{Movie, SearchField, SearchHit, Suggestion};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::{self, error::TryRecvError};
+
+impl fmt::Display for SearchField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = match self {
+            SearchField::Title => "title",
+            SearchField::ProductionHouse => "production_house",
+            SearchField::Cast => "cast",
+        };
+        return write!(f, "{}", field);
+    }
+}
+
+impl SearchField {
+    /// Weight of the field in relevance, titles weigh the most.
+    pub fn weight(&self) -> f64 {
+        match self {
+            SearchField::Title => TITLE_SEARCH_WEIGHT,
+            SearchField::ProductionHouse => PRODUCTION_HOUSE_SEARCH_WEIGHT,
+            SearchField::Cast => CAST_SEARCH_WEIGHT,
+        }
+    }
+}
+
+/// Returns words of text folded for comparison.
+pub fn tokens(text: &str) -> Vec<String> {
+    return text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(fold)
+        .collect();
+}
+
+/// Folds word to a spelling shared by its common transliterations.
+/// Lowercases it, writes 'w' as 'v', 'z' as 'j', 'q' as 'k', 'ph' as 'f', 'ee' as 'i', 'oo' as 'u'
+/// and a final 'y' as 'i', drops 'h' of aspirated consonants and 'sh', and writes doubled letters once.
+pub fn fold(word: &str) -> String {
+    let letters: Vec<char> = word.to_lowercase().chars().collect();
+    let mut folded = String::new();
+    let mut i = 0;
+    while i < letters.len() {
+        let letter = letters[i];
+        let next = letters.get(i + 1).copied();
+        let folded_letter = match (letter, next) {
+            ('p', Some('h')) => 'f',
+            ('k' | 'g' | 'c' | 'j' | 't' | 'd' | 'b' | 's', Some('h')) => letter,
+            ('c', Some('k')) => {
+                i += 1;
+                continue;
+            }
+            ('e', Some('e')) => 'i',
+            ('o', Some('o')) => 'u',
+            ('w', _) => 'v',
+            ('z', _) => 'j',
+            ('q', _) => 'k',
+            ('y', None) => 'i',
+            _ => letter,
+        };
+        i += 1;
+        // Letters written for the one folded letter, e.g. 'h' of 'chh' or the second 'e' of 'ee'.
+        if next == Some('h') && folded_letter != 'h' && letter != 'h' {
+            while letters.get(i) == Some(&'h') {
+                i += 1;
+            }
+        } else if matches!((letter, next), ('e', Some('e')) | ('o', Some('o'))) {
+            i += 1;
+        }
+        // Doubled letters are written once, digits are kept as they are.
+        if !folded_letter.is_ascii_digit() && folded.ends_with(folded_letter) {
+            continue;
+        }
+        folded.push(folded_letter);
+    }
+    return folded;
+}
+
+/// Returns number of typos allowed in a query word, longer words allow more.
+fn allowed_typos(word: &str) -> usize {
+    match word.chars().count() {
+        length if length >= MINIMUM_TWO_TYPOS_LENGTH => 2,
+        length if length >= MINIMUM_ONE_TYPO_LENGTH => 1,
+        _ => 0,
+    }
+}
+
+/// Returns number of insertions, deletions, substitutions and transpositions of adjacent letters
+/// turning one word into the other (optimal string alignment distance).
+pub fn edit_distance(first: &str, second: &str) -> usize {
+    let first: Vec<char> = first.chars().collect();
+    let second: Vec<char> = second.chars().collect();
+    let mut distances = vec![vec![0; second.len() + 1]; first.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=first.len() {
+        for j in 1..=second.len() {
+            let substitution = usize::from(first[i - 1] != second[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + substitution);
+            if i > 1 && j > 1 && first[i - 1] == second[j - 2] && first[i - 2] == second[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    return distances[first.len()][second.len()];
+}
+
+/// Returns how well a folded query word matches a folded word of a movie, none if it does not match.
+fn word_score(query_word: &str, word: &str) -> Option<f64> {
+    if query_word == word {
+        return Some(1.0);
+    }
+    if query_word.chars().count() >= MINIMUM_PREFIX_LENGTH && word.starts_with(query_word) {
+        return Some(PREFIX_MATCH_SCORE);
+    }
+    let allowed_typos = allowed_typos(query_word);
+    if allowed_typos == 0
+        || query_word.chars().count().abs_diff(word.chars().count()) > allowed_typos
+    {
+        return None;
+    }
+    return match edit_distance(query_word, word) {
+        1 => Some(ONE_TYPO_MATCH_SCORE),
+        2 if allowed_typos >= 2 => Some(TWO_TYPOS_MATCH_SCORE),
+        _ => None,
+    };
+}
+
+/// Returns how well a folded, partly typed query word matches the beginning of a folded word, none if it does not.
+fn prefix_score(query_word: &str, word: &str) -> Option<f64> {
+    if word.starts_with(query_word) {
+        return Some(1.0);
+    }
+    let length = query_word.chars().count();
+    if allowed_typos(query_word) == 0 || word.chars().count() < length {
+        return None;
+    }
+    let beginning: String = word.chars().take(length).collect();
+    return match edit_distance(query_word, &beginning) {
+        1 => Some(ONE_TYPO_MATCH_SCORE),
+        _ => None,
+    };
+}
+
+/// Occurrence of a folded word in a field of a movie.
+#[derive(Debug)]
+struct Posting {
+    movie: usize,
+    field: SearchField,
+}
+
+/// Title, production house or name of a credited person offered as completion.
+#[derive(Debug)]
+struct Term {
+    text: String,
+    field: SearchField,
+    movie_id: Option<u8>,
+    words: Vec<String>,
+}
+
+/// In-process search index over movies.
+#[derive(Debug)]
+pub struct SearchIndex {
+    movies: Vec<Movie>,
+    /// Folded words to the movies and fields they occur in.
+    postings: BTreeMap<String, Vec<Posting>>,
+    terms: Vec<Term>,
+    /// Text index scores of movies found by full-text search, by Movie Id.
+    text_scores: BTreeMap<u8, f64>,
+}
+
+impl SearchIndex {
+    /// Indexes title, production house and names of credited people of the movies.
+    pub fn new(movies: &[Movie]) -> Self {
+        let mut postings: BTreeMap<String, Vec<Posting>> = BTreeMap::new();
+        let mut terms: Vec<Term> = Vec::new();
+        for (movie_index, movie) in movies.iter().enumerate() {
+            let mut fields = vec![
+                (SearchField::Title, movie.title.as_str()),
+                (
+                    SearchField::ProductionHouse,
+                    movie.production_house.as_str(),
+                ),
+            ];
+            fields.extend(
+                movie
+                    .credits
+                    .iter()
+                    .flatten()
+                    .map(|credit| (SearchField::Cast, credit.name.as_str())),
+            );
+            for (field, text) in fields {
+                let words = tokens(text);
+                // Adjacent words are also indexed written together, e.g. 'Shah Rukh' as 'Shahrukh'.
+                let joined_words = words.windows(2).map(|pair| fold(&pair.concat()));
+                for word in words.iter().cloned().chain(joined_words) {
+                    postings.entry(word).or_default().push(Posting {
+                        movie: movie_index,
+                        field,
+                    });
+                }
+                // Titles are offered with their movie, production houses and people once.
+                let movie_id = match field {
+                    SearchField::Title => Some(movie.movie_id),
+                    _ => None,
+                };
+                if !terms.iter().any(|term| {
+                    term.field == field && term.movie_id == movie_id && term.words == words
+                }) {
+                    terms.push(Term {
+                        text: text.to_string(),
+                        field,
+                        movie_id,
+                        words,
+                    });
+                }
+            }
+        }
+        return Self {
+            movies: movies.to_vec(),
+            postings,
+            terms,
+            text_scores: BTreeMap::new(),
+        };
+    }
+
+    /// Adds text index scores of the movies to their relevance.
+    pub fn with_text_scores(mut self, text_scores: BTreeMap<u8, f64>) -> Self {
+        self.text_scores = text_scores;
+        return self;
+    }
+
+    /// Returns movies matching any word of the query, most relevant first, at most 'limit' of them.
+    /// Relevance adds up the best match of every query word weighted by the field matched and the scaled text score,
+    /// movies whose title is the whole query come first. Movies with a text score are returned even if no word matches here.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let query_words = tokens(query);
+        let mut scores: BTreeMap<usize, (f64, BTreeSet<SearchField>)> = BTreeMap::new();
+        for (movie_index, movie) in self.movies.iter().enumerate() {
+            if let Some(text_score) = self.text_scores.get(&movie.movie_id) {
+                scores.entry(movie_index).or_default().0 += text_score * TEXT_SCORE_SEARCH_WEIGHT;
+            }
+        }
+        for query_word in &query_words {
+            let mut best_scores: BTreeMap<usize, f64> = BTreeMap::new();
+            for (word, postings) in &self.postings {
+                let word_score = match word_score(query_word, word) {
+                    Some(word_score) => word_score,
+                    None => continue,
+                };
+                for posting in postings {
+                    let score = word_score * posting.field.weight();
+                    let best_score = best_scores.entry(posting.movie).or_default();
+                    *best_score = best_score.max(score);
+                    scores
+                        .entry(posting.movie)
+                        .or_default()
+                        .1
+                        .insert(posting.field);
+                }
+            }
+            for (movie, best_score) in best_scores {
+                scores.entry(movie).or_default().0 += best_score;
+            }
+        }
+        let mut hits: Vec<SearchHit> = scores
+            .into_iter()
+            .map(|(movie_index, (mut score, matched_fields))| {
+                let movie = &self.movies[movie_index];
+                if tokens(&movie.title) == query_words {
+                    score += EXACT_TITLE_SEARCH_BONUS;
+                }
+                SearchHit {
+                    movie_id: movie.movie_id,
+                    title: movie.title.clone(),
+                    production_house: movie.production_house.clone(),
+                    year_of_release: movie.year_of_release,
+                    verdict: movie.verdict.clone(),
+                    score: (score * 100.0).round() / 100.0,
+                    matched_fields: matched_fields.into_iter().collect(),
+                }
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.title.cmp(&b.title))
+                .then(a.movie_id.cmp(&b.movie_id))
+        });
+        hits.truncate(limit);
+        return hits;
+    }
+
+    /// Returns titles, production houses and people completing a partly typed query, at most 'limit' of them.
+    /// Every query word but the last has to match a word of the completion, the last one its beginning.
+    /// Completions starting with the query come first.
+    pub fn suggest(&self, query: &str, limit: usize) -> Vec<Suggestion> {
+        let query_words = tokens(query);
+        let (last_query_word, query_words) = match query_words.split_last() {
+            Some(split) => split,
+            None => return Vec::new(),
+        };
+        let mut suggestions: Vec<(f64, &Term)> = self
+            .terms
+            .iter()
+            .filter_map(|term| {
+                let all_words_match = query_words.iter().all(|query_word| {
+                    term.words
+                        .iter()
+                        .any(|word| word_score(query_word, word).is_some())
+                });
+                if !all_words_match {
+                    return None;
+                }
+                let (position, score) = term
+                    .words
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(position, word)| {
+                        prefix_score(last_query_word, word).map(|score| (position, score))
+                    })
+                    .max_by(|a, b| a.1.total_cmp(&b.1))?;
+                let starts_with_query = position == query_words.len();
+                let score = score * term.field.weight() * if starts_with_query { 2.0 } else { 1.0 };
+                Some((score, term))
+            })
+            .collect();
+        suggestions.sort_by(|a, b| {
+            b.0.total_cmp(&a.0)
+                .then(a.1.text.len().cmp(&b.1.text.len()))
+                .then_with(|| a.1.text.cmp(&b.1.text))
+        });
+        return suggestions
+            .into_iter()
+            .take(limit)
+            .map(|(_, term)| Suggestion {
+                text: term.text.clone(),
+                field: term.field,
+                movie_id: term.movie_id,
+            })
+            .collect();
+    }
+}
+
+/// Search index over all movies, shared by clones.
+/// Index is dropped on any movie event published on the event bus and rebuilt by the next search.
+#[derive(Debug, Clone)]
+pub struct SearchIndexCache {
+    cached: Arc<Mutex<CachedIndex>>,
+}
+
+#[derive(Debug)]
+struct CachedIndex {
+    index: Option<Arc<SearchIndex>>,
+    events: broadcast::Receiver<MovieEvent>,
+}
+
+impl SearchIndexCache {
+    /// Returns empty cache following movie events of the event bus.
+    pub fn new(events: &EventBus) -> Self {
+        let cached = CachedIndex {
+            index: None,
+            events: events.receiver(),
+        };
+        return Self {
+            cached: Arc::new(Mutex::new(cached)),
+        };
+    }
+
+    /// Returns cached index, none if there is none or a movie changed since it was built.
+    /// Index built from movies read after this call is current until the next movie event.
+    pub fn get(&self) -> Option<Arc<SearchIndex>> {
+        let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+        while let Ok(_) | Err(TryRecvError::Lagged(_)) = cached.events.try_recv() {
+            cached.index = None;
+        }
+        return cached.index.clone();
+    }
+
+    /// Caches index built from all movies.
+    pub fn set(&self, index: Arc<SearchIndex>) {
+        let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+        cached.index = Some(index);
+    }
+}
@@ -0,0 +1,100 @@
+/// Starts HTTP or HTTPS server and stops it gracefully.
+use crate::config::ServerConfig;
+use crate::{error::BoxOfficeError, request_context, shutdown, shutdown::Readiness, tls, Result};
+use hyper::server::{accept, conn::AddrStream};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::Server;
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+use tokio_rustls::server::TlsStream;
+use warp::{Filter, Reply};
+
+/// Serves routes on configured address until a termination signal is received
+/// and in-flight requests are drained.
+/// Uses TLS when server configuration contains TLS section.
+/// Every request is handled within its request id scope, see 'request_context'.
+pub async fn run<F, R>(routes: F, server_config: &ServerConfig, readiness: Readiness) -> Result<()>
+where
+    F: Filter<Extract = (R,), Error = Infallible> + Clone + Send + Sync + 'static,
+    R: Reply,
+{
+    let (shutdown_started_sender, shutdown_started_receiver) = oneshot::channel();
+    let shutdown_signal = shutdown::shutdown_signal(
+        readiness.clone(),
+        server_config.readiness_grace_period(),
+        shutdown_started_sender,
+    );
+    let filtered_service = warp::service(routes);
+
+    if let Some(tls_config) = &server_config.tls {
+        let rustls_config = Arc::new(tls::ReloadingServerConfig::new(tls_config)?);
+        tls::watch_certificate(rustls_config.clone(), tls_config);
+        let listener = TcpListener::bind(server_config.socket_address())
+            .await
+            .map_err(|e| BoxOfficeError::ServerError(e.to_string()))?;
+        let address = listener
+            .local_addr()
+            .map_err(|e| BoxOfficeError::ServerError(e.to_string()))?;
+        let make_service = make_service_fn(move |connection: &TlsStream<TcpStream>| {
+            let remote_address = connection.get_ref().0.peer_addr().ok();
+            let filtered_service = filtered_service.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    request_context::handle(filtered_service.clone(), remote_address, request)
+                }))
+            }
+        });
+        let server = Server::builder(accept::from_stream(tls::incoming(
+            listener,
+            rustls_config,
+            tls_config.handshake_timeout(),
+        )))
+        .serve(make_service)
+        .with_graceful_shutdown(shutdown_signal);
+        readiness.set_ready();
+        log::info!("Listening on port {} (HTTPS).", address.port());
+        println!("Listening on port {} (HTTPS).", address.port());
+        shutdown::run_until_drained(
+            log_server_error(server),
+            shutdown_started_receiver,
+            server_config.drain_timeout(),
+        )
+        .await;
+    } else {
+        let make_service = make_service_fn(move |connection: &AddrStream| {
+            let remote_address = Some(connection.remote_addr());
+            let filtered_service = filtered_service.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    request_context::handle(filtered_service.clone(), remote_address, request)
+                }))
+            }
+        });
+        let server = Server::try_bind(&server_config.socket_address())
+            .map_err(|e| BoxOfficeError::ServerError(e.to_string()))?
+            .serve(make_service);
+        let address = server.local_addr();
+        let server = server.with_graceful_shutdown(shutdown_signal);
+        readiness.set_ready();
+        log::info!("Listening on port {}.", address.port());
+        println!("Listening on port {}.", address.port());
+        shutdown::run_until_drained(
+            log_server_error(server),
+            shutdown_started_receiver,
+            server_config.drain_timeout(),
+        )
+        .await;
+    }
+    return Ok(());
+}
+
+/// Logs error which stopped the server.
+async fn log_server_error(
+    server: impl std::future::Future<Output = std::result::Result<(), hyper::Error>>,
+) {
+    if let Err(e) = server.await {
+        log::error!("Server error: {}", e);
+    }
+}
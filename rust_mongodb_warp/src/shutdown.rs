@@ -0,0 +1,98 @@
+/// Graceful shutdown support.
+/// Listens for termination signals and tracks readiness of the server.
+use crate::constants::*;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+/// Shared readiness flag.
+/// Server is ready once it starts listening and becomes not-ready as soon as shutdown begins,
+/// so that readiness probes stop routing traffic before connections are drained.
+#[derive(Clone, Debug, Default)]
+pub struct Readiness {
+    ready: Arc<AtomicBool>,
+}
+
+impl Readiness {
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+
+    pub fn set_ready(&self) {
+        self.ready.store(true, Ordering::SeqCst);
+    }
+
+    pub fn set_not_ready(&self) {
+        self.ready.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Completes when SIGTERM or SIGINT (Ctrl+C) is received.
+pub async fn termination_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl+C handler.");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler.")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => log::info!("SIGINT received."),
+        _ = terminate => log::info!("SIGTERM received."),
+    }
+}
+
+/// Waits for a termination signal, then flips readiness to not-ready
+/// and waits for the readiness grace period before letting the server stop accepting connections.
+/// 'shutdown_started' is notified once the server starts draining.
+pub async fn shutdown_signal(
+    readiness: Readiness,
+    readiness_grace_period: Duration,
+    shutdown_started: oneshot::Sender<()>,
+) {
+    termination_signal().await;
+    readiness.set_not_ready();
+    log::info!("{}", SERVER_NOT_READY);
+    println!("{}", SERVER_NOT_READY);
+    if !readiness_grace_period.is_zero() {
+        tokio::time::sleep(readiness_grace_period).await;
+    }
+    log::info!("Stopped accepting new connections, draining in-flight requests.");
+    let _ = shutdown_started.send(());
+}
+
+/// Runs server until all in-flight requests are drained.
+/// If draining takes longer than 'drain_timeout' remaining connections are dropped.
+pub async fn run_until_drained(
+    server: impl Future<Output = ()>,
+    shutdown_started: oneshot::Receiver<()>,
+    drain_timeout: Duration,
+) {
+    let drain_deadline = async {
+        if shutdown_started.await.is_ok() {
+            tokio::time::sleep(drain_timeout).await;
+        } else {
+            std::future::pending::<()>().await;
+        }
+    };
+
+    tokio::select! {
+        _ = server => log::info!("All in-flight requests drained."),
+        _ = drain_deadline => log::warn!(
+            "Drain timeout of {:?} elapsed, dropping remaining connections.",
+            drain_timeout
+        ),
+    }
+}
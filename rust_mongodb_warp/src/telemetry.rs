@@ -0,0 +1,166 @@
+/// OpenTelemetry tracing.
+/// Handler and database spans are exported over OTLP/HTTP or appended to a JSON file.
+/// Incoming W3C 'traceparent' header is used as parent of the request span.
+use crate::config::{TracingConfig, TracingExporter};
+use crate::{constants::*, error::BoxOfficeError, tls, Result};
+use futures::future::BoxFuture;
+use hyper::HeaderMap;
+use opentelemetry::propagation::Extractor;
+use opentelemetry::trace::{TraceError, TracerProvider as _};
+use opentelemetry::{global, KeyValue};
+use opentelemetry_http::hyper::HyperClient;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::export::trace::{ExportResult, SpanData, SpanExporter};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{self as sdktrace, TracerProvider};
+use opentelemetry_sdk::{runtime, Resource};
+use serde_json::json;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+/// Installs tracing subscriber exporting spans as configured.
+/// Does nothing when exporter is 'none'.
+pub fn init(tracing_config: &TracingConfig) -> Result<()> {
+    let trace_config = sdktrace::config().with_resource(Resource::new(vec![KeyValue::new(
+        SERVICE_NAME_RESOURCE,
+        tracing_config.service_name.clone(),
+    )]));
+
+    let tracer = match tracing_config.exporter {
+        TracingExporter::None => return Ok(()),
+        TracingExporter::Otlp => opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(
+                opentelemetry_otlp::new_exporter()
+                    .http()
+                    .with_http_client(HyperClient::new_with_timeout(
+                        tls::https_client(),
+                        OTLP_EXPORT_TIMEOUT,
+                    ))
+                    .with_endpoint(tracing_config.otlp_endpoint.clone()),
+            )
+            .with_trace_config(trace_config)
+            .install_batch(runtime::Tokio)
+            .map_err(|e| BoxOfficeError::TracingError(e.to_string()))?,
+        TracingExporter::JsonFile => {
+            let tracer_provider = TracerProvider::builder()
+                .with_config(trace_config)
+                .with_batch_exporter(
+                    JsonFileSpanExporter::new(tracing_config.json_file_path.clone()),
+                    runtime::Tokio,
+                )
+                .build();
+            let tracer = tracer_provider.tracer(TRACER_NAME);
+            global::set_tracer_provider(tracer_provider);
+            tracer
+        }
+    };
+
+    // Only spans of this application are exported, not those of hyper or mongodb internals.
+    let application_spans = Targets::new().with_target(TRACER_NAME, tracing::Level::INFO);
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    tracing_subscriber::registry()
+        .with(
+            tracing_opentelemetry::layer()
+                .with_tracer(tracer)
+                .with_filter(application_spans),
+        )
+        .try_init()
+        .map_err(|e| BoxOfficeError::TracingError(e.to_string()))?;
+    log::info!(
+        "Tracing enabled, exporting spans to {:?}.",
+        tracing_config.exporter
+    );
+    return Ok(());
+}
+
+/// Exports remaining spans before the process exits.
+pub fn shutdown() {
+    global::shutdown_tracer_provider();
+}
+
+/// Reads W3C trace context from request headers.
+pub fn extract_context(headers: &HeaderMap) -> opentelemetry::Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)))
+}
+
+/// Exposes hyper headers to OpenTelemetry propagator.
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl<'a> Extractor for HeaderExtractor<'a> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|name| name.as_str()).collect()
+    }
+}
+
+/// Span exporter appending one JSON object per span to a file.
+#[derive(Debug)]
+pub struct JsonFileSpanExporter {
+    path: PathBuf,
+}
+
+impl JsonFileSpanExporter {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    fn write(&self, batch: Vec<SpanData>) -> std::io::Result<()> {
+        if let Some(directory) = self.path.parent() {
+            std::fs::create_dir_all(directory)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        for span in batch {
+            let attributes: serde_json::Map<String, serde_json::Value> = span
+                .attributes
+                .iter()
+                .map(|key_value| {
+                    (
+                        key_value.key.to_string(),
+                        serde_json::Value::String(key_value.value.to_string()),
+                    )
+                })
+                .collect();
+            let line = json!({
+                "trace_id": span.span_context.trace_id().to_string(),
+                "span_id": span.span_context.span_id().to_string(),
+                "parent_span_id": span.parent_span_id.to_string(),
+                "name": span.name,
+                "kind": format!("{:?}", span.span_kind),
+                "start_time_unix_nano": unix_nanos(span.start_time),
+                "end_time_unix_nano": unix_nanos(span.end_time),
+                "attributes": attributes,
+                "status": format!("{:?}", span.status),
+            });
+            writeln!(file, "{}", line)?;
+        }
+        return file.flush();
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default()
+}
+
+impl SpanExporter for JsonFileSpanExporter {
+    fn export(&mut self, batch: Vec<SpanData>) -> BoxFuture<'static, ExportResult> {
+        let result = self
+            .write(batch)
+            .map_err(|e| TraceError::Other(Box::new(e)));
+        Box::pin(std::future::ready(result))
+    }
+}
@@ -0,0 +1,245 @@
+/// TLS termination for the HTTP server.
+/// Loads PEM certificate and key, optionally verifies client certificates
+/// and reloads certificates when files change on disk.
+/// Also provides the client used for outbound HTTP and HTTPS requests.
+use crate::config::{ClientAuth, TlsConfig};
+use crate::{constants::*, error::BoxOfficeError, Result};
+use futures::Stream;
+use hyper::client::HttpConnector;
+use hyper_rustls::HttpsConnector;
+use rustls::server::{
+    AllowAnyAnonymousOrAuthenticatedClient, AllowAnyAuthenticatedClient, NoClientAuth,
+};
+use rustls::{Certificate, PrivateKey, RootCertStore, ServerConfig};
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::{server::TlsStream, TlsAcceptor};
+
+/// TLS server configuration which can be rebuilt while the server is running.
+/// Certificate, key and client CA certificates are read again on reload,
+/// so that both the server certificate and client certificate verification follow the files on disk.
+pub struct ReloadingServerConfig {
+    tls_config: TlsConfig,
+    server_config: RwLock<Arc<ServerConfig>>,
+}
+
+impl ReloadingServerConfig {
+    /// Builds rustls server configuration from TLS configuration.
+    pub fn new(tls_config: &TlsConfig) -> Result<Self> {
+        let server_config = server_config(tls_config)?;
+        Ok(Self {
+            tls_config: tls_config.clone(),
+            server_config: RwLock::new(server_config),
+        })
+    }
+
+    /// Reads certificate, key and client CA certificates again.
+    /// On failure the previously loaded configuration stays in use.
+    pub fn reload(&self) -> Result<()> {
+        let server_config = server_config(&self.tls_config)?;
+        *self.server_config.write().unwrap() = server_config;
+        return Ok(());
+    }
+
+    /// Configuration used for new connections.
+    pub fn current(&self) -> Arc<ServerConfig> {
+        self.server_config.read().unwrap().clone()
+    }
+
+    /// Latest modification time of certificate, key and client CA files.
+    fn last_modified(&self) -> Option<SystemTime> {
+        let certificate_modified = modified(&self.tls_config.certificate_path)?;
+        let private_key_modified = modified(&self.tls_config.private_key_path)?;
+        let mut last_modified = certificate_modified.max(private_key_modified);
+        if let Some(client_ca_path) = &self.tls_config.client_ca_path {
+            last_modified = last_modified.max(modified(client_ca_path)?);
+        }
+        return Some(last_modified);
+    }
+}
+
+/// Builds rustls server configuration from TLS configuration.
+pub fn server_config(tls_config: &TlsConfig) -> Result<Arc<ServerConfig>> {
+    let certificates = load_certificates(&tls_config.certificate_path)?;
+    let private_key = load_private_key(&tls_config.private_key_path)?;
+
+    let client_certificate_verifier = match tls_config.client_auth {
+        ClientAuth::None => NoClientAuth::boxed(),
+        ClientAuth::Optional => {
+            AllowAnyAnonymousOrAuthenticatedClient::new(load_client_roots(tls_config)?).boxed()
+        }
+        ClientAuth::Required => {
+            AllowAnyAuthenticatedClient::new(load_client_roots(tls_config)?).boxed()
+        }
+    };
+
+    let mut server_config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_client_cert_verifier(client_certificate_verifier)
+        .with_single_cert(certificates, private_key)
+        .map_err(|e| {
+            BoxOfficeError::TlsConfigurationError(format!(
+                "{}: {}",
+                tls_config.private_key_path.display(),
+                e
+            ))
+        })?;
+    server_config.alpn_protocols = vec![ALPN_HTTP2.to_vec(), ALPN_HTTP1_1.to_vec()];
+    return Ok(Arc::new(server_config));
+}
+
+/// Accepts TCP connections and performs TLS handshakes.
+/// Returns stream of established TLS connections to be served by warp.
+/// Failed handshakes are logged and skipped so that one bad client can not stop the server.
+/// Handshakes not completed within 'handshake_timeout' are abandoned and the connection is closed.
+/// Every connection uses the configuration current at the time it is accepted.
+pub fn incoming(
+    listener: TcpListener,
+    server_config: Arc<ReloadingServerConfig>,
+    handshake_timeout: Duration,
+) -> impl Stream<Item = std::io::Result<TlsStream<TcpStream>>> {
+    let (sender, receiver) = mpsc::channel(TLS_ACCEPT_BACKLOG);
+
+    tokio::spawn(async move {
+        loop {
+            let accepted = tokio::select! {
+                accepted = listener.accept() => accepted,
+                // Server stopped reading connections, it is shutting down.
+                _ = sender.closed() => break,
+            };
+            let (tcp_stream, remote_address) = match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    log::error!("Unable to accept connection: {}", e);
+                    continue;
+                }
+            };
+            let acceptor = TlsAcceptor::from(server_config.current());
+            let sender = sender.clone();
+            tokio::spawn(async move {
+                match tokio::time::timeout(handshake_timeout, acceptor.accept(tcp_stream)).await {
+                    Ok(Ok(tls_stream)) => {
+                        let _ = sender.send(Ok(tls_stream)).await;
+                    }
+                    Ok(Err(e)) => {
+                        log::error!("TLS handshake with {} failed: {}", remote_address, e)
+                    }
+                    Err(_) => log::warn!(
+                        "TLS handshake with {} not completed within {:?}, connection closed.",
+                        remote_address,
+                        handshake_timeout
+                    ),
+                }
+            });
+        }
+    });
+
+    futures::stream::unfold(receiver, |mut receiver| async move {
+        receiver
+            .recv()
+            .await
+            .map(|tls_stream| (tls_stream, receiver))
+    })
+}
+
+/// Periodically checks certificate, key and client CA files and reloads them when they change.
+pub fn watch_certificate(server_config: Arc<ReloadingServerConfig>, tls_config: &TlsConfig) {
+    let reload_interval = tls_config.certificate_reload_interval();
+    if reload_interval.is_zero() {
+        return;
+    }
+    tokio::spawn(async move {
+        let mut last_modified = server_config.last_modified();
+        let mut interval = tokio::time::interval(reload_interval);
+        loop {
+            interval.tick().await;
+            let modified = server_config.last_modified();
+            if modified.is_none() || modified == last_modified {
+                continue;
+            }
+            match server_config.reload() {
+                Ok(()) => {
+                    last_modified = modified;
+                    log::info!("TLS certificate reloaded.");
+                }
+                Err(e) => log::error!("{}", e),
+            }
+        }
+    });
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn read_pem_items(path: &Path) -> Result<Vec<rustls_pemfile::Item>> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| BoxOfficeError::TlsConfigurationError(format!("{}: {}", path.display(), e)))?;
+    return rustls_pemfile::read_all(&mut BufReader::new(file))
+        .map_err(|e| BoxOfficeError::TlsConfigurationError(format!("{}: {}", path.display(), e)));
+}
+
+fn load_certificates(path: &Path) -> Result<Vec<Certificate>> {
+    let certificates: Vec<Certificate> = read_pem_items(path)?
+        .into_iter()
+        .filter_map(|item| match item {
+            rustls_pemfile::Item::X509Certificate(der) => Some(Certificate(der)),
+            _ => None,
+        })
+        .collect();
+    if certificates.is_empty() {
+        return Err(BoxOfficeError::TlsConfigurationError(format!(
+            "{}: {}",
+            path.display(),
+            NO_CERTIFICATE_FOUND
+        )));
+    }
+    return Ok(certificates);
+}
+
+fn load_private_key(path: &Path) -> Result<PrivateKey> {
+    return read_pem_items(path)?
+        .into_iter()
+        .find_map(|item| match item {
+            rustls_pemfile::Item::PKCS8Key(der)
+            | rustls_pemfile::Item::RSAKey(der)
+            | rustls_pemfile::Item::ECKey(der) => Some(PrivateKey(der)),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            BoxOfficeError::TlsConfigurationError(format!(
+                "{}: {}",
+                path.display(),
+                NO_PRIVATE_KEY_FOUND
+            ))
+        });
+}
+
+/// Loads CA certificates used to verify client certificates.
+fn load_client_roots(tls_config: &TlsConfig) -> Result<RootCertStore> {
+    let client_ca_path = tls_config.client_ca_path.as_ref().ok_or_else(|| {
+        BoxOfficeError::TlsConfigurationError(CLIENT_CA_PATH_REQUIRED.to_string())
+    })?;
+    let mut roots = RootCertStore::empty();
+    for certificate in load_certificates(client_ca_path)? {
+        roots.add(&certificate).map_err(|e| {
+            BoxOfficeError::TlsConfigurationError(format!("{}: {}", client_ca_path.display(), e))
+        })?;
+    }
+    return Ok(roots);
+}
+
+/// Client for outbound HTTP and HTTPS requests, trusting the Mozilla root certificates.
+pub fn https_client() -> hyper::Client<HttpsConnector<HttpConnector>> {
+    let connector = hyper_rustls::HttpsConnectorBuilder::new()
+        .with_webpki_roots()
+        .https_or_http()
+        .enable_http1()
+        .enable_http2()
+        .build();
+    return hyper::Client::builder().build(connector);
+}
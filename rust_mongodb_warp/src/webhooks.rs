@@ -0,0 +1,199 @@
+/// Outbound webhooks.
+/// Movie write operations in db_layer queue deliveries in the webhook outbox collection,
+/// in the same transaction as the movie change when MONGODB supports transactions.
+/// A background dispatcher posts them to registered webhooks signed with HMAC-SHA256.
+/// Failed deliveries are retried with exponential backoff and end up as dead letters.
+use crate::config::WebhookConfig;
+use crate::events::{MovieEvent, MovieEventType};
+use crate::{constants::*, db_layer::DB, model::OutboxEntry, model::WebhookDelivery, tls};
+use hmac::{Hmac, Mac};
+use hyper::client::HttpConnector;
+use hyper::{header::CONTENT_TYPE, Body, Client, Method, Request};
+use hyper_rustls::HttpsConnector;
+use serde::Serialize;
+use sha2::Sha256;
+use std::time::{Duration, Instant};
+
+/// Body posted to webhooks.
+/// 'id' is the same for every attempt of a delivery, so receivers can drop duplicates.
+#[derive(Serialize, Debug)]
+pub struct WebhookPayload<'a> {
+    pub id: &'a str,
+    pub event: &'a str,
+    pub timestamp: String,
+    pub data: &'a MovieEvent,
+}
+
+/// Returns webhook events raised by a movie event.
+/// Update changing the verdict raises both 'updated' and 'verdict_changed'.
+pub fn event_types(event: &MovieEvent) -> Vec<&'static str> {
+    let mut event_types = match event.event_type {
+        MovieEventType::Created => vec![WEBHOOK_EVENT_CREATED],
+        MovieEventType::Updated => vec![WEBHOOK_EVENT_UPDATED],
+        MovieEventType::Deleted => vec![WEBHOOK_EVENT_DELETED],
+    };
+    if event.event_type == MovieEventType::Updated && event.old_verdict != event.new_verdict {
+        event_types.push(WEBHOOK_EVENT_VERDICT_CHANGED);
+    }
+    return event_types;
+}
+
+/// Serializes webhook payload stored in the outbox.
+pub fn payload(delivery_id: &str, event_type: &str, event: &MovieEvent) -> String {
+    let webhook_payload = WebhookPayload {
+        id: delivery_id,
+        event: event_type,
+        timestamp: event.timestamp.clone(),
+        data: event,
+    };
+    return serde_json::to_string(&webhook_payload).unwrap_or_default();
+}
+
+/// Returns value of signature header: hex encoded HMAC-SHA256 of the body keyed with webhook secret.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    return format!(
+        "{}{}",
+        WEBHOOK_SIGNATURE_PREFIX,
+        hex::encode(mac.finalize().into_bytes())
+    );
+}
+
+/// Generates random webhook secret.
+pub fn generate_secret() -> String {
+    return format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+}
+
+/// Starts background task delivering due outbox entries.
+pub fn start_dispatcher(db: DB, webhook_config: WebhookConfig) {
+    tokio::spawn(async move {
+        let client = tls::https_client();
+        let lease = Duration::from_secs(WEBHOOK_CLAIM_LEASE_SECONDS);
+        let mut interval = tokio::time::interval(webhook_config.poll_interval());
+        loop {
+            interval.tick().await;
+            loop {
+                match db.claim_due_outbox_entry(lease).await {
+                    Ok(Some(outbox_entry)) => {
+                        deliver(&db, &client, &webhook_config, outbox_entry).await
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        log::error!("Unable to read webhook outbox: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Attempts one delivery, logs it and schedules retry or dead-letters the entry.
+/// If the outcome can not be saved, the entry is attempted again when its lease expires.
+async fn deliver(
+    db: &DB,
+    client: &Client<HttpsConnector<HttpConnector>>,
+    webhook_config: &WebhookConfig,
+    outbox_entry: OutboxEntry,
+) {
+    let attempt = outbox_entry.attempts + 1;
+    let webhook = match db.get_webhook(&outbox_entry.webhook_id).await {
+        Ok(Some(webhook)) => webhook,
+        Ok(None) => {
+            let _ = db
+                .update_outbox_entry(
+                    &outbox_entry.id,
+                    OUTBOX_DEAD,
+                    outbox_entry.attempts,
+                    Duration::ZERO,
+                    WEBHOOK_DELETED_ERROR,
+                )
+                .await;
+            return;
+        }
+        Err(e) => {
+            log::error!("Unable to read webhook {}: {}", outbox_entry.webhook_id, e);
+            return;
+        }
+    };
+
+    let start = Instant::now();
+    let response = match Request::builder()
+        .method(Method::POST)
+        .uri(&webhook.url)
+        .header(CONTENT_TYPE, JSON_CONTENT_TYPE)
+        .header(WEBHOOK_EVENT_HEADER, &outbox_entry.event_type)
+        .header(WEBHOOK_DELIVERY_HEADER, &outbox_entry.id)
+        .header(
+            WEBHOOK_SIGNATURE_HEADER,
+            sign(&webhook.secret, outbox_entry.payload.as_bytes()),
+        )
+        .body(Body::from(outbox_entry.payload.clone()))
+    {
+        Ok(request) => {
+            match tokio::time::timeout(webhook_config.request_timeout(), client.request(request))
+                .await
+            {
+                Ok(response) => response.map_err(|e| e.to_string()),
+                Err(_) => Err(format!(
+                    "Webhook did not respond within {:?}",
+                    webhook_config.request_timeout()
+                )),
+            }
+        }
+        Err(e) => Err(e.to_string()),
+    };
+    let (success, status_code, error) = match response {
+        Ok(response) if response.status().is_success() => {
+            (true, Some(response.status().as_u16()), String::new())
+        }
+        Ok(response) => (
+            false,
+            Some(response.status().as_u16()),
+            format!("Webhook responded with {}", response.status()),
+        ),
+        Err(e) => (false, None, e),
+    };
+    let delivery = WebhookDelivery {
+        id: String::new(),
+        webhook_id: webhook.id.clone(),
+        outbox_id: outbox_entry.id.clone(),
+        event_type: outbox_entry.event_type.clone(),
+        attempt,
+        success,
+        status_code,
+        error: error.clone(),
+        duration_ms: start.elapsed().as_millis() as u64,
+        delivered_at: String::new(),
+    };
+    if let Err(e) = db.record_webhook_delivery(&delivery).await {
+        log::error!("Unable to record webhook delivery: {}", e);
+    }
+
+    let (status, retry_after) = if success {
+        (OUTBOX_DELIVERED, Duration::ZERO)
+    } else if attempt >= webhook_config.maximum_attempts {
+        log::warn!(
+            "Webhook delivery {} to {} dead-lettered after {} attempts: {}",
+            outbox_entry.id,
+            webhook.url,
+            attempt,
+            error
+        );
+        (OUTBOX_DEAD, Duration::ZERO)
+    } else {
+        (OUTBOX_PENDING, webhook_config.backoff(attempt))
+    };
+    if let Err(e) = db
+        .update_outbox_entry(&outbox_entry.id, status, attempt, retry_after, &error)
+        .await
+    {
+        log::error!("Unable to update webhook outbox: {}", e);
+    }
+}
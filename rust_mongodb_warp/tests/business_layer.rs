@@ -0,0 +1,217 @@
+#![allow(clippy::needless_return)]
+
+/// Property-based tests of percentage and verdict calculation over random budgets and collections.
+use proptest::prelude::*;
+use rust_decimal::{Decimal, RoundingStrategy};
+use rust_mongodb_warp::business_layer::{
+    calculate_financials, calculate_percentage, calculate_verdict, is_territory_breakdown_stale,
+    FinancialFields, PercentageRounding,
+};
+use rust_mongodb_warp::constants::*;
+use rust_mongodb_warp::model::{Market, TerritoryCollection};
+use rust_mongodb_warp::BoxOfficeError;
+
+/// Verdicts from the worst to the best.
+const VERDICTS: [&str; 9] = [
+    LOSS_PERCENTAGE_GREATER_THAN_40,
+    LOSS_PERCENTAGE_GREATER_THAN_20_BUT_LESS_THAN_OR_EQUAL_TO_40,
+    LOSS_PERCENTAGE_GREATER_THAN_10_BUT_LESS_THAN_OR_EQUAL_TO_20,
+    BUDGET_EQUALS_COLLECTION,
+    PROFIT_PERCENTAGE_GREATER_THAN_10_BUT_LESS_THAN_OR_EQUAL_TO_20,
+    PROFIT_PERCENTAGE_GREATER_THAN_20_BUT_LESS_THAN_OR_EQUAL_TO_40,
+    PROFIT_PERCENTAGE_GREATER_THAN_40_BUT_LESS_THAN_OR_EQUAL_TO_80,
+    PROFIT_PERCENTAGE_GREATER_THAN_80_BUT_LESS_THAN_OR_EQUAL_TO_150,
+    PROFIT_PERCENTAGE_GREATER_THAN_150_BUT_LESS_THAN_OR_EQUAL_TO_300,
+];
+
+fn verdict_rank(verdict: &str) -> usize {
+    if verdict == PROFIT_PERCENTAGE_GREATER_THAN_300 {
+        return VERDICTS.len();
+    }
+    return VERDICTS
+        .iter()
+        .position(|known_verdict| *known_verdict == verdict)
+        .unwrap_or_else(|| panic!("unknown verdict '{}'", verdict));
+}
+
+/// Amounts in crores as stored for movies, up to 10^12 with up to 4 decimal places.
+fn amount() -> impl Strategy<Value = Decimal> {
+    return (1i64..=10_000_000_000_000_000, 0u32..=4)
+        .prop_map(|(mantissa, scale)| Decimal::new(mantissa, scale));
+}
+
+/// Any positive Decimal, from 10^-28 up to the largest Decimal.
+fn extreme_amount() -> impl Strategy<Value = Decimal> {
+    return (1u128..(1u128 << 96), 0u32..=28)
+        .prop_map(|(mantissa, scale)| Decimal::from_i128_with_scale(mantissa as i128, scale));
+}
+
+proptest! {
+    #[test]
+    fn percentage_is_exact(budget in amount(), collection in amount()) {
+        let percentage = calculate_percentage(budget, collection, PercentageRounding::EXACT).unwrap();
+        let difference = (collection - budget).abs();
+        // Percentage times budget gives back the difference up to the precision of Decimal.
+        let error = (percentage * budget / Decimal::ONE_HUNDRED - difference).abs();
+        prop_assert!(error <= difference * Decimal::new(1, 20) + Decimal::new(1, 20), "error {}", error);
+    }
+
+    #[test]
+    fn percentage_is_rounded_as_configured(
+        budget in amount(),
+        collection in amount(),
+        decimal_places in 0u32..=6,
+    ) {
+        let exact = calculate_percentage(budget, collection, PercentageRounding::EXACT).unwrap();
+        for strategy in [
+            RoundingStrategy::MidpointNearestEven,
+            RoundingStrategy::MidpointAwayFromZero,
+            RoundingStrategy::ToZero,
+            RoundingStrategy::AwayFromZero,
+        ] {
+            let rounding = PercentageRounding { decimal_places, strategy };
+            let rounded = calculate_percentage(budget, collection, rounding).unwrap();
+            prop_assert!(rounded.scale() <= decimal_places);
+            prop_assert!((rounded - exact).abs() < Decimal::new(1, decimal_places));
+            prop_assert_eq!(rounded, exact.round_dp_with_strategy(decimal_places, strategy));
+        }
+    }
+
+    #[test]
+    fn extreme_ratios_do_not_panic(budget in extreme_amount(), collection in extreme_amount()) {
+        match calculate_percentage(budget, collection, PercentageRounding::VERDICT) {
+            Ok(percentage) => prop_assert!(percentage >= Decimal::ZERO),
+            Err(BoxOfficeError::ErrorinVerdictCalculation(message)) => {
+                prop_assert!(message.contains(&budget.to_string()), "{}", message);
+            }
+            Err(e) => prop_assert!(false, "unexpected error {:?}", e),
+        }
+        match calculate_verdict(budget, collection) {
+            Ok(verdict) => {
+                verdict_rank(&verdict);
+            }
+            Err(e) => prop_assert!(matches!(e, BoxOfficeError::ErrorinVerdictCalculation(_))),
+        }
+        let financials = calculate_financials(budget, collection, &FinancialFields::all());
+        prop_assert!(financials.break_even_gap_crores.unwrap() >= Decimal::ZERO);
+    }
+
+    #[test]
+    fn verdict_is_known_and_never_empty(budget in amount(), collection in amount()) {
+        let verdict = calculate_verdict(budget, collection).unwrap();
+        prop_assert!(!verdict.is_empty());
+        verdict_rank(&verdict);
+    }
+
+    #[test]
+    fn verdict_does_not_worsen_with_higher_collection(
+        budget in amount(),
+        collection in amount(),
+        increase in amount(),
+    ) {
+        let verdict = calculate_verdict(budget, collection).unwrap();
+        let better_verdict = calculate_verdict(budget, collection + increase).unwrap();
+        prop_assert!(verdict_rank(&better_verdict) >= verdict_rank(&verdict));
+    }
+
+    #[test]
+    fn verdict_follows_rounded_percentage(budget in amount(), collection in amount()) {
+        let verdict = calculate_verdict(budget, collection).unwrap();
+        let percentage = calculate_percentage(budget, collection, PercentageRounding::VERDICT).unwrap();
+        if percentage <= Decimal::TEN {
+            prop_assert_eq!(verdict, BUDGET_EQUALS_COLLECTION);
+        } else if collection > budget && percentage > Decimal::from(300) {
+            prop_assert_eq!(verdict, PROFIT_PERCENTAGE_GREATER_THAN_300);
+        } else if collection < budget && percentage > Decimal::from(40) {
+            prop_assert_eq!(verdict, LOSS_PERCENTAGE_GREATER_THAN_40);
+        }
+    }
+
+    #[test]
+    fn financials_are_consistent(budget in amount(), collection in amount()) {
+        let financials = calculate_financials(budget, collection, &FinancialFields::all());
+        let profit = financials.profit_crores.unwrap();
+        prop_assert_eq!(profit, collection - budget);
+        prop_assert_eq!(
+            financials.break_even_gap_crores.unwrap(),
+            (budget - collection).max(Decimal::ZERO)
+        );
+        let profit_percentage = financials.profit_percentage.unwrap();
+        if profit > Decimal::ZERO {
+            prop_assert!(profit_percentage >= Decimal::ZERO);
+        } else {
+            prop_assert!(profit_percentage <= Decimal::ZERO);
+        }
+        let roi_multiple = financials.roi_multiple.unwrap();
+        // Percentage and multiple are rounded to 2 decimal places, so ROI - 1 differs from percentage / 100 by at most 0.01.
+        prop_assert!((roi_multiple - Decimal::ONE - profit_percentage / Decimal::ONE_HUNDRED).abs() <= Decimal::new(1, 2));
+    }
+}
+
+#[test]
+fn sleeper_hit_beyond_u16_percentage_is_all_time_blockbuster() {
+    // 100,000,000% profit used to overflow the u16 percentage.
+    let percentage = calculate_percentage(
+        Decimal::new(1, 2),
+        Decimal::new(1_000_001, 2),
+        PercentageRounding::VERDICT,
+    )
+    .unwrap();
+    assert_eq!(percentage, Decimal::new(100_000_000, 0));
+    assert_eq!(
+        calculate_verdict(Decimal::new(1, 2), Decimal::new(1_000_001, 2)).unwrap(),
+        PROFIT_PERCENTAGE_GREATER_THAN_300
+    );
+}
+
+#[test]
+fn zero_budget_is_verdict_calculation_error() {
+    let error = calculate_verdict(Decimal::ZERO, Decimal::TEN).unwrap_err();
+    match error {
+        BoxOfficeError::ErrorinVerdictCalculation(message) => {
+            assert!(message.contains("budget '0'"), "{}", message)
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn tiny_loss_and_profit_are_average() {
+    assert_eq!(
+        calculate_verdict(Decimal::new(1000, 0), Decimal::new(9999, 1)).unwrap(),
+        BUDGET_EQUALS_COLLECTION
+    );
+    assert_eq!(
+        calculate_verdict(Decimal::new(1000, 0), Decimal::new(10001, 1)).unwrap(),
+        BUDGET_EQUALS_COLLECTION
+    );
+}
+
+#[test]
+fn territory_breakdown_is_stale_once_amounts_change() {
+    let territories: Vec<TerritoryCollection> = [("mumbai", 40, 55), ("usa", 10, 20)]
+        .map(|(territory, budget, collection)| TerritoryCollection {
+            territory: territory.to_string(),
+            market: Market::Domestic,
+            budget_crores: Decimal::new(budget, 0),
+            collection_crores: Decimal::new(collection, 0),
+            verdict: calculate_verdict(Decimal::new(budget, 0), Decimal::new(collection, 0))
+                .unwrap(),
+        })
+        .to_vec();
+    assert!(!is_territory_breakdown_stale(
+        &territories,
+        Decimal::new(50, 0),
+        Decimal::new(75, 0)
+    ));
+    assert!(is_territory_breakdown_stale(
+        &territories,
+        Decimal::new(50, 0),
+        Decimal::new(80, 0)
+    ));
+    assert!(is_territory_breakdown_stale(
+        &territories,
+        Decimal::new(60, 0),
+        Decimal::new(75, 0)
+    ));
+}
@@ -0,0 +1,358 @@
+#![allow(clippy::needless_return)]
+
+/// Tests of the client against the server routes served in-process.
+/// Tests which need MONGODB are ignored, run them with 'cargo test -- --ignored' while MONGODB is running.
+use rust_decimal::Decimal;
+use rust_mongodb_warp::calculate_verdict;
+use rust_mongodb_warp::client::{BoxOfficeClient, ClientError, RetryPolicy};
+use rust_mongodb_warp::config::Config;
+use rust_mongodb_warp::request_response_structs::{
+    CreateMovieRequest, MovieQuery, MovieSortKey, SearchQuery, SortOrder, UpdateMovieRequest,
+};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use warp::{http::StatusCode, Filter};
+
+/// Movie Id used by tests which write to the database.
+const TEST_MOVIE_ID: u8 = 250;
+
+/// Serves 'build_routes' on an ephemeral port and returns its address.
+async fn start_server() -> SocketAddr {
+    let config = Config::load_from("boxoffice_configuration.yaml").unwrap();
+    let routes = rust_mongodb_warp::build_routes(&config).await.unwrap();
+    let (address, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    return address;
+}
+
+async fn start_client() -> BoxOfficeClient {
+    let address = start_server().await;
+    return BoxOfficeClient::new(&format!("http://{}", address))
+        .unwrap()
+        .with_retry_policy(fast_retry_policy());
+}
+
+/// Serves stub routes answering 503 to the first 'failures' requests and
+/// an empty movie list afterwards. Returns client and number of requests received.
+fn start_flaky_server(failures: u32) -> (BoxOfficeClient, Arc<AtomicU32>) {
+    let requests = Arc::new(AtomicU32::new(0));
+    let received = requests.clone();
+    let routes = warp::any().map(move || {
+        let request_number = received.fetch_add(1, Ordering::SeqCst) + 1;
+        if request_number <= failures {
+            return warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({
+                    "success": false,
+                    "error_message": "Service Unavailable",
+                })),
+                StatusCode::SERVICE_UNAVAILABLE,
+            );
+        }
+        return warp::reply::with_status(
+            warp::reply::json(&serde_json::json!({
+                "success": true,
+                "message": " '0' movies fetched.",
+                "data": [],
+            })),
+            StatusCode::OK,
+        );
+    });
+    let (address, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    let client = BoxOfficeClient::new(&format!("http://{}", address))
+        .unwrap()
+        .with_retry_policy(fast_retry_policy());
+    return (client, requests);
+}
+
+/// Serves stub routes answering an empty movie list. Returns client and query strings received.
+fn start_recording_server() -> (BoxOfficeClient, Arc<Mutex<Vec<String>>>) {
+    let queries = Arc::new(Mutex::new(Vec::new()));
+    let received = queries.clone();
+    let routes = warp::query::raw()
+        .or(warp::any().map(String::new))
+        .unify()
+        .map(move |query: String| {
+            received.lock().unwrap().push(query);
+            return warp::reply::json(&serde_json::json!({
+                "success": true,
+                "message": " '0' movies fetched.",
+                "data": [],
+            }));
+        });
+    let (address, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    let client = BoxOfficeClient::new(&format!("http://{}", address)).unwrap();
+    return (client, queries);
+}
+
+fn fast_retry_policy() -> RetryPolicy {
+    return RetryPolicy {
+        maximum_attempts: 3,
+        initial_backoff: Duration::from_millis(1),
+        maximum_backoff: Duration::from_millis(5),
+    };
+}
+
+fn create_movie_request(movie_id: u8) -> CreateMovieRequest {
+    return CreateMovieRequest {
+        movie_id,
+        title: "Client Test".to_string(),
+        production_house: "Client Studios".to_string(),
+        year_of_release: 2020,
+        release_date: None,
+        status: None,
+        budget_crores: Decimal::new(100, 0),
+        collection_crores: Decimal::new(300, 0),
+        currency: None,
+        unit: None,
+        exchange_rate_date: None,
+        metadata: None,
+    };
+}
+
+fn update_movie_request() -> UpdateMovieRequest {
+    return UpdateMovieRequest {
+        title: "Client Test".to_string(),
+        production_house: "Client Studios".to_string(),
+        year_of_release: 2020,
+        release_date: None,
+        status: None,
+        budget_crores: Decimal::new(100, 0),
+        collection_crores: Decimal::new(50, 0),
+        currency: None,
+        unit: None,
+        exchange_rate_date: None,
+        metadata: None,
+    };
+}
+
+#[tokio::test]
+async fn health_endpoints_report_server_alive_and_ready() {
+    let client = start_client().await;
+    client.liveness().await.unwrap();
+    assert!(client.readiness().await.unwrap());
+}
+
+#[tokio::test]
+async fn invalid_create_movie_request_is_validation_error() {
+    let client = start_client().await;
+    let mut request = create_movie_request(0);
+    request.year_of_release = 1800;
+    let error = client.create_movie(&request).await.unwrap_err();
+    match error {
+        ClientError::ValidationError(message) => {
+            assert!(message.contains("movie_id"), "{}", message);
+            assert!(message.contains("year_of_release"), "{}", message);
+        }
+        error => panic!("unexpected error: {:?}", error),
+    }
+}
+
+#[tokio::test]
+async fn invalid_update_movie_request_is_validation_error() {
+    let client = start_client().await;
+    let mut request = update_movie_request();
+    request.budget_crores = Decimal::ZERO;
+    let error = client.update_movie(1, &request).await.unwrap_err();
+    assert!(
+        matches!(error, ClientError::ValidationError(_)),
+        "{:?}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn malformed_ids_are_typed_errors() {
+    let client = start_client().await;
+    let error = client.delete_webhook("not-an-id").await.unwrap_err();
+    assert!(
+        matches!(error, ClientError::InvalidWebhookIDError(_)),
+        "{:?}",
+        error
+    );
+    let error = client
+        .read_webhook_deliveries("not-an-id")
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, ClientError::InvalidWebhookIDError(_)),
+        "{:?}",
+        error
+    );
+    let error = client.retry_dead_letter("not/an/id").await.unwrap_err();
+    assert!(
+        matches!(error, ClientError::InvalidDeadLetterIDError(_)),
+        "{:?}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn unknown_route_is_not_found() {
+    let address = start_server().await;
+    let client = BoxOfficeClient::new(&format!("http://{}/unknown/", address)).unwrap();
+    let error = client.read_movies().await.unwrap_err();
+    assert!(matches!(error, ClientError::NotFound(_)), "{:?}", error);
+}
+
+#[tokio::test]
+async fn invalid_search_query_is_validation_error() {
+    let client = start_client().await;
+    let search_query = SearchQuery {
+        q: "  ".to_string(),
+        limit: None,
+    };
+    let error = client.search_movies(&search_query).await.unwrap_err();
+    assert!(
+        matches!(error, ClientError::ValidationError(_)),
+        "{:?}",
+        error
+    );
+    let suggest_query = SearchQuery {
+        q: "sho".to_string(),
+        limit: Some(0),
+    };
+    let error = client.suggest(&suggest_query).await.unwrap_err();
+    assert!(
+        matches!(error, ClientError::ValidationError(_)),
+        "{:?}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn invalid_currency_is_typed_error() {
+    let client = start_client().await;
+    let query = MovieQuery {
+        currency: Some("DOLLAR".to_string()),
+        ..MovieQuery::default()
+    };
+    let error = client.read_movies_with(&query).await.unwrap_err();
+    assert!(
+        matches!(error, ClientError::InvalidCurrencyError(_)),
+        "{:?}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn movie_query_is_sent_as_query_parameters() {
+    let (client, queries) = start_recording_server();
+    client.read_movies().await.unwrap();
+    let query = MovieQuery {
+        include: Some("profit,roi_multiple".to_string()),
+        currency: Some("USD".to_string()),
+        sort: Some(MovieSortKey::CollectionCrores),
+        order: SortOrder::Desc,
+        genre: Some("drama".to_string()),
+        ..MovieQuery::default()
+    };
+    client.read_movies_with(&query).await.unwrap();
+    assert_eq!(
+        *queries.lock().unwrap(),
+        vec![
+            "order=asc".to_string(),
+            "include=profit%2Croi_multiple&currency=USD&sort=collection_crores&order=desc&genre=drama"
+                .to_string(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn idempotent_request_is_retried_until_success() {
+    let (client, requests) = start_flaky_server(2);
+    let movies = client.read_movies().await.unwrap();
+    assert!(movies.is_empty());
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn retries_stop_at_maximum_attempts() {
+    let (client, requests) = start_flaky_server(u32::MAX);
+    let error = client.read_movies().await.unwrap_err();
+    assert!(
+        matches!(error, ClientError::ServerError { status: 503, .. }),
+        "{:?}",
+        error
+    );
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn non_idempotent_request_is_not_retried_on_error_response() {
+    let (client, requests) = start_flaky_server(1);
+    let error = client
+        .create_movie(&create_movie_request(1))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, ClientError::ServerError { status: 503, .. }),
+        "{:?}",
+        error
+    );
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn connection_failure_is_request_error() {
+    // Port is released before the client connects, so the connection is refused.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    drop(listener);
+    let client = BoxOfficeClient::new(&format!("http://{}", address))
+        .unwrap()
+        .with_retry_policy(fast_retry_policy());
+    let error = client
+        .create_movie(&create_movie_request(1))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ClientError::RequestError(_)), "{:?}", error);
+}
+
+#[tokio::test]
+#[ignore = "needs MONGODB"]
+async fn movie_lifecycle() {
+    let client = start_client().await;
+    let _ = client.delete_movie(TEST_MOVIE_ID).await;
+
+    let created_movie = client
+        .create_movie(&create_movie_request(TEST_MOVIE_ID))
+        .await
+        .unwrap();
+    assert_eq!(created_movie.movie_id, TEST_MOVIE_ID);
+    assert_eq!(
+        created_movie.verdict,
+        calculate_verdict(Decimal::new(100, 0), Decimal::new(300, 0)).unwrap()
+    );
+
+    let error = client
+        .create_movie(&create_movie_request(TEST_MOVIE_ID))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, ClientError::InvalidFieldInCreateMovieRequestError(_)),
+        "{:?}",
+        error
+    );
+
+    let movies = client.read_movies().await.unwrap();
+    assert!(movies.iter().any(|movie| movie.movie_id == TEST_MOVIE_ID));
+
+    let updated_movie = client
+        .update_movie(TEST_MOVIE_ID, &update_movie_request())
+        .await
+        .unwrap();
+    assert_eq!(updated_movie.collection_crores, Decimal::new(50, 0));
+    assert_ne!(updated_movie.verdict, created_movie.verdict);
+
+    client.delete_movie(TEST_MOVIE_ID).await.unwrap();
+    let error = client.delete_movie(TEST_MOVIE_ID).await.unwrap_err();
+    assert!(
+        matches!(error, ClientError::BadDeleteMovieRequestError(_)),
+        "{:?}",
+        error
+    );
+}
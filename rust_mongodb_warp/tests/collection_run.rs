@@ -0,0 +1,125 @@
+#![allow(clippy::needless_return)]
+
+/// Tests of dates, overlap of periods and roll-up of collection entries into the theatrical run.
+use rust_decimal::Decimal;
+use rust_mongodb_warp::business_layer::{calculate_percentage, PercentageRounding};
+use rust_mongodb_warp::collection_run::{
+    collection_run, covered_days, date_of_day, day_number, rolled_up_entries, total_collection,
+};
+use rust_mongodb_warp::model::{CollectionEntry, CollectionPeriod};
+use rust_mongodb_warp::BoxOfficeError;
+
+fn entry(date: &str, period: CollectionPeriod, territory: &str, crores: i64) -> CollectionEntry {
+    return CollectionEntry {
+        id: format!("{}-{}-{}", territory, period, date),
+        movie_id: 7,
+        date: date.to_string(),
+        period,
+        territory: territory.to_string(),
+        collection_crores: Decimal::new(crores, 0),
+        created_at: String::new(),
+    };
+}
+
+/// Domestic weekend with a day it covers and the following week, overseas opening days.
+fn entries() -> Vec<CollectionEntry> {
+    return vec![
+        entry("2023-08-11", CollectionPeriod::Weekend, "domestic", 30),
+        entry("2023-08-12", CollectionPeriod::Day, "domestic", 5),
+        entry("2023-08-18", CollectionPeriod::Week, "domestic", 40),
+        entry("2023-08-11", CollectionPeriod::Day, "overseas", 2),
+        entry("2023-08-12", CollectionPeriod::Day, "overseas", 3),
+        entry("2023-08-13", CollectionPeriod::Day, "overseas", 4),
+    ];
+}
+
+#[test]
+fn day_numbers_count_from_epoch_and_round_trip() {
+    assert_eq!(day_number("1970-01-01").unwrap(), 0);
+    assert_eq!(day_number("1969-12-31").unwrap(), -1);
+    let leap_day = day_number("2024-02-29").unwrap();
+    assert_eq!(date_of_day(leap_day), "2024-02-29");
+    assert_eq!(date_of_day(leap_day + 1), "2024-03-01");
+    assert!(matches!(
+        day_number("2023-02-29"),
+        Err(BoxOfficeError::BadCollectionEntryRequestError(_))
+    ));
+}
+
+#[test]
+fn periods_cover_their_days() {
+    assert_eq!(
+        covered_days("1970-01-01", CollectionPeriod::Day).unwrap(),
+        vec![0]
+    );
+    assert_eq!(
+        covered_days("1970-01-02", CollectionPeriod::Weekend).unwrap(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(
+        covered_days("1970-01-01", CollectionPeriod::Week).unwrap(),
+        (0..7).collect::<Vec<i64>>()
+    );
+}
+
+#[test]
+fn entries_covered_by_longer_period_of_same_territory_are_not_rolled_up() {
+    let entries = entries();
+    let rolled_up: Vec<&str> = rolled_up_entries(&entries)
+        .unwrap()
+        .iter()
+        .map(|entry| entry.id.as_str())
+        .collect();
+    assert_eq!(
+        rolled_up,
+        vec![
+            "overseas-day-2023-08-11",
+            "domestic-weekend-2023-08-11",
+            "overseas-day-2023-08-12",
+            "overseas-day-2023-08-13",
+            "domestic-week-2023-08-18",
+        ]
+    );
+    assert_eq!(total_collection(&entries).unwrap(), Decimal::new(79, 0));
+}
+
+#[test]
+fn run_has_weeks_with_drop_and_opening_weekend() {
+    let run = collection_run(7, &entries()).unwrap();
+    assert_eq!(run.total_collection_crores, Decimal::new(79, 0));
+    // Domestic weekend and all three overseas opening days.
+    assert_eq!(run.opening_weekend_crores, Some(Decimal::new(39, 0)));
+    assert_eq!(run.weeks.len(), 2);
+    assert_eq!(run.weeks[0].start_date, "2023-08-11");
+    assert_eq!(run.weeks[0].collection_crores, Decimal::new(39, 0));
+    assert_eq!(run.weeks[0].drop_percentage, None);
+    assert_eq!(run.weeks[1].start_date, "2023-08-18");
+    assert_eq!(run.weeks[1].collection_crores, Decimal::new(40, 0));
+    // Collection grew, so the drop is negative.
+    let growth = calculate_percentage(
+        Decimal::new(39, 0),
+        Decimal::new(40, 0),
+        PercentageRounding::FINANCIALS,
+    )
+    .unwrap();
+    assert_eq!(run.weeks[1].drop_percentage, Some(-growth));
+}
+
+#[test]
+fn opening_weekend_is_unknown_without_all_opening_days() {
+    let entries = vec![
+        entry("2023-08-11", CollectionPeriod::Day, "domestic", 10),
+        entry("2023-08-12", CollectionPeriod::Day, "domestic", 12),
+    ];
+    let run = collection_run(7, &entries).unwrap();
+    assert_eq!(run.opening_weekend_crores, None);
+    assert_eq!(run.total_collection_crores, Decimal::new(22, 0));
+}
+
+#[test]
+fn run_without_entries_is_empty() {
+    let run = collection_run(7, &[]).unwrap();
+    assert_eq!(run.total_collection_crores, Decimal::ZERO);
+    assert!(run.weeks.is_empty());
+    assert!(run.series.is_empty());
+}
@@ -0,0 +1,150 @@
+#![allow(clippy::needless_return)]
+
+/// Tests of conversion of amounts between currencies and units with the exchange rate table.
+use rust_decimal::Decimal;
+use rust_mongodb_warp::config::ExchangeRatesConfig;
+use rust_mongodb_warp::currency::{from_crores, ExchangeRates};
+use rust_mongodb_warp::model::{AmountUnit, ExchangeRate};
+use rust_mongodb_warp::BoxOfficeError;
+use std::str::FromStr;
+
+/// Table kept only in memory, with two USD rates.
+fn exchange_rates() -> ExchangeRates {
+    let exchange_rates = ExchangeRates::load(&ExchangeRatesConfig { file_path: None }).unwrap();
+    exchange_rates
+        .upsert(&[
+            ExchangeRate {
+                currency: "USD".to_string(),
+                date: "2023-01-01".to_string(),
+                rate_to_inr: Decimal::new(82, 0),
+            },
+            ExchangeRate {
+                currency: "USD".to_string(),
+                date: "2024-01-01".to_string(),
+                rate_to_inr: Decimal::new(83, 0),
+            },
+        ])
+        .unwrap();
+    return exchange_rates;
+}
+
+#[test]
+fn units_are_parsed_displayed_and_valued_in_crores() {
+    for (unit, crores) in [
+        (AmountUnit::Crore, Decimal::ONE),
+        (AmountUnit::Lakh, Decimal::new(1, 2)),
+        (AmountUnit::Million, Decimal::new(1, 1)),
+    ] {
+        assert_eq!(AmountUnit::from_str(&unit.to_string()).unwrap(), unit);
+        assert_eq!(unit.in_crores(), crores);
+    }
+    assert!(matches!(
+        AmountUnit::from_str("billion"),
+        Err(BoxOfficeError::AmountConversionError(_))
+    ));
+}
+
+#[test]
+fn rate_of_date_is_latest_rate_on_or_before_it() {
+    let exchange_rates = exchange_rates();
+    assert_eq!(
+        exchange_rates.rate("INR", None).unwrap(),
+        (Decimal::ONE, None)
+    );
+    assert_eq!(
+        exchange_rates.rate("USD", Some("2023-06-30")).unwrap(),
+        (Decimal::new(82, 0), Some("2023-01-01".to_string()))
+    );
+    assert_eq!(
+        exchange_rates.rate("USD", None).unwrap(),
+        (Decimal::new(83, 0), Some("2024-01-01".to_string()))
+    );
+    assert!(matches!(
+        exchange_rates.rate("USD", Some("2022-12-31")),
+        Err(BoxOfficeError::ExchangeRateNotFoundError(_))
+    ));
+    assert!(matches!(
+        exchange_rates.rate("EUR", None),
+        Err(BoxOfficeError::ExchangeRateNotFoundError(_))
+    ));
+}
+
+#[test]
+fn amounts_are_normalized_to_inr_crores() {
+    let amounts = exchange_rates()
+        .normalize(
+            Decimal::new(10, 0),
+            Decimal::new(25, 0),
+            Some("USD"),
+            Some(AmountUnit::Million),
+            Some("2023-06-30"),
+        )
+        .unwrap();
+    assert_eq!(amounts.budget_crores, Decimal::new(82, 0));
+    assert_eq!(amounts.collection_crores, Decimal::new(205, 0));
+    assert_eq!(amounts.reported_amounts.budget, Decimal::new(10, 0));
+    assert_eq!(amounts.reported_amounts.exchange_rate, Decimal::new(82, 0));
+    assert_eq!(
+        amounts.reported_amounts.exchange_rate_date.as_deref(),
+        Some("2023-01-01")
+    );
+}
+
+#[test]
+fn converted_amounts_are_rounded() {
+    let amounts = exchange_rates()
+        .convert(
+            Decimal::new(83, 0),
+            Decimal::new(100, 0),
+            "USD",
+            AmountUnit::Million,
+        )
+        .unwrap();
+    assert_eq!(amounts.budget, Decimal::new(10, 0));
+    // 100 / 8.3 rounded to four decimal places.
+    assert_eq!(amounts.collection, Decimal::new(120482, 4));
+    assert_eq!(
+        from_crores(Decimal::new(5, 0), "INR", AmountUnit::Lakh, Decimal::ONE).unwrap(),
+        Decimal::new(500, 0)
+    );
+}
+
+#[test]
+fn conversion_out_of_range_is_error() {
+    assert!(matches!(
+        from_crores(Decimal::MAX, "USD", AmountUnit::Lakh, Decimal::new(1, 3)),
+        Err(BoxOfficeError::AmountConversionError(_))
+    ));
+    assert!(matches!(
+        exchange_rates().normalize(
+            Decimal::MAX,
+            Decimal::ONE,
+            Some("USD"),
+            Some(AmountUnit::Crore),
+            None
+        ),
+        Err(BoxOfficeError::AmountConversionError(_))
+    ));
+}
+
+#[test]
+fn rates_are_unchanged_when_they_can_not_be_saved() {
+    let file_path = std::env::temp_dir()
+        .join("boxoffice-missing-directory")
+        .join("exchange_rates.json");
+    let exchange_rates = ExchangeRates::load(&ExchangeRatesConfig {
+        file_path: Some(file_path),
+    })
+    .unwrap();
+    let result = exchange_rates.upsert(&[ExchangeRate {
+        currency: "USD".to_string(),
+        date: "2024-01-01".to_string(),
+        rate_to_inr: Decimal::new(83, 0),
+    }]);
+    assert!(matches!(
+        result,
+        Err(BoxOfficeError::ExchangeRatesFileError(_))
+    ));
+    assert!(exchange_rates.read().is_empty());
+    assert!(exchange_rates.rate("USD", None).is_err());
+}
@@ -0,0 +1,55 @@
+#![allow(clippy::needless_return)]
+
+/// Tests of collection trend over instalments of a franchise summary.
+use rust_decimal::Decimal;
+use rust_mongodb_warp::franchise::summary;
+use rust_mongodb_warp::model::Franchise;
+use rust_mongodb_warp::Movie;
+use std::str::FromStr;
+
+fn movie(movie_id: u8, collection_crores: &str) -> Movie {
+    return serde_json::from_value(serde_json::json!({
+        "id": format!("64b7f0c2a1b2c3d4e5f6071{}", movie_id),
+        "movie_id": movie_id,
+        "title": format!("Dhoom {}", movie_id),
+        "production_house": "Yash Raj Films",
+        "year_of_release": 2003 + u16::from(movie_id) * 3,
+        "status": "released",
+        "budget_crores": "40",
+        "collection_crores": collection_crores,
+        "verdict": "HIT",
+        "verdict_provisional": false,
+    }))
+    .unwrap();
+}
+
+#[test]
+fn collection_change_is_signed_percentage_of_previous_instalment() {
+    let franchise = Franchise {
+        id: String::from("64b7f0c2a1b2c3d4e5f60700"),
+        name: String::from("Dhoom"),
+        movie_ids: vec![1, 2, 3, 4],
+        created_at: String::from("2024-01-01T00:00:00Z"),
+    };
+    let movies = [
+        movie(1, "0"),
+        movie(2, "100"),
+        movie(3, "150"),
+        movie(4, "120"),
+    ];
+    let changes: Vec<Option<Decimal>> = summary(&franchise, &movies)
+        .unwrap()
+        .instalments
+        .iter()
+        .map(|instalment| instalment.collection_change_percentage)
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            None,
+            None,
+            Some(Decimal::from_str("50").unwrap()),
+            Some(Decimal::from_str("-20").unwrap()),
+        ]
+    );
+}
@@ -0,0 +1,87 @@
+#![allow(clippy::needless_return)]
+
+/// Tests of inflation adjustment with the consumer price index table and of saving the table.
+use rust_decimal::Decimal;
+use rust_mongodb_warp::config::InflationConfig;
+use rust_mongodb_warp::inflation::CpiTable;
+use rust_mongodb_warp::model::ConsumerPriceIndex;
+use rust_mongodb_warp::BoxOfficeError;
+use std::str::FromStr;
+
+fn decimal(value: &str) -> Decimal {
+    return Decimal::from_str(value).unwrap();
+}
+
+/// Table kept only in memory, with index of the given years.
+fn cpi_table(cpi: &[(u16, &str)]) -> CpiTable {
+    let cpi_table = CpiTable::load(&InflationConfig {
+        cpi_file_path: None,
+    })
+    .unwrap();
+    let cpi: Vec<ConsumerPriceIndex> = cpi
+        .iter()
+        .map(|(year, index)| ConsumerPriceIndex {
+            year: *year,
+            index: decimal(index),
+        })
+        .collect();
+    cpi_table.upsert(&cpi).unwrap();
+    return cpi_table;
+}
+
+#[test]
+fn amounts_are_adjusted_and_rounded_half_away_from_zero() {
+    let cpi_table = cpi_table(&[(1990, "7"), (2000, "10"), (2023, "3")]);
+    let adjusted = cpi_table
+        .adjust(decimal("10"), decimal("20"), 1990, 2023)
+        .unwrap();
+    assert_eq!(adjusted.base_year, 2023);
+    assert_eq!(adjusted.budget_crores, decimal("4.2857"));
+    assert_eq!(adjusted.collection_crores, decimal("8.5714"));
+    let adjusted = cpi_table
+        .adjust(decimal("0.00005"), decimal("0.00015"), 2000, 2000)
+        .unwrap();
+    assert_eq!(adjusted.budget_crores, decimal("0.0001"));
+    assert_eq!(adjusted.collection_crores, decimal("0.0002"));
+    assert_eq!(cpi_table.latest_year().unwrap(), 2023);
+}
+
+#[test]
+fn missing_cpi_year_is_not_found_error() {
+    let cpi_table = cpi_table(&[(2000, "10")]);
+    assert!(matches!(
+        cpi_table.adjust(decimal("10"), decimal("20"), 1999, 2000),
+        Err(BoxOfficeError::CpiNotFoundError(year)) if year == "1999"
+    ));
+    assert!(matches!(
+        CpiTable::default().latest_year(),
+        Err(BoxOfficeError::CpiNotFoundError(_))
+    ));
+}
+
+#[test]
+fn adjustment_out_of_range_is_error() {
+    let cpi_table = cpi_table(&[(2000, "1"), (2023, "2")]);
+    assert!(matches!(
+        cpi_table.adjust(Decimal::MAX, decimal("20"), 2000, 2023),
+        Err(BoxOfficeError::InflationAdjustmentError(_))
+    ));
+}
+
+#[test]
+fn table_is_unchanged_when_it_can_not_be_saved() {
+    let cpi_file_path = std::env::temp_dir()
+        .join("boxoffice-missing-directory")
+        .join("cpi.json");
+    let cpi_table = CpiTable::load(&InflationConfig {
+        cpi_file_path: Some(cpi_file_path),
+    })
+    .unwrap();
+    let result = cpi_table.upsert(&[ConsumerPriceIndex {
+        year: 2000,
+        index: decimal("10"),
+    }]);
+    assert!(matches!(result, Err(BoxOfficeError::CpiFileError(_))));
+    assert!(cpi_table.read().is_empty());
+    assert!(cpi_table.index(2000).is_err());
+}
@@ -0,0 +1,36 @@
+#![allow(clippy::needless_return)]
+
+/// Tests of spelling of metadata filters matched against stored movie metadata.
+use rust_mongodb_warp::metadata::Vocabularies;
+
+#[test]
+fn filter_values_are_spelled_as_in_vocabularies() {
+    let vocabularies = Vocabularies::default();
+    assert_eq!(
+        vocabularies.filter_values(Some("Action"), Some(" HINDI "), Some("ua 13+"), None),
+        [
+            Some(String::from("action")),
+            Some(String::from("hindi")),
+            Some(String::from("UA 13+")),
+            None
+        ]
+    );
+}
+
+#[test]
+fn tags_are_lowercased_when_any_tag_is_accepted() {
+    let vocabularies = Vocabularies::default();
+    assert_eq!(
+        vocabularies.filter_values(None, None, None, Some("Cult Classic")),
+        [None, None, None, Some(String::from("cult classic"))]
+    );
+}
+
+#[test]
+fn values_not_in_vocabulary_are_kept_as_given() {
+    let vocabularies = Vocabularies::default();
+    assert_eq!(
+        vocabularies.filter_values(Some("Space Opera"), None, None, None),
+        [Some(String::from("Space Opera")), None, None, None]
+    );
+}
@@ -0,0 +1,127 @@
+#![allow(clippy::needless_return)]
+
+use rust_mongodb_warp::error::handle_rejection;
+/// Tests of format negotiation from 'Accept' and 'Content-Type' headers and of CSV rendering of list responses.
+use rust_mongodb_warp::model::{Credit, Role, SearchField, Suggestion};
+use rust_mongodb_warp::negotiation::{
+    request_format, response_format, NegotiatedResponse, RequestFormat, ResponseFormat,
+};
+use warp::http::StatusCode;
+use warp::Filter;
+
+/// Returns format negotiated for the 'Accept' header on a list or non-list route.
+async fn negotiated(accept: &str, is_list: bool) -> Option<ResponseFormat> {
+    return warp::test::request()
+        .header("accept", accept)
+        .filter(&response_format(is_list))
+        .await
+        .ok();
+}
+
+#[tokio::test]
+async fn highest_quality_format_is_chosen() {
+    assert_eq!(
+        negotiated("application/msgpack;q=0.5, application/cbor", false).await,
+        Some(ResponseFormat::Cbor)
+    );
+    assert_eq!(
+        negotiated("application/msgpack;q=0.5, application/cbor;q=0.4", false).await,
+        Some(ResponseFormat::MessagePack)
+    );
+}
+
+#[tokio::test]
+async fn wildcard_matches_first_format_not_refused() {
+    assert_eq!(negotiated("*/*", false).await, Some(ResponseFormat::Json));
+    assert_eq!(
+        negotiated("application/json;q=0, */*", false).await,
+        Some(ResponseFormat::MessagePack)
+    );
+}
+
+#[tokio::test]
+async fn quality_zero_refuses_format() {
+    assert_eq!(negotiated("*/*;q=0", false).await, None);
+    assert_eq!(negotiated("application/cbor;q=0", false).await, None);
+}
+
+#[tokio::test]
+async fn csv_is_not_acceptable_on_non_list_route() {
+    assert_eq!(
+        negotiated("text/csv", true).await,
+        Some(ResponseFormat::Csv)
+    );
+    let route = response_format(false)
+        .map(|_| warp::reply())
+        .recover(handle_rejection);
+    let response = warp::test::request()
+        .header("accept", "text/csv")
+        .reply(&route)
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+}
+
+#[tokio::test]
+async fn unsupported_content_type_is_rejected() {
+    let format = warp::test::request()
+        .header("content-type", "application/cbor")
+        .filter(&request_format())
+        .await
+        .ok();
+    assert_eq!(format, Some(RequestFormat::Cbor));
+    let route = request_format()
+        .map(|_| warp::reply())
+        .recover(handle_rejection);
+    let response = warp::test::request()
+        .header("content-type", "application/xml")
+        .reply(&route)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+fn csv_of(response: &impl NegotiatedResponse) -> String {
+    let bytes = response.to_csv().expect("list response").expect("csv");
+    return String::from_utf8(bytes).unwrap();
+}
+
+#[test]
+fn credits_without_character_keep_character_column() {
+    let credits = vec![
+        Credit {
+            person_id: String::from("p1"),
+            name: String::from("Amitabh Bachchan"),
+            role: Role::Actor,
+            character: Some(String::from("Jai")),
+        },
+        Credit {
+            person_id: String::from("p2"),
+            name: String::from("Ramesh Sippy"),
+            role: Role::Director,
+            character: None,
+        },
+    ];
+    assert_eq!(
+        csv_of(&credits),
+        "person_id,name,role,character\np1,Amitabh Bachchan,actor,Jai\np2,Ramesh Sippy,director,\n"
+    );
+}
+
+#[test]
+fn suggestions_without_movie_keep_movie_id_column() {
+    let suggestions = vec![
+        Suggestion {
+            text: String::from("Dilwale Dulhania Le Jayenge"),
+            field: SearchField::Title,
+            movie_id: Some(3),
+        },
+        Suggestion {
+            text: String::from("Dharma Productions"),
+            field: SearchField::ProductionHouse,
+            movie_id: None,
+        },
+    ];
+    assert_eq!(
+        csv_of(&suggestions),
+        "text,field,movie_id\nDilwale Dulhania Le Jayenge,title,3\nDharma Productions,production_house,\n"
+    );
+}
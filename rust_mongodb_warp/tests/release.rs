@@ -0,0 +1,116 @@
+#![allow(clippy::needless_return)]
+
+/// Tests of release lifecycle checks on status transitions, release dates and years of release.
+use rust_decimal::Decimal;
+use rust_mongodb_warp::constants::*;
+use rust_mongodb_warp::model::ReleaseStatus;
+use rust_mongodb_warp::release::{
+    check_release, check_transition, current_year, maximum_year_of_release, today,
+};
+use rust_mongodb_warp::BoxOfficeError;
+
+/// Statuses in lifecycle order.
+const STATUSES: [ReleaseStatus; 5] = [
+    ReleaseStatus::Announced,
+    ReleaseStatus::InProduction,
+    ReleaseStatus::Released,
+    ReleaseStatus::Running,
+    ReleaseStatus::Closed,
+];
+
+#[test]
+fn status_moves_only_forward() {
+    for (i, status) in STATUSES.iter().enumerate() {
+        for (j, next_status) in STATUSES.iter().enumerate() {
+            let result = check_transition(*status, *next_status);
+            match j >= i {
+                true => assert!(result.is_ok(), "{} to {}", status, next_status),
+                false => assert!(
+                    matches!(result, Err(BoxOfficeError::InvalidStatusTransitionError(_))),
+                    "{} to {}",
+                    status,
+                    next_status
+                ),
+            }
+        }
+    }
+}
+
+#[test]
+fn release_date_must_exist_and_fall_in_year_of_release() {
+    let error = check_release(
+        ReleaseStatus::Released,
+        Some("2023-02-30"),
+        2023,
+        Decimal::ZERO,
+    )
+    .unwrap_err();
+    assert!(matches!(error, BoxOfficeError::ReleaseLifecycleError(_)));
+    let error = check_release(
+        ReleaseStatus::Released,
+        Some("2022-12-31"),
+        2023,
+        Decimal::ZERO,
+    )
+    .unwrap_err();
+    assert!(matches!(error, BoxOfficeError::ReleaseLifecycleError(_)));
+    check_release(
+        ReleaseStatus::Closed,
+        Some("2023-08-11"),
+        2023,
+        Decimal::new(150, 0),
+    )
+    .unwrap();
+}
+
+#[test]
+fn released_movie_is_not_released_in_future() {
+    let next_year = current_year() + 1;
+    let future_date = format!("{}-01-01", next_year);
+    let error = check_release(
+        ReleaseStatus::Released,
+        Some(&future_date),
+        next_year,
+        Decimal::ZERO,
+    )
+    .unwrap_err();
+    assert!(matches!(error, BoxOfficeError::ReleaseLifecycleError(_)));
+    let error = check_release(ReleaseStatus::Running, None, next_year, Decimal::ZERO).unwrap_err();
+    assert!(matches!(error, BoxOfficeError::ReleaseLifecycleError(_)));
+    check_release(
+        ReleaseStatus::Announced,
+        Some(&future_date),
+        next_year,
+        Decimal::ZERO,
+    )
+    .unwrap();
+}
+
+#[test]
+fn movie_not_released_yet_has_no_collection() {
+    match check_release(
+        ReleaseStatus::InProduction,
+        None,
+        current_year(),
+        Decimal::new(1, 0),
+    ) {
+        Err(BoxOfficeError::ReleaseLifecycleError(message)) => {
+            assert!(
+                message.contains(COLLECTION_BEFORE_RELEASE_ERROR),
+                "{}",
+                message
+            )
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+}
+
+#[test]
+fn current_year_is_year_of_today() {
+    assert!(today().starts_with(&format!("{:04}-", current_year())));
+    assert!(current_year() > MINIMUM_YEAR_OF_RELEASE);
+    assert_eq!(
+        maximum_year_of_release(),
+        current_year() + MAXIMUM_YEARS_UNTIL_RELEASE
+    );
+}
@@ -0,0 +1,80 @@
+#![allow(clippy::needless_return)]
+
+/// Tests of request id checks and redaction of credential carrying headers in access logs.
+use hyper::header::{HeaderMap, HeaderValue};
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response};
+use rust_mongodb_warp::constants::*;
+use rust_mongodb_warp::request_context::{handle, is_valid_request_id, redacted_headers};
+use std::convert::Infallible;
+
+/// Returns request id of the response to a request sent with the given 'X-Request-Id' header.
+/// The service echoes the request id it was handed, which must be the one returned.
+async fn returned_request_id(request_id: HeaderValue) -> String {
+    let service = service_fn(|request: Request<Body>| async move {
+        let mut response = Response::new(Body::empty());
+        let request_id = request.headers().get(X_REQUEST_ID).unwrap().clone();
+        response
+            .headers_mut()
+            .insert("x-seen-request-id", request_id);
+        return Ok::<_, Infallible>(response);
+    });
+    let request = Request::builder()
+        .uri("/boxoffice/health")
+        .header(X_REQUEST_ID, request_id)
+        .body(Body::empty())
+        .unwrap();
+    let response = handle(service, None, request).await.unwrap();
+    let returned = response.headers().get(X_REQUEST_ID).unwrap();
+    assert_eq!(
+        returned,
+        response.headers().get("x-seen-request-id").unwrap()
+    );
+    return returned.to_str().unwrap().to_string();
+}
+
+#[tokio::test]
+async fn valid_request_id_is_kept() {
+    assert_eq!(
+        returned_request_id(HeaderValue::from_static("client-42_a.b:c")).await,
+        "client-42_a.b:c"
+    );
+}
+
+#[tokio::test]
+async fn request_id_with_spaces_is_replaced() {
+    for request_id in ["forged id", "forged\tid"] {
+        let returned = returned_request_id(HeaderValue::from_str(request_id).unwrap()).await;
+        assert_ne!(returned, request_id);
+        assert!(is_valid_request_id(&returned));
+    }
+}
+
+#[tokio::test]
+async fn overlong_request_id_is_replaced() {
+    let request_id = "a".repeat(MAXIMUM_REQUEST_ID_LENGTH + 1);
+    let returned = returned_request_id(HeaderValue::from_str(&request_id).unwrap()).await;
+    assert_ne!(returned, request_id);
+    assert!(is_valid_request_id(&returned));
+    assert!(is_valid_request_id(&"a".repeat(MAXIMUM_REQUEST_ID_LENGTH)));
+}
+
+#[test]
+fn request_id_with_line_breaks_is_invalid() {
+    // Header values can not carry line breaks, ids from other sources are checked the same way.
+    assert!(!is_valid_request_id("id\nfake log line"));
+    assert!(!is_valid_request_id("id\r\n"));
+    assert!(!is_valid_request_id(""));
+}
+
+#[test]
+fn credential_headers_are_redacted() {
+    let mut headers = HeaderMap::new();
+    headers.insert("authorization", HeaderValue::from_static("Bearer secret"));
+    headers.insert("cookie", HeaderValue::from_static("session=secret"));
+    headers.insert("accept", HeaderValue::from_static("application/json"));
+    let redacted = redacted_headers(&headers);
+    assert_eq!(redacted["authorization"], REDACTED);
+    assert_eq!(redacted["cookie"], REDACTED);
+    assert_eq!(redacted["accept"], "application/json");
+}
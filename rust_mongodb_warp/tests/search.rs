@@ -0,0 +1,95 @@
+#![allow(clippy::needless_return)]
+
+/// Tests of folding of transliterated spellings and edit distance used by typo-tolerant search.
+use rust_mongodb_warp::events::{EventBus, MovieEvent, MovieEventType};
+use rust_mongodb_warp::search::{edit_distance, fold, tokens, SearchIndex, SearchIndexCache};
+use rust_mongodb_warp::Movie;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[test]
+fn common_transliterations_fold_to_one_spelling() {
+    for (first, second) in [
+        ("Dilwale", "Dilvaale"),
+        ("Sholay", "Sholai"),
+        ("Phool", "Fool"),
+        ("Zanjeer", "Janjir"),
+        ("Chhichhore", "Chichore"),
+        ("Qayamat", "Kayamat"),
+    ] {
+        assert_eq!(fold(first), fold(second), "{} and {}", first, second);
+    }
+    assert_eq!(fold("Dilwale"), "dilvale");
+    assert_eq!(fold("Jack"), "jak");
+}
+
+#[test]
+fn digits_are_kept_as_they_are() {
+    assert_eq!(fold("2002"), "2002");
+    assert_ne!(fold("1942"), fold("942"));
+}
+
+#[test]
+fn tokens_are_folded_words() {
+    assert_eq!(tokens("Om Shanti Om!"), vec!["om", "santi", "om"]);
+    assert!(tokens(" - ").is_empty());
+}
+
+#[test]
+fn edit_distance_counts_edits_and_adjacent_transpositions() {
+    assert_eq!(edit_distance("sholay", "sholay"), 0);
+    assert_eq!(edit_distance("", "dil"), 3);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+    assert_eq!(edit_distance("dil", "dli"), 1);
+    // Optimal string alignment does not edit a transposed pair again.
+    assert_eq!(edit_distance("ca", "abc"), 3);
+    assert_eq!(
+        edit_distance("kitten", "sitting"),
+        edit_distance("sitting", "kitten")
+    );
+}
+
+fn movie(movie_id: u8, title: &str) -> Movie {
+    return serde_json::from_value(serde_json::json!({
+        "id": format!("64b7f0c2a1b2c3d4e5f6071{}", movie_id),
+        "movie_id": movie_id,
+        "title": title,
+        "production_house": "Yash Raj Films",
+        "year_of_release": 1995,
+        "status": "released",
+        "budget_crores": "4",
+        "collection_crores": "102",
+        "verdict": "ALL TIME BLOCKBUSTER",
+        "verdict_provisional": false,
+    }))
+    .unwrap();
+}
+
+#[test]
+fn text_scores_are_added_to_relevance() {
+    let movies = [movie(1, "Dil To Pagal Hai"), movie(2, "Dil Se")];
+    let text_scores = BTreeMap::from([(1, 10.0), (2, 20.0)]);
+    let hits = SearchIndex::new(&movies)
+        .with_text_scores(text_scores)
+        .search("dil", 10);
+    let ranked: Vec<(u8, f64)> = hits.iter().map(|hit| (hit.movie_id, hit.score)).collect();
+    assert_eq!(ranked, vec![(2, 9.0), (1, 6.0)]);
+}
+
+#[test]
+fn cached_index_is_dropped_on_movie_event() {
+    let events = EventBus::default();
+    let cache = SearchIndexCache::new(&events);
+    assert!(cache.get().is_none());
+    let movies = [movie(1, "Dil Se")];
+    cache.set(Arc::new(SearchIndex::new(&movies)));
+    assert_eq!(cache.get().unwrap().search("dil", 10).len(), 1);
+
+    let changed_movie = movie(1, "Dil Se..");
+    events.publish(MovieEvent::new(
+        MovieEventType::Updated,
+        Some(&movies[0]),
+        Some(changed_movie),
+    ));
+    assert!(cache.get().is_none());
+}
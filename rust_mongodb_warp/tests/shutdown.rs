@@ -0,0 +1,133 @@
+#![allow(clippy::needless_return)]
+
+/// Tests of readiness flipping on SIGTERM and draining of in-flight requests.
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use rust_mongodb_warp::shutdown::{run_until_drained, shutdown_signal, Readiness};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::oneshot;
+
+/// Starts a server whose every request takes 'handling_time' to answer.
+/// Returns its address, the server future and the sender which starts graceful shutdown.
+fn slow_server(
+    handling_time: Duration,
+) -> (
+    SocketAddr,
+    impl std::future::Future<Output = ()>,
+    oneshot::Sender<()>,
+) {
+    let make_service = make_service_fn(move |_| async move {
+        Ok::<_, Infallible>(service_fn(move |_request: Request<Body>| async move {
+            tokio::time::sleep(handling_time).await;
+            Ok::<_, Infallible>(Response::new(Body::from("done")))
+        }))
+    });
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+    let address = server.local_addr();
+    let (stop_sender, stop_receiver) = oneshot::channel::<()>();
+    let server = server.with_graceful_shutdown(async {
+        let _ = stop_receiver.await;
+    });
+    return (
+        address,
+        async move {
+            let _ = server.await;
+        },
+        stop_sender,
+    );
+}
+
+/// Sends a GET request without waiting for the response.
+async fn send_request(address: SocketAddr) -> TcpStream {
+    let mut stream = TcpStream::connect(address).await.unwrap();
+    stream
+        .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    return stream;
+}
+
+#[tokio::test]
+async fn in_flight_request_is_drained_before_server_stops() {
+    let (address, server, stop_sender) = slow_server(Duration::from_millis(300));
+    let (shutdown_started_sender, shutdown_started_receiver) = oneshot::channel();
+    let running_server = tokio::spawn(run_until_drained(
+        server,
+        shutdown_started_receiver,
+        Duration::from_secs(10),
+    ));
+
+    let mut stream = send_request(address).await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    stop_sender.send(()).unwrap();
+    shutdown_started_sender.send(()).unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+    assert!(response.ends_with("done"), "{}", response);
+    tokio::time::timeout(Duration::from_secs(5), running_server)
+        .await
+        .expect("server did not stop after draining")
+        .unwrap();
+}
+
+#[tokio::test]
+async fn drain_timeout_stops_server_with_slow_requests() {
+    let (address, server, stop_sender) = slow_server(Duration::from_secs(60));
+    let (shutdown_started_sender, shutdown_started_receiver) = oneshot::channel();
+    let running_server = tokio::spawn(run_until_drained(
+        server,
+        shutdown_started_receiver,
+        Duration::from_millis(200),
+    ));
+
+    let _stream = send_request(address).await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let shutdown_started_at = Instant::now();
+    stop_sender.send(()).unwrap();
+    shutdown_started_sender.send(()).unwrap();
+
+    tokio::time::timeout(Duration::from_secs(5), running_server)
+        .await
+        .expect("drain timeout did not stop the server")
+        .unwrap();
+    assert!(shutdown_started_at.elapsed() >= Duration::from_millis(200));
+}
+
+#[tokio::test]
+async fn sigterm_flips_readiness_before_draining_starts() {
+    let readiness = Readiness::default();
+    readiness.set_ready();
+    let (shutdown_started_sender, mut shutdown_started_receiver) = oneshot::channel();
+    tokio::spawn(shutdown_signal(
+        readiness.clone(),
+        Duration::from_millis(300),
+        shutdown_started_sender,
+    ));
+    // Let the signal handlers be installed before the signal is sent.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let status = std::process::Command::new("kill")
+        .args(["-TERM", &std::process::id().to_string()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while readiness.is_ready() {
+        assert!(Instant::now() < deadline, "readiness did not flip");
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(
+        shutdown_started_receiver.try_recv().is_err(),
+        "draining started before the readiness grace period"
+    );
+    tokio::time::timeout(Duration::from_secs(5), shutdown_started_receiver)
+        .await
+        .expect("draining did not start")
+        .unwrap();
+}
@@ -0,0 +1,129 @@
+#![allow(clippy::needless_return)]
+
+/// Tests of span export to a stand-in OTLP/HTTP collector and to a JSON file.
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use opentelemetry::trace::{Span, Tracer, TracerProvider as _};
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::trace::TracerProvider;
+use rust_mongodb_warp::config::{TracingConfig, TracingExporter};
+use rust_mongodb_warp::constants::TRACER_NAME;
+use rust_mongodb_warp::telemetry::{self, JsonFileSpanExporter};
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Request received by the stand-in collector.
+struct CollectedRequest {
+    path: String,
+    content_type: String,
+    body: Vec<u8>,
+}
+
+/// Starts a collector which accepts every export and keeps the requests.
+fn start_collector() -> (String, Arc<Mutex<Vec<CollectedRequest>>>) {
+    let collected = Arc::new(Mutex::new(Vec::new()));
+    let collected_by_service = collected.clone();
+    let make_service = make_service_fn(move |_| {
+        let collected = collected_by_service.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                let collected = collected.clone();
+                async move {
+                    let path = request.uri().path().to_string();
+                    let content_type = request
+                        .headers()
+                        .get(hyper::header::CONTENT_TYPE)
+                        .and_then(|value| value.to_str().ok())
+                        .unwrap_or_default()
+                        .to_string();
+                    let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                    collected.lock().unwrap().push(CollectedRequest {
+                        path,
+                        content_type,
+                        body: body.to_vec(),
+                    });
+                    Ok::<_, Infallible>(Response::new(Body::empty()))
+                }
+            }))
+        }
+    });
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+    let endpoint = format!("http://{}", server.local_addr());
+    tokio::spawn(server);
+    return (endpoint, collected);
+}
+
+fn contains(haystack: &[u8], needle: &str) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle.as_bytes())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn otlp_exporter_sends_spans_to_collector() {
+    let (endpoint, collected) = start_collector();
+    let tracing_config = TracingConfig {
+        exporter: TracingExporter::Otlp,
+        service_name: "boxoffice_test".to_string(),
+        otlp_endpoint: endpoint,
+        ..TracingConfig::default()
+    };
+    telemetry::init(&tracing_config).unwrap();
+
+    tracing::info_span!(target: TRACER_NAME, "collector_test_span").in_scope(|| {});
+    // Shutdown waits for the batch exporter, which runs on the runtime.
+    tokio::task::spawn_blocking(telemetry::shutdown)
+        .await
+        .unwrap();
+
+    let collected = collected.lock().unwrap();
+    let export = collected
+        .iter()
+        .find(|request| contains(&request.body, "collector_test_span"))
+        .expect("span was not exported");
+    assert_eq!(export.path, "/v1/traces");
+    assert_eq!(export.content_type, "application/x-protobuf");
+    assert!(contains(&export.body, "boxoffice_test"));
+}
+
+#[tokio::test]
+async fn json_file_exporter_appends_one_line_per_span() {
+    let path = std::env::temp_dir().join(format!(
+        "boxoffice_traces_{}/traces.json",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    let tracer_provider = TracerProvider::builder()
+        .with_simple_exporter(JsonFileSpanExporter::new(path.clone()))
+        .build();
+    let tracer = tracer_provider.tracer(TRACER_NAME);
+    tracer.in_span("parent_span", |_| {
+        let mut child_span = tracer.start("child_span");
+        child_span.set_attribute(KeyValue::new("db.operation", "find"));
+        child_span.end();
+    });
+    tracer_provider.force_flush();
+
+    let contents = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let contents = std::fs::read_to_string(&path).unwrap_or_default();
+            if contents.lines().count() == 2 {
+                return contents;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("spans were not written");
+    let spans: Vec<serde_json::Value> = contents
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let (child_span, parent_span) = (&spans[0], &spans[1]);
+    assert_eq!(child_span["name"], "child_span");
+    assert_eq!(parent_span["name"], "parent_span");
+    assert_eq!(child_span["trace_id"], parent_span["trace_id"]);
+    assert_eq!(child_span["parent_span_id"], parent_span["span_id"]);
+    assert_eq!(child_span["attributes"]["db.operation"], "find");
+}
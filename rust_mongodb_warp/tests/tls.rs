@@ -0,0 +1,184 @@
+#![allow(clippy::needless_return)]
+
+/// Tests of TLS handshakes, handshake timeout and reloading of certificates and client CA certificates.
+use futures::{Stream, StreamExt};
+use rcgen::{BasicConstraints, Certificate, CertificateParams, IsCa};
+use rust_mongodb_warp::config::{ClientAuth, TlsConfig};
+use rust_mongodb_warp::tls::{incoming, ReloadingServerConfig};
+use rustls::{ClientConfig, PrivateKey, RootCertStore, ServerName};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::{client, server, TlsConnector};
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_millis(500);
+
+fn ca_certificate() -> Certificate {
+    let mut params = CertificateParams::new(Vec::new());
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    return Certificate::from_params(params).unwrap();
+}
+
+fn leaf_certificate() -> Certificate {
+    return Certificate::from_params(CertificateParams::new(vec!["localhost".to_string()]))
+        .unwrap();
+}
+
+/// Directory of PEM files of one test.
+fn test_directory(name: &str) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("boxoffice_tls_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    return directory;
+}
+
+/// Writes server certificate signed by 'ca' and its key, and 'client_ca' as client CA certificate.
+fn write_server_files(directory: &Path, ca: &Certificate, client_ca: &Certificate) -> TlsConfig {
+    let server = leaf_certificate();
+    let tls_config = TlsConfig {
+        certificate_path: directory.join("server.crt"),
+        private_key_path: directory.join("server.key"),
+        client_ca_path: Some(directory.join("client_ca.crt")),
+        client_auth: ClientAuth::Required,
+        certificate_reload_interval_seconds: 0,
+        handshake_timeout_seconds: 0,
+    };
+    std::fs::write(
+        &tls_config.certificate_path,
+        server.serialize_pem_with_signer(ca).unwrap(),
+    )
+    .unwrap();
+    std::fs::write(
+        &tls_config.private_key_path,
+        server.serialize_private_key_pem(),
+    )
+    .unwrap();
+    std::fs::write(
+        tls_config.client_ca_path.as_ref().unwrap(),
+        client_ca.serialize_pem().unwrap(),
+    )
+    .unwrap();
+    return tls_config;
+}
+
+/// Starts accepting TLS connections, returns listening address and accepted connections.
+async fn start(
+    server_config: Arc<ReloadingServerConfig>,
+) -> (
+    SocketAddr,
+    impl Stream<Item = std::io::Result<server::TlsStream<TcpStream>>>,
+) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    return (
+        address,
+        incoming(listener, server_config, HANDSHAKE_TIMEOUT),
+    );
+}
+
+/// Connects trusting 'ca' and presenting a client certificate signed by 'client_ca'.
+async fn connect(
+    address: SocketAddr,
+    ca: &Certificate,
+    client_ca: &Certificate,
+) -> std::io::Result<client::TlsStream<TcpStream>> {
+    let mut roots = RootCertStore::empty();
+    roots
+        .add(&rustls::Certificate(ca.serialize_der().unwrap()))
+        .unwrap();
+    let client = leaf_certificate();
+    let client_config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_client_auth_cert(
+            vec![rustls::Certificate(
+                client.serialize_der_with_signer(client_ca).unwrap(),
+            )],
+            PrivateKey(client.serialize_private_key_der()),
+        )
+        .unwrap();
+    let tcp_stream = TcpStream::connect(address).await?;
+    return TlsConnector::from(Arc::new(client_config))
+        .connect(ServerName::try_from("localhost").unwrap(), tcp_stream)
+        .await;
+}
+
+/// Whether the server yields a connection within a second.
+async fn is_accepted(
+    connections: &mut (impl Stream<Item = std::io::Result<server::TlsStream<TcpStream>>> + Unpin),
+) -> bool {
+    return matches!(
+        tokio::time::timeout(Duration::from_secs(1), connections.next()).await,
+        Ok(Some(Ok(_)))
+    );
+}
+
+#[tokio::test]
+async fn client_with_certificate_of_client_ca_is_accepted() {
+    let (ca, client_ca) = (ca_certificate(), ca_certificate());
+    let tls_config = write_server_files(&test_directory("accepted"), &ca, &client_ca);
+    let server_config = Arc::new(ReloadingServerConfig::new(&tls_config).unwrap());
+    let (address, connections) = start(server_config).await;
+    let mut connections = Box::pin(connections);
+
+    let _client = connect(address, &ca, &client_ca).await.unwrap();
+    assert!(is_accepted(&mut connections).await);
+}
+
+#[tokio::test]
+async fn incomplete_handshake_is_closed_after_timeout() {
+    let (ca, client_ca) = (ca_certificate(), ca_certificate());
+    let tls_config = write_server_files(&test_directory("timeout"), &ca, &client_ca);
+    let server_config = Arc::new(ReloadingServerConfig::new(&tls_config).unwrap());
+    let (address, _connections) = start(server_config).await;
+
+    // Connect without ever sending ClientHello.
+    let mut tcp_stream = TcpStream::connect(address).await.unwrap();
+    let mut buffer = [0u8; 16];
+    let read = tokio::time::timeout(HANDSHAKE_TIMEOUT * 4, tcp_stream.read(&mut buffer))
+        .await
+        .expect("connection was not closed after handshake timeout");
+    assert!(matches!(read, Ok(0) | Err(_)));
+}
+
+#[tokio::test]
+async fn reload_picks_up_new_certificate_and_client_ca() {
+    let (ca, client_ca) = (ca_certificate(), ca_certificate());
+    let directory = test_directory("reload");
+    let tls_config = write_server_files(&directory, &ca, &client_ca);
+    let server_config = Arc::new(ReloadingServerConfig::new(&tls_config).unwrap());
+    let (address, connections) = start(server_config.clone()).await;
+    let mut connections = Box::pin(connections);
+
+    let (new_ca, new_client_ca) = (ca_certificate(), ca_certificate());
+    write_server_files(&directory, &new_ca, &new_client_ca);
+    server_config.reload().unwrap();
+
+    // Server certificate signed by the old CA is no longer presented.
+    assert!(connect(address, &ca, &new_client_ca).await.is_err());
+    // Client certificates signed by the old client CA are no longer accepted.
+    let _rejected_client = connect(address, &new_ca, &client_ca).await;
+    assert!(!is_accepted(&mut connections).await);
+
+    let _client = connect(address, &new_ca, &new_client_ca).await.unwrap();
+    assert!(is_accepted(&mut connections).await);
+}
+
+#[tokio::test]
+async fn failed_reload_keeps_previous_configuration() {
+    let (ca, client_ca) = (ca_certificate(), ca_certificate());
+    let directory = test_directory("failed_reload");
+    let tls_config = write_server_files(&directory, &ca, &client_ca);
+    let server_config = Arc::new(ReloadingServerConfig::new(&tls_config).unwrap());
+    let (address, connections) = start(server_config.clone()).await;
+    let mut connections = Box::pin(connections);
+
+    std::fs::write(&tls_config.private_key_path, "not a key").unwrap();
+    assert!(server_config.reload().is_err());
+
+    let _client = connect(address, &ca, &client_ca).await.unwrap();
+    assert!(is_accepted(&mut connections).await);
+}
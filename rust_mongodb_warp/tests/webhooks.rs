@@ -0,0 +1,120 @@
+#![allow(clippy::needless_return)]
+
+/// Tests of webhook signing, retry backoff and events raised by movie changes.
+use rust_mongodb_warp::config::WebhookConfig;
+use rust_mongodb_warp::constants::*;
+use rust_mongodb_warp::events::{MovieEvent, MovieEventType};
+use rust_mongodb_warp::webhooks::{event_types, payload, sign};
+use rust_mongodb_warp::Movie;
+use std::time::Duration;
+
+fn movie(verdict: &str) -> Movie {
+    return serde_json::from_value(serde_json::json!({
+        "id": "64b7f0c2a1b2c3d4e5f60718",
+        "movie_id": 7,
+        "title": "Sholay",
+        "production_house": "Sippy Films",
+        "year_of_release": 1975,
+        "status": "released",
+        "budget_crores": "3",
+        "collection_crores": "15",
+        "verdict": verdict,
+        "verdict_provisional": false,
+    }))
+    .unwrap();
+}
+
+#[test]
+fn signature_is_hex_hmac_sha256_of_body() {
+    // Known HMAC-SHA256 value of this key and message.
+    assert_eq!(
+        sign("key", b"The quick brown fox jumps over the lazy dog"),
+        "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+    );
+}
+
+#[test]
+fn signature_depends_on_secret_and_body() {
+    let signature = sign("secret", b"{\"id\":\"1\"}");
+    assert!(signature.starts_with(WEBHOOK_SIGNATURE_PREFIX));
+    assert_ne!(signature, sign("other secret", b"{\"id\":\"1\"}"));
+    assert_ne!(signature, sign("secret", b"{\"id\":\"2\"}"));
+}
+
+#[test]
+fn backoff_doubles_after_every_attempt_up_to_maximum() {
+    let webhook_config = WebhookConfig {
+        initial_backoff_seconds: 10,
+        maximum_backoff_seconds: 60,
+        ..WebhookConfig::default()
+    };
+    let backoffs: Vec<Duration> = (1..=5)
+        .map(|attempts| webhook_config.backoff(attempts))
+        .collect();
+    assert_eq!(
+        backoffs,
+        [10, 20, 40, 60, 60].map(Duration::from_secs).to_vec()
+    );
+}
+
+#[test]
+fn backoff_does_not_overflow_after_many_attempts() {
+    let webhook_config = WebhookConfig {
+        initial_backoff_seconds: u64::MAX / 2,
+        maximum_backoff_seconds: u64::MAX,
+        ..WebhookConfig::default()
+    };
+    assert_eq!(webhook_config.backoff(0), Duration::from_secs(u64::MAX / 2));
+    assert_eq!(
+        webhook_config.backoff(u32::MAX),
+        Duration::from_secs(u64::MAX)
+    );
+}
+
+#[test]
+fn created_and_deleted_movies_raise_one_event() {
+    let created = MovieEvent::new(MovieEventType::Created, None, Some(movie("HIT")));
+    assert_eq!(event_types(&created), vec![WEBHOOK_EVENT_CREATED]);
+    let deleted = MovieEvent::new(MovieEventType::Deleted, Some(&movie("HIT")), None);
+    assert_eq!(event_types(&deleted), vec![WEBHOOK_EVENT_DELETED]);
+}
+
+#[test]
+fn update_changing_verdict_also_raises_verdict_changed() {
+    let unchanged = MovieEvent::new(
+        MovieEventType::Updated,
+        Some(&movie("HIT")),
+        Some(movie("HIT")),
+    );
+    assert_eq!(event_types(&unchanged), vec![WEBHOOK_EVENT_UPDATED]);
+    let changed = MovieEvent::new(
+        MovieEventType::Updated,
+        Some(&movie("HIT")),
+        Some(movie("SUPER HIT")),
+    );
+    assert_eq!(
+        event_types(&changed),
+        vec![WEBHOOK_EVENT_UPDATED, WEBHOOK_EVENT_VERDICT_CHANGED]
+    );
+}
+
+#[test]
+fn payload_carries_delivery_id_event_type_and_movie_event() {
+    let event = MovieEvent::new(
+        MovieEventType::Updated,
+        Some(&movie("HIT")),
+        Some(movie("SUPER HIT")),
+    );
+    let body: serde_json::Value = serde_json::from_str(&payload(
+        "delivery-1",
+        WEBHOOK_EVENT_VERDICT_CHANGED,
+        &event,
+    ))
+    .unwrap();
+    assert_eq!(body["id"], "delivery-1");
+    assert_eq!(body["event"], WEBHOOK_EVENT_VERDICT_CHANGED);
+    assert_eq!(body["timestamp"], event.timestamp);
+    assert_eq!(body["data"]["movie_id"], 7);
+    assert_eq!(body["data"]["old_verdict"], "HIT");
+    assert_eq!(body["data"]["new_verdict"], "SUPER HIT");
+}